@@ -0,0 +1,14 @@
+//! Helpers shared by the benchmarks.
+
+/// Generate pseudo-random values so the benchmark isn’t just one branch.
+pub fn values() -> impl Iterator<Item = u64> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    core::iter::repeat_with(move || {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state >> 8
+    })
+    .take(1000)
+}
@@ -0,0 +1,102 @@
+//! Benchmark rounding integers to power of two and other factors, with and
+//! without a [`Rounder`].
+
+// criterion_group! generates an undocumented function.
+#![allow(missing_docs)]
+
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion,
+};
+use roundable::{Direction, Precomputable, Rounder, Tie};
+
+mod common;
+use common::values;
+
+/// Factors to benchmark: a power of two and a similar non-power of two.
+const FACTORS: [u16; 2] = [4096, 4000];
+
+/// Benchmark rounding `values` to each of `factors`, with and without a
+/// [`Rounder`].
+fn round<T>(c: &mut Criterion, name: &str, values: &[T], factors: &[T])
+where
+    T: Precomputable + std::fmt::Display,
+{
+    let mut group = c.benchmark_group(name);
+    for &factor in factors {
+        group.bench_with_input(
+            BenchmarkId::new("TowardEven", factor),
+            &factor,
+            |b, &factor| {
+                b.iter(|| {
+                    for &value in values {
+                        black_box(
+                            black_box(value).try_round_to(
+                                black_box(factor),
+                                Tie::TowardEven,
+                            ),
+                        );
+                    }
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Down", factor),
+            &factor,
+            |b, &factor| {
+                b.iter(|| {
+                    for &value in values {
+                        black_box(black_box(value).try_round_directed_to(
+                            black_box(factor),
+                            Direction::Down,
+                        ));
+                    }
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Rounder TowardEven", factor),
+            &factor,
+            |b, &factor| {
+                let rounder = Rounder::new(black_box(factor)).unwrap();
+                b.iter(|| {
+                    for &value in values {
+                        black_box(
+                            rounder
+                                .try_round(black_box(value), Tie::TowardEven),
+                        );
+                    }
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Rounder Down", factor),
+            &factor,
+            |b, &factor| {
+                let rounder = Rounder::new(black_box(factor)).unwrap();
+                b.iter(|| {
+                    for &value in values {
+                        black_box(rounder.try_round_directed(
+                            black_box(value),
+                            Direction::Down,
+                        ));
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Benchmark rounding `u64`s and `i64`s, half of which are negative.
+fn round_integers(c: &mut Criterion) {
+    let u64s: Vec<u64> = values().collect();
+    round(c, "u64", &u64s, &FACTORS.map(u64::from));
+
+    let i64s: Vec<i64> = values()
+        .map(|value| i64::try_from(value).unwrap().wrapping_sub(1 << 55))
+        .collect();
+    round(c, "i64", &i64s, &FACTORS.map(i64::from));
+}
+
+criterion_group!(benches, round_integers);
+criterion_main!(benches);
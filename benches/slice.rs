@@ -0,0 +1,73 @@
+//! Benchmark rounding slices with [`SliceRoundable`] against rounding each
+//! value with [`try_round_to()`](roundable::Roundable::try_round_to()).
+
+// criterion_group! generates an undocumented function.
+#![allow(missing_docs)]
+
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion,
+};
+use roundable::{SliceRoundable, Tie};
+
+mod common;
+use common::values;
+
+/// Benchmark rounding `values` to `factor` one at a time and as a slice.
+fn round<T>(c: &mut Criterion, name: &str, values: &[T], factor: T)
+where
+    T: SliceRoundable + std::fmt::Display,
+{
+    let mut group = c.benchmark_group(name);
+    group.bench_with_input(
+        BenchmarkId::new("try_round_to", factor),
+        &factor,
+        |b, &factor| {
+            let mut output = values.to_vec();
+            b.iter(|| {
+                for (&value, rounded) in values.iter().zip(&mut output) {
+                    *rounded = black_box(value)
+                        .try_round_to(black_box(factor), Tie::TowardEven)
+                        .unwrap();
+                }
+                black_box(&output);
+            });
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("try_round_slice_into", factor),
+        &factor,
+        |b, &factor| {
+            let mut output = values.to_vec();
+            b.iter(|| {
+                T::try_round_slice_into(
+                    black_box(values),
+                    &mut output,
+                    black_box(factor),
+                    Tie::TowardEven,
+                )
+                .unwrap();
+                black_box(&output);
+            });
+        },
+    );
+    group.finish();
+}
+
+/// Benchmark rounding slices of the types we care most about.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn round_slices(c: &mut Criterion) {
+    let f32s: Vec<f32> = values().map(|n| (n % 100_000) as f32 / 7.0).collect();
+    round(c, "f32", &f32s, 0.25);
+    round(c, "f32", &f32s, 0.1);
+
+    let i64s: Vec<i64> = values()
+        .map(|value| i64::try_from(value).unwrap().wrapping_sub(1 << 55))
+        .collect();
+    round(c, "i64", &i64s, 4000);
+
+    let u32s: Vec<u32> = values().map(|n| n as u32 >> 1).collect();
+    round(c, "u32", &u32s, 4000);
+}
+
+criterion_group!(benches, round_slices);
+criterion_main!(benches);
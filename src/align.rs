@@ -0,0 +1,265 @@
+//! Align unsigned integers to powers of two.
+
+use crate::{rounded_or_none, unwrap_rounded, RoundError};
+
+/// Methods to align offsets and sizes to a power of two.
+///
+/// This is implemented for unsigned integers. Aligning is the same as
+/// rounding with
+/// [`Roundable::round_directed_to()`](crate::Roundable::round_directed_to()),
+/// but the alignment must be a power of two, which makes it possible to use
+/// bit masks instead of division.
+///
+/// ```rust
+/// use roundable::Alignable;
+///
+/// assert!(4096 == 1usize.align_up(4096));
+/// assert!(4096 == 4097usize.align_down(4096));
+/// assert!(8192usize.is_aligned(4096));
+/// ```
+pub trait Alignable: Copy {
+    /// Round up to the next multiple of `align`. Panics if there is an
+    /// overflow.
+    ///
+    /// ```rust
+    /// use roundable::Alignable;
+    ///
+    /// assert!(16 == 9u8.align_up(8));
+    /// assert!(16 == 16u8.align_up(8));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two, or if the result does not fit
+    /// in the return type.
+    #[must_use]
+    fn align_up(self, align: Self) -> Self {
+        unwrap_rounded(self.checked_align_up(align), "try_align_up")
+    }
+
+    /// Round up to the next multiple of `align`. Returns `None` if there is an
+    /// overflow.
+    ///
+    /// ```rust
+    /// use roundable::Alignable;
+    ///
+    /// assert!(Some(248) == 241u8.try_align_up(8));
+    /// assert!(None == 249u8.try_align_up(8));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[must_use]
+    fn try_align_up(self, align: Self) -> Option<Self> {
+        rounded_or_none(self.checked_align_up(align), "try_align_up")
+    }
+
+    /// Round up to the next multiple of `align`. Returns a [`RoundError`] on
+    /// failure.
+    ///
+    /// ```rust
+    /// use roundable::{Alignable, RoundError};
+    ///
+    /// assert!(Ok(248) == 241u8.checked_align_up(8));
+    /// assert!(Err(RoundError::Overflow) == 249u8.checked_align_up(8));
+    /// assert!(Err(RoundError::InvalidAlignment) == 249u8.checked_align_up(6));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::InvalidAlignment`] if `align` is not a power of
+    /// two, and [`RoundError::Overflow`] if the result does not fit in the
+    /// return type.
+    fn checked_align_up(self, align: Self) -> Result<Self, RoundError>;
+
+    /// Round down to the previous multiple of `align`.
+    ///
+    /// ```rust
+    /// use roundable::Alignable;
+    ///
+    /// assert!(8 == 15u8.align_down(8));
+    /// assert!(16 == 16u8.align_down(8));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[must_use]
+    fn align_down(self, align: Self) -> Self {
+        unwrap_rounded(self.checked_align_down(align), "try_align_down")
+    }
+
+    /// Round down to the previous multiple of `align`. Aligning down can’t
+    /// overflow, so this always returns `Some`, but it matches
+    /// [`Alignable::try_align_up()`].
+    ///
+    /// ```rust
+    /// use roundable::Alignable;
+    ///
+    /// assert!(Some(240) == 241u8.try_align_down(8));
+    /// assert!(Some(0) == 7u8.try_align_down(8));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[must_use]
+    fn try_align_down(self, align: Self) -> Option<Self> {
+        rounded_or_none(self.checked_align_down(align), "try_align_down")
+    }
+
+    /// Round down to the previous multiple of `align`. Returns a
+    /// [`RoundError`] on failure.
+    ///
+    /// ```rust
+    /// use roundable::{Alignable, RoundError};
+    ///
+    /// assert!(Ok(8) == 15u8.checked_align_down(8));
+    /// assert!(Err(RoundError::InvalidAlignment) == 15u8.checked_align_down(0));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::InvalidAlignment`] if `align` is not a power of
+    /// two.
+    fn checked_align_down(self, align: Self) -> Result<Self, RoundError>;
+
+    /// Check if the value is a multiple of `align`.
+    ///
+    /// ```rust
+    /// use roundable::Alignable;
+    ///
+    /// assert!(16u8.is_aligned(8));
+    /// assert!(!12u8.is_aligned(8));
+    /// assert!(0u8.is_aligned(8));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[must_use]
+    fn is_aligned(self, align: Self) -> bool;
+}
+
+/// Implement alignment for unsigned integer types.
+macro_rules! alignable_integer {
+    ($($ty:ident)+) => {$(
+        impl Alignable for $ty {
+            fn checked_align_up(self, align: Self) -> Result<Self, RoundError> {
+                let mask = align.alignment_mask()?;
+                self.checked_add(mask)
+                    .map(|value| value & !mask)
+                    .ok_or(RoundError::Overflow)
+            }
+
+            fn checked_align_down(
+                self,
+                align: Self,
+            ) -> Result<Self, RoundError> {
+                Ok(self & !align.alignment_mask()?)
+            }
+
+            fn is_aligned(self, align: Self) -> bool {
+                let mask = unwrap_rounded(align.alignment_mask(), "is_aligned");
+                self & mask == 0
+            }
+        }
+
+        impl AlignmentMask for $ty {
+            #[allow(clippy::arithmetic_side_effects)]
+            fn alignment_mask(self) -> Result<Self, RoundError> {
+                if self.is_power_of_two() {
+                    // Safe: self is at least 1.
+                    Ok(self - 1)
+                } else {
+                    Err(RoundError::InvalidAlignment)
+                }
+            }
+        }
+    )+}
+}
+
+alignable_integer!(u8 u16 u32 u64 u128 usize);
+
+/// Get the bit mask for an alignment.
+///
+/// This is implemented by `alignable_integer!` above.
+trait AlignmentMask: Sized {
+    /// Get the mask of the bits that must be zero in aligned values, or an
+    /// error if `self` is not a power of two.
+    fn alignment_mask(self) -> Result<Self, RoundError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Direction, Roundable};
+    use assert2::check;
+
+    #[test]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn align_matches_directed_rounding() {
+        for shift in 0..8 {
+            let align = 1u8 << shift;
+            for value in 0..=u8::MAX {
+                check!(
+                    value.try_round_directed_to(align, Direction::Up)
+                        == value.try_align_up(align)
+                );
+                check!(
+                    value.round_directed_to(align, Direction::Down)
+                        == value.align_down(align)
+                );
+                check!(
+                    value.try_round_directed_to(align, Direction::Down)
+                        == value.try_align_down(align)
+                );
+                check!(
+                    (value % align == 0) == value.is_aligned(align),
+                    "{} {}",
+                    value,
+                    align
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn align_large() {
+        check!(u128::MAX - 4095 == (u128::MAX - 4096).align_up(4096));
+        check!(None == (u128::MAX - 4094).try_align_up(4096));
+        check!(1 << 127 == (1u128 << 127).align_up(1 << 127));
+        check!(1 << 127 == u128::MAX.align_down(1 << 127));
+        check!(usize::MAX == usize::MAX.align_up(1));
+    }
+
+    #[test]
+    fn align_invalid() {
+        check!(Err(RoundError::InvalidAlignment) == 5u32.checked_align_up(0));
+        check!(Err(RoundError::InvalidAlignment) == 5u32.checked_align_up(3));
+        check!(
+            Err(RoundError::InvalidAlignment) == 5u32.checked_align_down(12)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "try_align_up() requires power of two alignment")]
+    fn try_align_up_invalid() {
+        let _ = 5u32.try_align_up(3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "try_align_down() requires power of two alignment"
+    )]
+    fn align_down_invalid() {
+        let _ = 5u32.align_down(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "is_aligned() requires power of two alignment")]
+    fn is_aligned_invalid() {
+        let _ = 5u32.is_aligned(0);
+    }
+}
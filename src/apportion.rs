@@ -0,0 +1,827 @@
+//! Round values so that they add up to a rounded total.
+
+use crate::{unwrap_rounded, Direction, RoundError, Roundable, TieStrategy};
+
+/// Methods to round parts of a total so that they still add up to it.
+///
+/// This is implemented for integers. Rounding each part independently can
+/// change its sum: 33 + 33 + 33 is not 100. These use the largest remainder
+/// method to choose which parts to round up so that the parts add up to the
+/// rounded total:
+///
+///   1. Round every part down to a multiple of `factor`.
+///   2. Round the parts with the largest remainders up, one at a time, until
+///      the parts add up to the total rounded to `factor`.
+///
+/// Parts with equal remainders are chosen according to the tie strategy. If a
+/// part would round up when rounded on its own, as if it were a tie, it is
+/// chosen before parts that would round down. Otherwise, earlier parts are
+/// chosen first.
+///
+/// These never allocate.
+///
+/// ```rust
+/// use roundable::{Apportionable, Tie};
+///
+/// let mut parts = [0; 3];
+/// i64::split_to(100, &[1, 1, 1], &mut parts, 1, Tie::Up);
+/// assert!(parts == [34, 33, 33]);
+///
+/// let mut rounded = [0; 3];
+/// u32::apportion_to(&[250, 250, 250], &mut rounded, 100, Tie::Up);
+/// assert!(rounded == [300, 300, 200]);
+/// ```
+///
+/// Floats are not supported, because their sums are rarely exact. To round
+/// floats, scale them to integers first, e.g. use cents instead of dollars, or
+/// thousandths of a percent instead of percents:
+///
+/// ```rust
+/// use roundable::{Apportionable, Tie};
+///
+/// // Round percentages to one decimal place so that they add up to 100%.
+/// let percentages = [100.0 / 3.0; 3];
+/// let scaled = percentages.map(|percent: f64| (percent * 1e3).round() as i64);
+///
+/// let mut rounded = [0; 3];
+/// i64::apportion_to(&scaled, &mut rounded, 100, Tie::Up);
+/// assert!(rounded == [33_400, 33_300, 33_300]);
+///
+/// let rounded = rounded.map(|thousandths| thousandths as f64 / 1e3);
+/// assert!(rounded == [33.4, 33.3, 33.3]);
+/// ```
+pub trait Apportionable: Roundable + Copy {
+    /// Round every value in `values` to a multiple of `factor`, so that the
+    /// results add up to the sum of `values` rounded to `factor`, and store
+    /// them in `output`. Panics on failure.
+    ///
+    /// Every value is rounded to one of the two nearest multiples of `factor`.
+    /// Values that are already multiples of `factor` are unchanged.
+    ///
+    /// ```rust
+    /// use roundable::{Apportionable, Tie};
+    ///
+    /// // Round prices in cents to whole dollars. Rounding them independently
+    /// // would add up to $6, but they add up to $7.
+    /// let mut rounded = [0; 3];
+    /// i32::apportion_to(&[1_40, 2_30, 3_30], &mut rounded, 100, Tie::Up);
+    /// assert!(rounded == [2_00, 2_00, 3_00]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` and `output` have different lengths, if `factor` is
+    /// not positive, or if a result does not fit in the type.
+    fn apportion_to<S>(
+        values: &[Self],
+        output: &mut [Self],
+        factor: Self,
+        tie: S,
+    ) where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            Self::checked_apportion_to(values, output, factor, tie),
+            "apportion_to",
+        );
+    }
+
+    /// Round every value in `values` to a multiple of `factor`, so that the
+    /// results add up to the sum of `values` rounded to `factor`, and store
+    /// them in `output`. Returns a [`RoundError`] on failure.
+    ///
+    /// This is just like [`Apportionable::apportion_to()`], except that it
+    /// reports errors instead of panicking. If it fails, the contents of
+    /// `output` are unspecified.
+    ///
+    /// ```rust
+    /// use roundable::{Apportionable, RoundError, Tie};
+    ///
+    /// let mut rounded = [0; 2];
+    /// let result = u8::checked_apportion_to(&[14, 16], &mut rounded, 10, Tie::Up);
+    /// assert!(Ok(()) == result);
+    /// assert!(rounded == [10, 20]);
+    ///
+    /// let result = u8::checked_apportion_to(&[255, 5], &mut rounded, 10, Tie::Up);
+    /// assert!(Err(RoundError::Overflow) == result);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::NonPositiveFactor`] if `factor` is not positive,
+    /// and [`RoundError::Overflow`] if a result, or the sum of `values`, does
+    /// not fit in the type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` and `output` have different lengths.
+    fn checked_apportion_to<S>(
+        values: &[Self],
+        output: &mut [Self],
+        factor: Self,
+        tie: S,
+    ) -> Result<(), RoundError>
+    where
+        S: TieStrategy;
+
+    /// Split `total` into parts proportional to `ratios`, so that every part
+    /// is a multiple of `factor` and they add up to `total` rounded to
+    /// `factor`, and store them in `output`. Panics on failure.
+    ///
+    /// ```rust
+    /// use roundable::{Apportionable, Tie};
+    ///
+    /// // Split $100.00 into two payments at a ratio of 1:2.
+    /// let mut payments = [0; 2];
+    /// u64::split_to(100_00, &[1, 2], &mut payments, 1, Tie::Up);
+    /// assert!(payments == [33_33, 66_67]);
+    ///
+    /// // Negative totals are split into negative parts.
+    /// let mut parts = [0; 3];
+    /// i64::split_to(-100, &[1, 1, 1], &mut parts, 5, Tie::Up);
+    /// assert!(parts == [-35, -35, -30]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratios` and `output` have different lengths, if `factor` is
+    /// not positive, if `ratios` are all zero, or if `total` rounded to
+    /// `factor` does not fit in the type.
+    fn split_to<S>(
+        total: Self,
+        ratios: &[u32],
+        output: &mut [Self],
+        factor: Self,
+        tie: S,
+    ) where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            Self::checked_split_to(total, ratios, output, factor, tie),
+            "split_to",
+        );
+    }
+
+    /// Split `total` into parts proportional to `ratios`, so that every part
+    /// is a multiple of `factor` and they add up to `total` rounded to
+    /// `factor`, and store them in `output`. Returns a [`RoundError`] on
+    /// failure.
+    ///
+    /// This is just like [`Apportionable::split_to()`], except that it
+    /// reports errors instead of panicking. If it fails, the contents of
+    /// `output` are unspecified.
+    ///
+    /// ```rust
+    /// use roundable::{Apportionable, RoundError, Tie};
+    ///
+    /// let mut parts = [0; 2];
+    /// let result = u32::checked_split_to(10, &[0, 0], &mut parts, 1, Tie::Up);
+    /// assert!(Err(RoundError::ZeroRatios) == result);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::NonPositiveFactor`] if `factor` is not positive,
+    /// [`RoundError::ZeroRatios`] if `ratios` are all zero, and
+    /// [`RoundError::Overflow`] if `total` rounded to `factor` does not fit in
+    /// the type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratios` and `output` have different lengths.
+    fn checked_split_to<S>(
+        total: Self,
+        ratios: &[u32],
+        output: &mut [Self],
+        factor: Self,
+        tie: S,
+    ) -> Result<(), RoundError>
+    where
+        S: TieStrategy;
+}
+
+/// Implement [`Apportionable`] for integer types.
+///
+/// `$wide` is a type that can hold the sum of many values, so that summing
+/// the values only fails for 128-bit types.
+macro_rules! apportionable_integer {
+    ($($ty:ident $wide:ident;)+) => {$(
+        impl Apportionable for $ty {
+            #[allow(
+                clippy::arithmetic_side_effects,
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::integer_division,
+                unused_comparisons
+            )]
+            fn checked_apportion_to<S>(
+                values: &[Self],
+                output: &mut [Self],
+                factor: Self,
+                tie: S,
+            ) -> Result<(), RoundError>
+            where
+                S: TieStrategy,
+            {
+                /// Should `value` be rounded up if its remainder is tied with
+                /// other values?
+                fn prefers_up<S: TieStrategy>(
+                    value: $ty,
+                    remainder: $ty,
+                    factor: $ty,
+                    tie: S,
+                ) -> bool {
+                    // Safe: value - remainder is a multiple of factor.
+                    let is_floor_odd = ((value - remainder) / factor) & 1 != 0;
+
+                    // The multiple toward zero is the floor for positive
+                    // values, and the ceiling for negative values.
+                    let negative = value < 0;
+                    let is_base_odd = is_floor_odd != negative;
+                    let away =
+                        tie.is_away_from_zero(negative, || is_base_odd);
+                    away != negative
+                }
+
+                assert!(
+                    values.len() == output.len(),
+                    "values and output slices must have the same length",
+                );
+                if factor <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                // Round every value down, and keep the remainders in output
+                // until we know which values to round up.
+                let mut total: $wide = 0;
+                let mut floors: $wide = 0;
+                for (&value, remainder) in values.iter().zip(output.iter_mut())
+                {
+                    let floor = value
+                        .checked_round_directed_to(factor, Direction::Down)?;
+
+                    // Safe: floor ≤ value < floor + factor.
+                    *remainder = value - floor;
+                    total = total
+                        .checked_add(value as $wide)
+                        .ok_or(RoundError::Overflow)?;
+                    floors = floors
+                        .checked_add(floor as $wide)
+                        .ok_or(RoundError::Overflow)?;
+                }
+
+                // This is the sum of the remainders rounded to factor, which
+                // is between 0 and values.len() × factor.
+                let extra = total
+                    .checked_round_to(factor as $wide, tie)?
+                    .checked_sub(floors)
+                    .ok_or(RoundError::Overflow)?;
+
+                let mut cutoff = Cutoff::new(
+                    output.iter().map(|&remainder| remainder as u128),
+                    (extra / factor as $wide) as usize,
+                    |i| prefers_up(values[i], output[i], factor, tie),
+                );
+
+                for (&value, rounded) in values.iter().zip(output.iter_mut()) {
+                    let remainder = *rounded;
+                    let floor = value - remainder;
+                    *rounded = if cutoff.is_chosen(remainder as u128, || {
+                        prefers_up(value, remainder, factor, tie)
+                    }) {
+                        floor.checked_add(factor).ok_or(RoundError::Overflow)?
+                    } else {
+                        floor
+                    };
+                }
+
+                Ok(())
+            }
+
+            #[allow(
+                clippy::arithmetic_side_effects,
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_possible_wrap,
+                clippy::cast_sign_loss,
+                clippy::integer_division,
+                unused_comparisons
+            )]
+            fn checked_split_to<S>(
+                total: Self,
+                ratios: &[u32],
+                output: &mut [Self],
+                factor: Self,
+                tie: S,
+            ) -> Result<(), RoundError>
+            where
+                S: TieStrategy,
+            {
+                assert!(
+                    ratios.len() == output.len(),
+                    "ratios and output slices must have the same length",
+                );
+
+                let total = total.checked_round_to(factor, tie)?;
+                let negative = total < 0;
+
+                // Work with the number of factors in the total. Casting a
+                // negative value to u128 sign extends it, so negating it
+                // leaves its magnitude.
+                let units = total / factor;
+                let units = if negative {
+                    (units as u128).wrapping_neg()
+                } else {
+                    units as u128
+                };
+
+                // Safe: there are fewer than 2^64 ratios, so this is less
+                // than 2^96.
+                let sum: u128 =
+                    ratios.iter().map(|&ratio| u128::from(ratio)).sum();
+                if sum == 0 {
+                    return Err(RoundError::ZeroRatios);
+                }
+
+                // units × ratio might not fit in a u128, so split units into
+                // whole × sum + rest, and get each share of both separately.
+                // Safe: rest < 2^96 and ratio < 2^32, so rest × ratio fits,
+                // and whole × ratio ≤ units.
+                let (whole, rest) = (units / sum, units % sum);
+                let share = |ratio: u32| {
+                    let ratio = u128::from(ratio);
+                    let part = rest * ratio;
+                    (whole * ratio + part / sum, part % sum)
+                };
+
+                // Safe: the shares are rounded down, so they add up to less
+                // than units, but not by more than ratios.len().
+                let assigned: u128 =
+                    ratios.iter().map(|&ratio| share(ratio).0).sum();
+                let mut cutoff = Cutoff::new(
+                    ratios.iter().map(|&ratio| share(ratio).1),
+                    (units - assigned) as usize,
+                    |i| {
+                        let units = share(ratios[i]).0;
+                        tie.is_away_from_zero(negative, || units & 1 != 0)
+                    },
+                );
+
+                for (&ratio, part) in ratios.iter().zip(output.iter_mut()) {
+                    let (units, remainder) = share(ratio);
+                    let away = cutoff.is_chosen(remainder, || {
+                        tie.is_away_from_zero(negative, || units & 1 != 0)
+                    });
+
+                    // Safe: the magnitude of the part is at most the magnitude
+                    // of total, which fits in the type.
+                    let magnitude = (units + u128::from(away)) * factor as u128;
+                    *part = if negative {
+                        (magnitude as $ty).wrapping_neg()
+                    } else {
+                        magnitude as $ty
+                    };
+                }
+
+                Ok(())
+            }
+        }
+    )+}
+}
+
+apportionable_integer! {
+    u8 u128; u16 u128; u32 u128; u64 u128; u128 u128; usize u128;
+    i8 i128; i16 i128; i32 i128; i64 i128; i128 i128; isize i128;
+}
+
+/// Which values to round up with the largest remainder method.
+///
+/// Every value with a remainder greater than `remainder` is rounded up, as
+/// are some of the values with a remainder equal to it.
+#[derive(Clone, Copy, Debug)]
+struct Cutoff {
+    /// The smallest remainder that can be rounded up.
+    remainder: u128,
+
+    /// How many more values at `remainder` that prefer rounding up to round
+    /// up.
+    preferred: usize,
+
+    /// How many more values at `remainder` that don’t prefer rounding up to
+    /// round up.
+    other: usize,
+}
+
+impl Cutoff {
+    /// Find the cutoff to round up `count` of the values with `remainders`.
+    ///
+    /// `prefers_up` takes the index of a value and returns whether it should
+    /// be rounded up before others with the same remainder. `count` must not
+    /// be more than the number of remainders.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn new<R, P>(remainders: R, count: usize, prefers_up: P) -> Self
+    where
+        R: Iterator<Item = u128> + Clone,
+        P: Fn(usize) -> bool,
+    {
+        if count == 0 {
+            return Self { remainder: u128::MAX, preferred: 0, other: 0 };
+        }
+
+        // Find the largest remainder that at least count values have. Every
+        // value has a remainder of at least 0.
+        let at_least = |cutoff| {
+            remainders
+                .clone()
+                .filter(|&remainder| remainder >= cutoff)
+                .count()
+        };
+        let mut low = 0;
+        let mut high = remainders.clone().max().unwrap_or(0);
+        while low < high {
+            // Safe: low < high, so this is between low + 1 and high.
+            let middle = high - ((high - low) >> 1);
+            if at_least(middle) >= count {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+
+        // Safe: fewer than count values have a remainder above low.
+        let above = remainders
+            .clone()
+            .filter(|&remainder| remainder > low)
+            .count();
+        let remaining = count - above;
+
+        let preferred = remainders
+            .enumerate()
+            .filter(|&(i, remainder)| remainder == low && prefers_up(i))
+            .count()
+            .min(remaining);
+
+        Self { remainder: low, preferred, other: remaining - preferred }
+    }
+
+    /// Should a value with `remainder` be rounded up?
+    ///
+    /// This must be called for each value in order.
+    fn is_chosen<P>(&mut self, remainder: u128, prefers_up: P) -> bool
+    where
+        P: FnOnce() -> bool,
+    {
+        if remainder != self.remainder {
+            return remainder > self.remainder;
+        }
+
+        let remaining = if prefers_up() {
+            &mut self.preferred
+        } else {
+            &mut self.other
+        };
+        match remaining.checked_sub(1) {
+            Some(count) => {
+                *remaining = count;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::arithmetic_side_effects)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random, TIES};
+    use crate::{tie, Tie};
+    use assert2::check;
+
+    extern crate std;
+    use std::vec::Vec;
+
+    /// Apportion by sorting, to check [`Cutoff`].
+    ///
+    /// `remainders` are (remainder, prefers up) pairs. Returns whether each
+    /// value is rounded up.
+    fn reference(remainders: &[(u128, bool)], count: usize) -> Vec<bool> {
+        let mut order: Vec<usize> = (0..remainders.len()).collect();
+        order.sort_by_key(|&i| {
+            (core::cmp::Reverse(remainders[i].0), !remainders[i].1, i)
+        });
+        let mut chosen = std::vec![false; remainders.len()];
+        for &i in &order[..count] {
+            chosen[i] = true;
+        }
+        chosen
+    }
+
+    #[test]
+    fn cutoff_matches_sorting() {
+        let mut random = random();
+        for len in 0..20 {
+            for range in [1, 2, 3, 10, 1_000] {
+                let remainders: Vec<(u128, bool)> = (&mut random)
+                    .take(len)
+                    .map(|n| (u128::from(n % range), n >> 63 != 0))
+                    .collect();
+                for count in 0..=len {
+                    let mut cutoff = Cutoff::new(
+                        remainders.iter().map(|&(remainder, _)| remainder),
+                        count,
+                        |i| remainders[i].1,
+                    );
+                    let chosen: Vec<bool> = remainders
+                        .iter()
+                        .map(|&(remainder, prefers_up)| {
+                            cutoff.is_chosen(remainder, || prefers_up)
+                        })
+                        .collect();
+                    check!(chosen == reference(&remainders, count));
+                }
+            }
+        }
+    }
+
+    /// Check that `values` are apportioned correctly.
+    fn check_apportion(values: &[i32], factor: i32) {
+        for tie in TIES {
+            let mut output = std::vec![0; values.len()];
+            i32::apportion_to(values, &mut output, factor, tie);
+
+            let total: i32 = values.iter().sum();
+            check!(
+                output.iter().sum::<i32>() == total.round_to(factor, tie),
+                "{:?} {} {:?}",
+                values,
+                factor,
+                tie
+            );
+            for (&value, &rounded) in values.iter().zip(&output) {
+                check!(rounded % factor == 0);
+                check!(
+                    value.round_directed_to(factor, Direction::Down) <= rounded
+                );
+                check!(
+                    rounded <= value.round_directed_to(factor, Direction::Up)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn apportion_random() {
+        let mut random = random();
+        for len in 0..10 {
+            for factor in [1, 2, 3, 10, 100] {
+                #[allow(clippy::cast_possible_truncation)]
+                let values: Vec<i32> =
+                    (&mut random).take(len).map(|n| (n as i32) >> 20).collect();
+                check_apportion(&values, factor);
+            }
+        }
+    }
+
+    #[test]
+    fn apportion_largest_remainder() {
+        let mut rounded = [0; 4];
+        u32::apportion_to(&[12, 16, 19, 33], &mut rounded, 10, Tie::Up);
+        check!(rounded == [10, 20, 20, 30]);
+    }
+
+    #[test]
+    fn apportion_ties() {
+        let values = [5, 15, 25, 35];
+        let mut rounded = [0; 4];
+
+        // The sum is 80, so two values must be rounded up.
+        u32::apportion_to(&values, &mut rounded, 10, Tie::Up);
+        check!(rounded == [10, 20, 20, 30]);
+        u32::apportion_to(&values, &mut rounded, 10, Tie::Down);
+        check!(rounded == [10, 20, 20, 30]);
+        u32::apportion_to(&values, &mut rounded, 10, Tie::TowardEven);
+        check!(rounded == [0, 20, 20, 40]);
+        u32::apportion_to(&values, &mut rounded, 10, tie::TowardOdd);
+        check!(rounded == [10, 10, 30, 30]);
+    }
+
+    #[test]
+    fn apportion_negative_ties() {
+        let values = [-5, -15, 5, 15];
+        let mut rounded = [0; 4];
+
+        // The sum is 0, so two values must be rounded up.
+        i8::apportion_to(&values, &mut rounded, 10, Tie::Up);
+        check!(rounded == [0, -10, 0, 10]);
+        i8::apportion_to(&values, &mut rounded, 10, Tie::Down);
+        check!(rounded == [0, -10, 0, 10]);
+        i8::apportion_to(&values, &mut rounded, 10, Tie::TowardZero);
+        check!(rounded == [0, -10, 0, 10]);
+        i8::apportion_to(&values, &mut rounded, 10, Tie::AwayFromZero);
+        check!(rounded == [-10, -20, 10, 20]);
+        i8::apportion_to(&values, &mut rounded, 10, Tie::TowardEven);
+        check!(rounded == [0, -20, 0, 20]);
+    }
+
+    #[test]
+    fn apportion_exact() {
+        let values = [-20, 0, 10, 30];
+        let mut rounded = [1; 4];
+        i64::apportion_to(&values, &mut rounded, 10, Tie::Up);
+        check!(rounded == values);
+    }
+
+    #[test]
+    fn apportion_extremes() {
+        let mut rounded = [0; 3];
+        i8::apportion_to(&[i8::MIN, 0, i8::MAX], &mut rounded, 1, Tie::Up);
+        check!(rounded == [i8::MIN, 0, i8::MAX]);
+
+        let mut rounded = [0; 2];
+        u128::apportion_to(&[u128::MAX - 5, 3], &mut rounded, 10, Tie::Up);
+        check!(rounded == [u128::MAX - 5, 0]);
+        check!(
+            Err(RoundError::Overflow)
+                == u128::checked_apportion_to(
+                    &[u128::MAX, 1],
+                    &mut rounded,
+                    1,
+                    Tie::Up
+                )
+        );
+    }
+
+    #[test]
+    fn apportion_errors() {
+        let mut rounded = [0; 2];
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == i32::checked_apportion_to(&[1, 2], &mut rounded, 0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == i8::checked_apportion_to(
+                    &[-128, 0],
+                    &mut [0; 2],
+                    3,
+                    Tie::Up
+                )
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == u8::checked_apportion_to(
+                    &[255, 5],
+                    &mut [0; 2],
+                    10,
+                    Tie::Up
+                )
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let mut output: [u8; 0] = [];
+        u8::apportion_to(&[], &mut output, 10, Tie::Up);
+        check!(
+            Err(RoundError::ZeroRatios)
+                == u8::checked_split_to(10, &[], &mut output, 10, Tie::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "apportion_to() requires positive factor")]
+    fn apportion_zero_factor() {
+        u32::apportion_to(&[1], &mut [0], 0, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "values and output slices must have the same")]
+    fn apportion_length_mismatch() {
+        u32::apportion_to(&[1, 2], &mut [0], 1, Tie::Up);
+    }
+
+    /// Check that `total` is split by `ratios` correctly.
+    fn check_split(total: i64, ratios: &[u32], factor: i64) {
+        for tie in TIES {
+            let mut parts = std::vec![0; ratios.len()];
+            i64::split_to(total, ratios, &mut parts, factor, tie);
+
+            let total = total.round_to(factor, tie);
+            check!(parts.iter().sum::<i64>() == total);
+
+            // Every part is within one factor of its exact share.
+            let sum: i64 = ratios.iter().copied().map(i64::from).sum();
+            for (&ratio, &part) in ratios.iter().zip(&parts) {
+                check!(part % factor == 0);
+                let share = i128::from(total) * i128::from(ratio);
+                let part = i128::from(part) * i128::from(sum);
+                check!(
+                    (share - part).abs() < i128::from(factor * sum),
+                    "{} {:?} {} {:?}",
+                    total,
+                    ratios,
+                    factor,
+                    tie
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn split_random() {
+        let mut random = random();
+        for len in 1..10 {
+            for factor in [1, 3, 100] {
+                #[allow(clippy::cast_possible_truncation)]
+                let ratios: Vec<u32> =
+                    (&mut random).take(len).map(|n| (n % 5) as u32).collect();
+                if ratios.iter().all(|&ratio| ratio == 0) {
+                    continue;
+                }
+                for total in [0, 1, 99, 100, 12_345, -1, -12_345] {
+                    check_split(total, &ratios, factor);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn split_ties() {
+        let mut parts = [0; 4];
+        u32::split_to(6, &[1, 1, 1, 1], &mut parts, 1, Tie::Up);
+        check!(parts == [2, 2, 1, 1]);
+        u32::split_to(6, &[1, 1, 1, 1], &mut parts, 1, Tie::TowardEven);
+        check!(parts == [2, 2, 1, 1]);
+        u32::split_to(12, &[1, 1, 3, 3], &mut parts, 1, Tie::TowardEven);
+        check!(parts == [2, 2, 4, 4]);
+        u32::split_to(12, &[1, 1, 3, 3], &mut parts, 1, Tie::TowardOdd);
+        check!(parts == [1, 1, 5, 5]);
+
+        // Every share is x.5, so two parts must be rounded away from zero.
+        let mut parts = [0; 4];
+        i32::split_to(-12, &[1, 1, 3, 3], &mut parts, 1, Tie::Up);
+        check!(parts == [-2, -2, -4, -4]);
+        i32::split_to(-12, &[1, 1, 3, 3], &mut parts, 1, Tie::TowardOdd);
+        check!(parts == [-1, -1, -5, -5]);
+        i32::split_to(-12, &[3, 1, 3, 1], &mut parts, 1, Tie::TowardEven);
+        check!(parts == [-4, -2, -4, -2]);
+    }
+
+    #[test]
+    fn split_extremes() {
+        let mut parts = [0; 3];
+        u128::split_to(
+            u128::MAX,
+            &[u32::MAX, 1, u32::MAX],
+            &mut parts,
+            1,
+            Tie::Up,
+        );
+        check!(
+            parts
+                .iter()
+                .try_fold(0u128, |sum, &part| sum.checked_add(part))
+                == Some(u128::MAX)
+        );
+        check!(parts[0] - parts[2] <= 1);
+
+        let mut parts = [0; 2];
+        i8::split_to(i8::MIN, &[1, 1], &mut parts, 1, Tie::Up);
+        check!(parts == [-64, -64]);
+        i8::split_to(i8::MIN, &[1, 0], &mut parts, 1, Tie::Up);
+        check!(parts == [-128, 0]);
+
+        let mut parts = [0; 1];
+        check!(
+            Err(RoundError::Overflow)
+                == u8::checked_split_to(255, &[1], &mut parts, 10, Tie::Up)
+        );
+    }
+
+    #[test]
+    fn split_errors() {
+        let mut parts = [0; 2];
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == i32::checked_split_to(10, &[1, 2], &mut parts, -1, Tie::Up)
+        );
+        check!(
+            Err(RoundError::ZeroRatios)
+                == i32::checked_split_to(10, &[0, 0], &mut parts, 1, Tie::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "split_to() requires a non-zero ratio")]
+    fn split_zero_ratios() {
+        u32::split_to(10, &[0], &mut [0], 1, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "ratios and output slices must have the same")]
+    fn split_length_mismatch() {
+        u32::split_to(10, &[1, 2], &mut [0], 1, Tie::Up);
+    }
+}
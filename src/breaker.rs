@@ -0,0 +1,569 @@
+//! Resolve ties with rules that depend on the value being rounded.
+
+use crate::{
+    rounded_or_none, unwrap_rounded, Direction, RoundError, Roundable, Tie,
+    TieStrategy,
+};
+
+/// How to resolve a tie, given the value and the two candidates.
+///
+/// A [`TieStrategy`] only knows the sign of the value and whether the multiple
+/// toward zero is odd, and it can’t change between values. A `TieBreaker` gets
+/// the value, the candidates, and the factor, and can keep state, so it can
+/// implement rules that [`Tie`](crate::Tie) can’t express, e.g. alternating
+/// between rounding up and down.
+///
+/// This is implemented for every [`TieStrategy`], including
+/// [`Tie`](crate::Tie), so they can be used anywhere a `TieBreaker` is
+/// expected. Use it with [`TieBreakerRoundable`].
+///
+/// ```rust
+/// use roundable::{Direction, TieBreaker, TieBreakerRoundable};
+///
+/// /// Round ties up and down alternately, so that they don’t add up to a bias.
+/// #[derive(Default)]
+/// struct Alternate {
+///     up: bool,
+/// }
+///
+/// impl<T> TieBreaker<T> for Alternate {
+///     fn break_tie(
+///         &mut self,
+///         _value: T,
+///         _lower: Option<T>,
+///         _upper: Option<T>,
+///         _factor: T,
+///     ) -> Direction {
+///         self.up = !self.up;
+///         if self.up {
+///             Direction::Up
+///         } else {
+///             Direction::Down
+///         }
+///     }
+/// }
+///
+/// let mut alternate = Alternate::default();
+/// assert!(20 == 15.round_to_with_breaker(10, &mut alternate));
+/// assert!(10 == 14.round_to_with_breaker(10, &mut alternate));
+/// assert!(10 == 15.round_to_with_breaker(10, &mut alternate));
+/// assert!(20 == 15.round_to_with_breaker(10, &mut alternate));
+/// ```
+pub trait TieBreaker<T> {
+    /// Choose how to round `value`, which is exactly halfway between `lower`
+    /// and `upper`, the multiples of `factor` below and above it.
+    ///
+    /// [`Direction::Up`] chooses `upper` and [`Direction::Down`] chooses
+    /// `lower`. [`Direction::TowardZero`] and [`Direction::AwayFromZero`]
+    /// choose based on the sign of `value`.
+    ///
+    /// This is only called for ties. A candidate is `None` if it does not fit
+    /// in the type, and choosing it makes rounding fail. The candidate toward
+    /// zero always fits.
+    fn break_tie(
+        &mut self,
+        value: T,
+        lower: Option<T>,
+        upper: Option<T>,
+        factor: T,
+    ) -> Direction;
+}
+
+impl<T, S> TieBreaker<T> for S
+where
+    T: Roundable + Default + PartialOrd,
+    S: TieStrategy,
+{
+    fn break_tie(
+        &mut self,
+        value: T,
+        lower: Option<T>,
+        upper: Option<T>,
+        factor: T,
+    ) -> Direction {
+        let negative = value < T::default();
+        let base = if negative { upper } else { lower };
+        let away = self.is_away_from_zero(negative, || {
+            // Rounding a tie toward even only chooses base if it’s even.
+            value.checked_round_to(factor, Tie::TowardEven).ok() != base
+        });
+        if away {
+            Direction::AwayFromZero
+        } else {
+            Direction::TowardZero
+        }
+    }
+}
+
+/// Round to an arbitrary factor, resolving ties with a [`TieBreaker`].
+///
+/// This is implemented for integers, floats, and
+/// [`Duration`](core::time::Duration). It works just like
+/// [`Roundable::round_to()`], except that the tie breaker is called to choose
+/// between the two nearest multiples when the value is exactly halfway between
+/// them.
+///
+/// ```rust
+/// use roundable::{Direction, Tie, TieBreaker, TieBreakerRoundable};
+///
+/// /// Round ties toward multiples of 100, and down otherwise.
+/// struct TowardHundred;
+///
+/// impl TieBreaker<i32> for TowardHundred {
+///     fn break_tie(
+///         &mut self,
+///         _value: i32,
+///         _lower: Option<i32>,
+///         upper: Option<i32>,
+///         _factor: i32,
+///     ) -> Direction {
+///         if upper.map_or(false, |upper| upper % 100 == 0) {
+///             Direction::Up
+///         } else {
+///             Direction::Down
+///         }
+///     }
+/// }
+///
+/// assert!(100 == 95.round_to_with_breaker(10, &mut TowardHundred));
+/// assert!(-100 == (-105).round_to_with_breaker(10, &mut TowardHundred));
+/// assert!(20 == 25.round_to_with_breaker(10, &mut TowardHundred));
+/// assert!(30 == 25.round_to_with_breaker(10, &mut Tie::Up));
+/// ```
+pub trait TieBreakerRoundable: Roundable {
+    /// Round to the nearest `factor`, resolving ties with `breaker`. Panics
+    /// if the result does not fit in the type.
+    ///
+    /// ```rust
+    /// use roundable::{tie, TieBreakerRoundable, SECOND};
+    /// use std::time::Duration;
+    ///
+    /// assert!(2.0 == 2.5.round_to_with_breaker(1.0, &mut tie::TowardEven));
+    /// assert!(
+    ///     SECOND == Duration::from_millis(1_500).round_to_with_breaker(SECOND, &mut tie::Down)
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0, or if rounding
+    /// would return a value that does not fit in the return type.
+    #[must_use]
+    fn round_to_with_breaker<B>(self, factor: Self, breaker: &mut B) -> Self
+    where
+        B: TieBreaker<Self> + ?Sized,
+    {
+        unwrap_rounded(
+            self.checked_round_to_with_breaker(factor, breaker),
+            "round_to_with_breaker",
+        )
+    }
+
+    /// Round to the nearest `factor`, resolving ties with `breaker`. Returns
+    /// `None` if the result does not fit in the type.
+    ///
+    /// ```rust
+    /// use roundable::{Tie, TieBreakerRoundable};
+    ///
+    /// assert!(Some(250) == 251u8.try_round_to_with_breaker(10, &mut Tie::Up));
+    /// assert!(None == 255u8.try_round_to_with_breaker(10, &mut Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0.
+    #[must_use]
+    fn try_round_to_with_breaker<B>(
+        self,
+        factor: Self,
+        breaker: &mut B,
+    ) -> Option<Self>
+    where
+        B: TieBreaker<Self> + ?Sized,
+    {
+        rounded_or_none(
+            self.checked_round_to_with_breaker(factor, breaker),
+            "try_round_to_with_breaker",
+        )
+    }
+
+    /// Round to the nearest `factor`, resolving ties with `breaker`. Returns
+    /// a [`RoundError`] on failure.
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, Tie, TieBreakerRoundable};
+    ///
+    /// assert!(Ok(-20) == (-15).checked_round_to_with_breaker(10, &mut Tie::Down));
+    ///
+    /// // 127 is halfway between 126 and 128, which doesn’t fit.
+    /// assert!(Ok(126) == 127i8.checked_round_to_with_breaker(2, &mut Tie::Down));
+    /// assert!(
+    ///     Err(RoundError::Overflow)
+    ///         == 127i8.checked_round_to_with_breaker(2, &mut Tie::Up)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Roundable::checked_round_to()`]: usually
+    /// [`RoundError::NonPositiveFactor`] if `factor` is not positive, or
+    /// [`RoundError::Overflow`] if the result does not fit in the type, e.g.
+    /// if `breaker` chooses a candidate that is `None`.
+    fn checked_round_to_with_breaker<B>(
+        self,
+        factor: Self,
+        breaker: &mut B,
+    ) -> Result<Self, RoundError>
+    where
+        B: TieBreaker<Self> + ?Sized;
+}
+
+#[cfg(test)]
+#[allow(clippy::arithmetic_side_effects, clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::test_util::{DIRECTIONS, TIES};
+    use assert2::check;
+    use core::time::Duration;
+
+    extern crate std;
+    use std::vec::Vec;
+
+    /// Records the arguments it was called with, and resolves ties with a
+    /// fixed direction.
+    struct Record<T> {
+        /// The direction to return.
+        direction: Direction,
+
+        /// The `(value, lower, upper, factor)` from every call.
+        calls: Vec<(T, Option<T>, Option<T>, T)>,
+    }
+
+    impl<T> Record<T> {
+        /// Create a breaker that returns `direction`.
+        const fn new(direction: Direction) -> Self {
+            Self { direction, calls: Vec::new() }
+        }
+    }
+
+    impl<T> TieBreaker<T> for Record<T> {
+        fn break_tie(
+            &mut self,
+            value: T,
+            lower: Option<T>,
+            upper: Option<T>,
+            factor: T,
+        ) -> Direction {
+            self.calls.push((value, lower, upper, factor));
+            self.direction
+        }
+    }
+
+    /// Panics if it’s called.
+    struct Unreachable;
+
+    impl<T> TieBreaker<T> for Unreachable {
+        fn break_tie(
+            &mut self,
+            _: T,
+            _: Option<T>,
+            _: Option<T>,
+            _: T,
+        ) -> Direction {
+            panic!("break_tie() called for a value that isn’t a tie");
+        }
+    }
+
+    /// Check that using `tie` as a tie breaker matches
+    /// [`Roundable::checked_round_to()`] with `tie`.
+    fn check_tie<T>(value: T, factor: T, tie: Tie)
+    where
+        T: TieBreakerRoundable + Copy + Default + PartialOrd,
+        T: core::fmt::Debug,
+    {
+        check!(
+            value.checked_round_to(factor, tie)
+                == value.checked_round_to_with_breaker(factor, &mut { tie }),
+            "{:?} {:?} {:?}",
+            value,
+            factor,
+            tie
+        );
+    }
+
+    #[test]
+    fn tie_matches_round_to_i8() {
+        for tie in TIES {
+            for factor in 1..=i8::MAX {
+                for value in i8::MIN..=i8::MAX {
+                    check_tie(value, factor, tie);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tie_matches_round_to_u8() {
+        for tie in TIES {
+            for factor in 1..=u8::MAX {
+                for value in u8::MIN..=u8::MAX {
+                    check_tie(value, factor, tie);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn tie_matches_round_to_others() {
+        for tie in TIES {
+            for value in [-2.5, -1.5, -0.5, 0.0, 0.5, 1.25, 1.5, 2.5] {
+                check_tie(value, 1.0, tie);
+                check_tie(value, 0.5, tie);
+                check_tie(value as f32, 1.0, tie);
+            }
+            check_tie(0.15, 0.1, tie);
+            check_tie(u128::MAX - 1, 4, tie);
+            check_tie(i128::MIN + 2, 4, tie);
+            for millis in [500, 1_499, 1_500, 2_500] {
+                check_tie(
+                    Duration::from_millis(millis),
+                    Duration::from_secs(1),
+                    tie,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tie_matches_round_to_bounds() {
+        // 2^1023, so the multiple above 1.5 × 2^1023 is infinite.
+        let huge = f64::from_bits(0x7fe0_0000_0000_0000);
+        let second = Duration::from_secs(1);
+        for tie in TIES {
+            check_tie(-125i8, 10, tie);
+            check_tie(125i8, 10, tie);
+            check_tie(127i8, 2, tie);
+            check_tie(-127i8, 2, tie);
+            check_tie(255u8, 2, tie);
+            check_tie(u128::MAX, 2, tie);
+            check_tie(i128::MAX, 2, tie);
+            check_tie(i128::MIN + 1, 2, tie);
+            check_tie(huge * 1.5, huge, tie);
+            check_tie(-huge * 1.5, huge, tie);
+            check_tie(Duration::new(u64::MAX, 500_000_000), second, tie);
+            check_tie(Duration::MAX, Duration::from_nanos(2), tie);
+        }
+    }
+
+    #[test]
+    fn arguments() {
+        let mut breaker = Record::new(Direction::Up);
+        check!(20 == 15.round_to_with_breaker(10, &mut breaker));
+        check!(-10 == (-15).round_to_with_breaker(10, &mut breaker));
+        check!(
+            breaker.calls
+                == [
+                    (15, Some(10), Some(20), 10),
+                    (-15, Some(-20), Some(-10), 10)
+                ]
+        );
+
+        let mut breaker = Record::new(Direction::Down);
+        check!(0 == 5u8.round_to_with_breaker(10, &mut breaker));
+        check!(breaker.calls == [(5, Some(0), Some(10), 10)]);
+
+        let mut breaker = Record::new(Direction::Up);
+        check!(3.0 == 2.5.round_to_with_breaker(1.0, &mut breaker));
+        check!(-2.0 == (-2.5).round_to_with_breaker(1.0, &mut breaker));
+        check!(0.5 == 0.25.round_to_with_breaker(0.5, &mut breaker));
+        check!(
+            breaker.calls
+                == [
+                    (2.5, Some(2.0), Some(3.0), 1.0),
+                    (-2.5, Some(-3.0), Some(-2.0), 1.0),
+                    (0.25, Some(0.0), Some(0.5), 0.5),
+                ]
+        );
+
+        let second = Duration::from_secs(1);
+        let value = Duration::from_millis(1_500);
+        let mut breaker = Record::new(Direction::Down);
+        check!(second == value.round_to_with_breaker(second, &mut breaker));
+        check!(
+            breaker.calls == [(value, Some(second), Some(2 * second), second)]
+        );
+    }
+
+    #[test]
+    fn directions() {
+        let expected = [
+            (Direction::Up, 20, -10),
+            (Direction::Down, 10, -20),
+            (Direction::TowardZero, 10, -10),
+            (Direction::AwayFromZero, 20, -20),
+        ];
+        for (direction, positive, negative) in expected {
+            let mut breaker = Record::new(direction);
+            check!(positive == 15.round_to_with_breaker(10, &mut breaker));
+            check!(negative == (-15).round_to_with_breaker(10, &mut breaker));
+
+            let mut breaker = Record::new(direction);
+            let positive = f64::from(positive) / 10.0;
+            let negative = f64::from(negative) / 10.0;
+            check!(positive == 1.5.round_to_with_breaker(1.0, &mut breaker));
+            check!(negative == (-1.5).round_to_with_breaker(1.0, &mut breaker));
+        }
+
+        for direction in DIRECTIONS {
+            let mut breaker = Record::new(direction);
+            let value = Duration::from_millis(1_500);
+            let second = Duration::from_secs(1);
+            let expected = value.round_directed_to(second, direction);
+            check!(
+                expected == value.round_to_with_breaker(second, &mut breaker)
+            );
+        }
+    }
+
+    #[test]
+    fn only_called_for_ties() {
+        for value in -100..=100 {
+            let _ = value.round_to_with_breaker(7, &mut Unreachable);
+            if value % 5 != 0 {
+                let _ = value.round_to_with_breaker(10, &mut Unreachable);
+            }
+        }
+        for value in [-1.4, -0.6, 0.0, 0.1, 0.49, 0.51, 1.0] {
+            let _ = value.round_to_with_breaker(1.0, &mut Unreachable);
+        }
+        let _ = Duration::from_millis(1_499)
+            .round_to_with_breaker(Duration::from_secs(1), &mut Unreachable);
+    }
+
+    #[test]
+    fn alternating() {
+        /// Alternate between rounding up and down.
+        struct Alternate(bool);
+
+        impl TieBreaker<i32> for Alternate {
+            fn break_tie(
+                &mut self,
+                _: i32,
+                _: Option<i32>,
+                _: Option<i32>,
+                _: i32,
+            ) -> Direction {
+                self.0 = !self.0;
+                if self.0 {
+                    Direction::Up
+                } else {
+                    Direction::Down
+                }
+            }
+        }
+
+        let breaker: &mut dyn TieBreaker<i32> = &mut Alternate(false);
+        let rounded: Vec<i32> = [5, 5, 14, 5, 5]
+            .iter()
+            .map(|&value| value.round_to_with_breaker(10, breaker))
+            .collect();
+        check!(rounded == [10, 0, 10, 10, 0]);
+    }
+
+    #[test]
+    fn candidate_does_not_fit() {
+        let mut breaker = Record::new(Direction::Down);
+        check!(Ok(254) == 255u8.checked_round_to_with_breaker(2, &mut breaker));
+        check!(breaker.calls == [(255, Some(254), None, 2)]);
+        check!(
+            Err(RoundError::Overflow)
+                == 255u8.checked_round_to_with_breaker(
+                    2,
+                    &mut Record::new(Direction::Up)
+                )
+        );
+
+        let mut breaker = Record::new(Direction::TowardZero);
+        check!(
+            Ok(-120)
+                == (-125i8).checked_round_to_with_breaker(10, &mut breaker)
+        );
+        check!(breaker.calls == [(-125, None, Some(-120), 10)]);
+        check!(
+            Err(RoundError::Overflow)
+                == (-125i8).checked_round_to_with_breaker(
+                    10,
+                    &mut Record::new(Direction::AwayFromZero)
+                )
+        );
+
+        // The multiple above is 2^1024, which is infinite.
+        let factor = f64::from_bits(0x7fe0_0000_0000_0000); // 2^1023
+        let mut breaker = Record::new(Direction::Up);
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == (factor * 1.5)
+                    .checked_round_to_with_breaker(factor, &mut breaker)
+        );
+        check!(breaker.calls == [(factor * 1.5, Some(factor), None, factor)]);
+
+        let mut breaker = Record::new(Direction::Up);
+        check!(
+            Err(RoundError::Overflow)
+                == Duration::MAX.checked_round_to_with_breaker(
+                    Duration::from_nanos(2),
+                    &mut breaker
+                )
+        );
+        check!(
+            breaker.calls
+                == [(
+                    Duration::MAX,
+                    Some(Duration::new(u64::MAX, 999_999_998)),
+                    None,
+                    Duration::from_nanos(2)
+                )]
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 5.checked_round_to_with_breaker(0, &mut Unreachable)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 5.0.checked_round_to_with_breaker(-1.0, &mut Unreachable)
+        );
+        check!(
+            Err(RoundError::NanInput)
+                == f32::NAN
+                    .checked_round_to_with_breaker(1.0, &mut Unreachable)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == Duration::MAX.checked_round_to_with_breaker(
+                    Duration::ZERO,
+                    &mut Unreachable
+                )
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "round_to_with_breaker() requires positive factor"
+    )]
+    fn zero_factor() {
+        let _ = 5u32.round_to_with_breaker(0, &mut Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "try_round_to_with_breaker() requires positive factor"
+    )]
+    fn try_zero_factor() {
+        let _ = 5u32.try_round_to_with_breaker(0, &mut Tie::Up);
+    }
+}
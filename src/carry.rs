@@ -0,0 +1,789 @@
+//! Round a sequence of values, carrying each rounding error to the next value.
+
+use crate::duration::checked_nanos_to_duration;
+use crate::{unwrap_rounded, RoundError, Roundable, Tie, TieStrategy};
+use core::fmt::Debug;
+use core::time::Duration;
+
+/// Round a sequence of values to the same factor so that their sum stays
+/// close to the sum of the original values.
+///
+/// Rounding each value independently with [`Roundable::round_to()`] can add up
+/// to a large error: 0.4 rounds to 0 every time, so ten of them add up to 0
+/// instead of 4. A `CarryRounder` remembers how much each value was changed by
+/// rounding (the residual) and adds it to the next value before rounding it.
+/// This is also known as error diffusion.
+///
+/// After any number of values, the sum of the rounded values plus
+/// [`CarryRounder::residual()`] is the sum of the original values, and the
+/// residual is at most half of the factor. The sums of floats may differ by
+/// floating point error.
+///
+/// This works for integers, floats, and [`Duration`].
+///
+/// ```rust
+/// use roundable::{CarryRounder, Tie};
+///
+/// let mut rounder = CarryRounder::new(1.0).unwrap();
+/// let rounded: Vec<f64> =
+///     [0.4; 5].iter().map(|&value| rounder.round(value, Tie::Up)).collect();
+/// assert!(rounded == [0.0, 1.0, 0.0, 1.0, 0.0]);
+///
+/// let mut rounder = CarryRounder::new(10u32).unwrap();
+/// let rounded: Vec<u32> =
+///     [3; 5].iter().map(|&value| rounder.round(value, Tie::Up)).collect();
+/// assert!(rounded == [0, 10, 0, 0, 10]);
+/// assert!(-5 == rounder.residual());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CarryRounder<T: Carryable> {
+    /// The factor to round to. Always positive.
+    factor: T,
+
+    /// The residual to add to the next value before rounding it.
+    residual: T::Residual,
+}
+
+impl<T: Carryable> CarryRounder<T> {
+    /// Create a `CarryRounder` that rounds to `factor`, with no residual.
+    ///
+    /// ```rust
+    /// use roundable::{CarryRounder, RoundError, SECOND};
+    ///
+    /// assert!(CarryRounder::new(SECOND).is_ok());
+    /// assert!(CarryRounder::new(0.5).is_ok());
+    /// assert!(
+    ///     Err(RoundError::NonPositiveFactor)
+    ///         == CarryRounder::new(0i32).map(|rounder| rounder.factor())
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::NonPositiveFactor`] if `factor` is not positive,
+    /// or if it is an infinite or NaN float.
+    pub fn new(factor: T) -> Result<Self, RoundError> {
+        // Every implementation checks the factor before anything else, and
+        // rounding the factor to itself can’t fail otherwise.
+        let residual = T::Residual::default();
+        factor.checked_round_carrying(residual, factor, Tie::Up)?;
+        Ok(Self { factor, residual })
+    }
+
+    /// Get the factor this rounds to.
+    ///
+    /// ```rust
+    /// use roundable::CarryRounder;
+    ///
+    /// assert!(10 == CarryRounder::new(10).unwrap().factor());
+    /// ```
+    #[must_use]
+    pub fn factor(&self) -> T {
+        self.factor
+    }
+
+    /// Get the residual that will be added to the next value before it is
+    /// rounded.
+    ///
+    /// This is the sum of the original values minus the sum of the rounded
+    /// values so far. See [`Carryable::Residual`] for its type.
+    ///
+    /// ```rust
+    /// use roundable::{CarryRounder, Tie};
+    ///
+    /// let mut rounder = CarryRounder::new(10u8).unwrap();
+    /// assert!(0 == rounder.residual());
+    /// assert!(10 == rounder.round(14, Tie::Up));
+    /// assert!(4 == rounder.residual());
+    /// assert!(10 == rounder.round(8, Tie::Up));
+    /// assert!(2 == rounder.residual());
+    /// ```
+    #[must_use]
+    pub fn residual(&self) -> T::Residual {
+        self.residual
+    }
+
+    /// Discard the residual, so that the next value is rounded on its own.
+    ///
+    /// ```rust
+    /// use roundable::{CarryRounder, Tie};
+    ///
+    /// let mut rounder = CarryRounder::new(10).unwrap();
+    /// assert!(0 == rounder.round(4, Tie::Up));
+    /// rounder.reset();
+    /// assert!(0 == rounder.residual());
+    /// assert!(0 == rounder.round(4, Tie::Up));
+    /// ```
+    pub fn reset(&mut self) {
+        self.residual = T::Residual::default();
+    }
+
+    /// Add the residual to `value`, round it to the nearest factor, and carry
+    /// the difference to the next value. Panics if there is an overflow.
+    ///
+    /// ```rust
+    /// use roundable::{CarryRounder, Tie, SECOND};
+    /// use std::time::Duration;
+    ///
+    /// let mut rounder = CarryRounder::new(SECOND).unwrap();
+    /// let value = Duration::from_millis(1_200);
+    /// assert!(SECOND == rounder.round(value, Tie::Up));
+    /// assert!(SECOND == rounder.round(value, Tie::Up));
+    /// assert!(2 * SECOND == rounder.round(value, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if rounding would return a value that does not fit in the
+    /// return type.
+    pub fn round<S>(&mut self, value: T, tie: S) -> T
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(self.checked_round(value, tie), "round")
+    }
+
+    /// Add the residual to `value`, round it to the nearest factor, and carry
+    /// the difference to the next value. Returns `None` if there is an
+    /// overflow, and leaves the residual unchanged.
+    ///
+    /// ```rust
+    /// use roundable::{CarryRounder, Tie};
+    ///
+    /// let mut rounder = CarryRounder::new(10u8).unwrap();
+    /// assert!(Some(250) == rounder.try_round(254, Tie::Up));
+    /// assert!(None == rounder.try_round(252, Tie::Up));
+    /// assert!(4 == rounder.residual());
+    /// ```
+    pub fn try_round<S>(&mut self, value: T, tie: S) -> Option<T>
+    where
+        S: TieStrategy,
+    {
+        self.checked_round(value, tie).ok()
+    }
+
+    /// Add the residual to `value`, round it to the nearest factor, and carry
+    /// the difference to the next value. Returns a [`RoundError`] on failure,
+    /// and leaves the residual unchanged.
+    ///
+    /// ```rust
+    /// use roundable::{CarryRounder, RoundError, Tie};
+    ///
+    /// let mut rounder = CarryRounder::new(1.0).unwrap();
+    /// assert!(Ok(2.0) == rounder.checked_round(1.5, Tie::Up));
+    /// assert!(Err(RoundError::NanInput) == rounder.checked_round(f64::NAN, Tie::Up));
+    /// assert!(-0.5 == rounder.residual());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Roundable::checked_round_to()`], usually
+    /// [`RoundError::Overflow`] if the result does not fit in the type.
+    pub fn checked_round<S>(
+        &mut self,
+        value: T,
+        tie: S,
+    ) -> Result<T, RoundError>
+    where
+        S: TieStrategy,
+    {
+        let (rounded, residual) =
+            value.checked_round_carrying(self.residual, self.factor, tie)?;
+        self.residual = residual;
+        Ok(rounded)
+    }
+}
+
+/// Values that can be rounded with a [`CarryRounder`].
+///
+/// This is implemented for integers, floats, and [`Duration`].
+pub trait Carryable: Roundable + Copy {
+    /// The difference between a value and the value rounded.
+    ///
+    /// Since a value can be rounded up, this is negative when it is larger
+    /// than the original value. For signed integers and floats, this is the
+    /// same type as the value. For unsigned integers, it is the signed integer
+    /// of the same size, which can hold half of any factor. For [`Duration`],
+    /// it is a number of nanoseconds.
+    type Residual: Copy + Debug + Default;
+
+    /// Add `residual` to the value and round it to the nearest `factor`.
+    /// Returns the rounded value and the difference between the rounded
+    /// value and the value plus `residual`.
+    ///
+    /// The sum does not have to fit in the type, as long as the rounded value
+    /// does, except for 128-bit integers. For unsigned integers and
+    /// [`Duration`], a negative sum rounds to 0 and all of it is carried, since
+    /// 0 is the nearest multiple that fits.
+    ///
+    /// ```rust
+    /// use roundable::{Carryable, Tie};
+    ///
+    /// assert!(Ok((10, 3)) == 8u8.checked_round_carrying(5, 10, Tie::Up));
+    /// assert!(Ok((0, -4)) == 2u8.checked_round_carrying(-6, 10, Tie::Up));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Roundable::checked_round_to()`]: usually
+    /// [`RoundError::NonPositiveFactor`] if `factor` is not positive, or
+    /// [`RoundError::Overflow`] if the result does not fit in the type. Unlike
+    /// [`Roundable::checked_round_to()`], an infinite float factor also fails
+    /// with [`RoundError::NonPositiveFactor`], since it would carry everything.
+    fn checked_round_carrying<S>(
+        self,
+        residual: Self::Residual,
+        factor: Self,
+        tie: S,
+    ) -> Result<(Self, Self::Residual), RoundError>
+    where
+        S: TieStrategy;
+}
+
+/// Add `residual` to `value`, round it to the nearest `factor`, and return the
+/// rounded value and the new residual.
+///
+/// If `unsigned` is true, a negative sum rounds to 0 instead.
+#[allow(clippy::arithmetic_side_effects)]
+fn round_carrying_i128<S>(
+    value: i128,
+    residual: i128,
+    factor: i128,
+    unsigned: bool,
+    tie: S,
+) -> Result<(i128, i128), RoundError>
+where
+    S: TieStrategy,
+{
+    let adjusted = value.checked_add(residual).ok_or(RoundError::Overflow)?;
+    if unsigned && adjusted < 0 {
+        return Ok((0, adjusted));
+    }
+
+    let rounded = adjusted.checked_round_to(factor, tie)?;
+
+    // Safe: rounded is the multiple of factor nearest to adjusted, so this is
+    // at most half of factor.
+    Ok((rounded, adjusted - rounded))
+}
+
+/// Implement [`Carryable`] for integer types up to 64 bits.
+///
+/// The sum of the value and the residual is computed as an `i128`, so it
+/// never overflows.
+macro_rules! carryable_integer {
+    ($($ty:ident $residual:ident;)+) => {$(
+        impl Carryable for $ty {
+            type Residual = $residual;
+
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_possible_wrap,
+                unused_comparisons
+            )]
+            fn checked_round_carrying<S>(
+                self,
+                residual: $residual,
+                factor: Self,
+                tie: S,
+            ) -> Result<(Self, $residual), RoundError>
+            where
+                S: TieStrategy,
+            {
+                if factor <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                let (rounded, residual) = round_carrying_i128(
+                    self as i128,
+                    residual as i128,
+                    factor as i128,
+                    $ty::MIN == 0,
+                    tie,
+                )?;
+                let rounded =
+                    rounded.try_into().map_err(|_| RoundError::Overflow)?;
+
+                // The residual is at most half of factor, so it fits.
+                Ok((rounded, residual as $residual))
+            }
+        }
+    )+}
+}
+
+carryable_integer! {
+    u8 i8; u16 i16; u32 i32; u64 i64; usize isize;
+    i8 i8; i16 i16; i32 i32; i64 i64; isize isize;
+}
+
+impl Carryable for i128 {
+    type Residual = Self;
+
+    fn checked_round_carrying<S>(
+        self,
+        residual: Self,
+        factor: Self,
+        tie: S,
+    ) -> Result<(Self, Self), RoundError>
+    where
+        S: TieStrategy,
+    {
+        if factor <= 0 {
+            return Err(RoundError::NonPositiveFactor);
+        }
+
+        round_carrying_i128(self, residual, factor, false, tie)
+    }
+}
+
+impl Carryable for u128 {
+    type Residual = i128;
+
+    #[allow(
+        clippy::arithmetic_side_effects,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    fn checked_round_carrying<S>(
+        self,
+        residual: i128,
+        factor: Self,
+        tie: S,
+    ) -> Result<(Self, i128), RoundError>
+    where
+        S: TieStrategy,
+    {
+        if factor == 0 {
+            return Err(RoundError::NonPositiveFactor);
+        }
+
+        let adjusted = if residual >= 0 {
+            self.checked_add(residual as Self)
+                .ok_or(RoundError::Overflow)?
+        } else if let Some(adjusted) = self.checked_sub(residual.unsigned_abs())
+        {
+            adjusted
+        } else {
+            // The sum is negative, so it rounds to 0. Safe: self is less than
+            // -residual, so it fits in an i128 and the sum doesn’t overflow.
+            return Ok((0, residual + self as i128));
+        };
+
+        let rounded = adjusted.checked_round_to(factor, tie)?;
+
+        // The residual is at most half of factor, so it fits in an i128.
+        let residual = if adjusted >= rounded {
+            (adjusted - rounded) as i128
+        } else {
+            -((rounded - adjusted) as i128)
+        };
+        Ok((rounded, residual))
+    }
+}
+
+/// Implement [`Carryable`] for floating point types.
+macro_rules! carryable_float {
+    ($($ty:ident)+) => {$(
+        impl Carryable for $ty {
+            type Residual = Self;
+
+            #[allow(clippy::arithmetic_side_effects)]
+            fn checked_round_carrying<S>(
+                self,
+                residual: Self,
+                factor: Self,
+                tie: S,
+            ) -> Result<(Self, Self), RoundError>
+            where
+                S: TieStrategy,
+            {
+                if !(factor.is_finite() && factor > 0.0) {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                let adjusted = self + residual;
+                let rounded = adjusted.checked_round_to(factor, tie)?;
+                Ok((rounded, adjusted - rounded))
+            }
+        }
+    )+}
+}
+
+carryable_float!(f32 f64);
+
+impl Carryable for Duration {
+    type Residual = i128;
+
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    fn checked_round_carrying<S>(
+        self,
+        residual: i128,
+        factor: Self,
+        tie: S,
+    ) -> Result<(Self, i128), RoundError>
+    where
+        S: TieStrategy,
+    {
+        if factor == Self::ZERO {
+            return Err(RoundError::NonPositiveFactor);
+        }
+
+        // Duration uses at most 94 bits as nanoseconds, so these fit.
+        let (rounded, residual) = round_carrying_i128(
+            self.as_nanos() as i128,
+            residual,
+            factor.as_nanos() as i128,
+            true,
+            tie,
+        )?;
+        let rounded = checked_nanos_to_duration(rounded as u128)?;
+        Ok((rounded, residual))
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::arithmetic_side_effects,
+    clippy::float_cmp,
+    clippy::integer_division
+)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random, TIES};
+    use crate::Tie;
+    use assert2::check;
+
+    /// Factors to check exhaustively with every value and residual.
+    const FACTORS: [u8; 15] =
+        [1, 2, 3, 4, 5, 7, 10, 16, 99, 100, 101, 127, 128, 200, 255];
+
+    /// Round `value + residual` in `i32`, where it can’t overflow. If
+    /// `unsigned` is true, a negative sum rounds to 0.
+    fn reference(
+        value: i32,
+        residual: i32,
+        factor: i32,
+        unsigned: bool,
+        tie: Tie,
+    ) -> (i32, i32) {
+        let adjusted = value + residual;
+        if unsigned && adjusted < 0 {
+            return (0, adjusted);
+        }
+        let rounded = adjusted.round_to(factor, tie);
+        (rounded, adjusted - rounded)
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn u8_every_value_and_residual() {
+        for factor in FACTORS {
+            let half = i32::from(factor / 2);
+            for residual in -half..=half {
+                for value in u8::MIN..=u8::MAX {
+                    for tie in TIES {
+                        let (rounded, expected_residual) = reference(
+                            i32::from(value),
+                            residual,
+                            i32::from(factor),
+                            true,
+                            tie,
+                        );
+                        let expected = u8::try_from(rounded)
+                            .map(|rounded| (rounded, expected_residual as i8))
+                            .map_err(|_| RoundError::Overflow);
+                        check!(
+                            expected
+                                == value.checked_round_carrying(
+                                    residual as i8,
+                                    factor,
+                                    tie
+                                ),
+                            "{} {} {} {:?}",
+                            value,
+                            residual,
+                            factor,
+                            tie
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn u128_matches_u8_reference() {
+        // u128 has its own implementation for negative sums.
+        for factor in FACTORS {
+            let half = i32::from(factor / 2);
+            for residual in -half..=half {
+                for value in u8::MIN..=u8::MAX {
+                    for tie in TIES {
+                        let (rounded, expected_residual) = reference(
+                            i32::from(value),
+                            residual,
+                            i32::from(factor),
+                            true,
+                            tie,
+                        );
+                        let expected = u128::try_from(rounded)
+                            .map(|rounded| {
+                                (rounded, i128::from(expected_residual))
+                            })
+                            .map_err(|_| RoundError::Overflow);
+                        check!(
+                            expected
+                                == u128::from(value).checked_round_carrying(
+                                    i128::from(residual),
+                                    u128::from(factor),
+                                    tie
+                                ),
+                            "{} {} {} {:?}",
+                            value,
+                            residual,
+                            factor,
+                            tie
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn i8_every_value_and_residual() {
+        for factor in FACTORS.iter().filter_map(|&f| i8::try_from(f).ok()) {
+            let half = i32::from(factor / 2);
+            for residual in -half..=half {
+                for value in i8::MIN..=i8::MAX {
+                    for tie in TIES {
+                        let (rounded, expected_residual) = reference(
+                            i32::from(value),
+                            residual,
+                            i32::from(factor),
+                            false,
+                            tie,
+                        );
+                        let expected = i8::try_from(rounded)
+                            .map(|rounded| (rounded, expected_residual as i8))
+                            .map_err(|_| RoundError::Overflow);
+                        check!(
+                            expected
+                                == value.checked_round_carrying(
+                                    residual as i8,
+                                    factor,
+                                    tie
+                                ),
+                            "{} {} {} {:?}",
+                            value,
+                            residual,
+                            factor,
+                            tie
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_wrap)]
+    fn sum_tracks_values() {
+        let mut random = random();
+        for tie in TIES {
+            for _ in 0..20 {
+                let factor = (random.next().unwrap() % 1_000) as i64 + 1;
+                let mut rounder = CarryRounder::new(factor).unwrap();
+                let mut total = 0;
+                let mut rounded_total = 0;
+                for value in random.by_ref().take(1_000) {
+                    let value = (value >> 40) as i64 - (1 << 23);
+                    total += value;
+                    rounded_total += rounder.round(value, tie);
+                    check!(total == rounded_total + rounder.residual());
+                    check!(rounder.residual().abs() <= factor / 2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wide_extremes() {
+        check!(
+            Ok((u128::MAX - 5, -3))
+                == (u128::MAX - 5).checked_round_carrying(-3, 10, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == u128::MAX.checked_round_carrying(1, 10, Tie::Up)
+        );
+        check!(
+            Ok((0, i128::MIN + 1))
+                == 0u128.checked_round_carrying(
+                    i128::MIN + 1,
+                    u128::MAX,
+                    Tie::Up
+                )
+        );
+        check!(
+            Ok((i128::MIN + 2, 0))
+                == i128::MIN.checked_round_carrying(2, 3, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == i128::MAX.checked_round_carrying(1, 3, Tie::Up)
+        );
+    }
+
+    #[test]
+    fn unsigned_negative_sum() {
+        let mut rounder = CarryRounder::new(10u8).unwrap();
+        check!(20 == rounder.round(15, Tie::AwayFromZero));
+        check!(-5 == rounder.residual());
+        check!(Ok(0) == rounder.checked_round(0, Tie::AwayFromZero));
+        check!(-5 == rounder.residual());
+        check!(0 == rounder.round(0, Tie::Up));
+        check!(-5 == rounder.residual());
+        check!(0 == rounder.round(3, Tie::Up));
+        check!(-2 == rounder.residual());
+
+        // A negative sum rounds to 0 and is carried, so the rounder never gets
+        // stuck.
+        let mut rounder = CarryRounder::new(10u32).unwrap();
+        check!(10 == rounder.round(5, Tie::AwayFromZero));
+        check!(Ok(0) == rounder.checked_round(0, Tie::AwayFromZero));
+        check!(-5 == rounder.residual());
+        check!(Ok(0) == rounder.checked_round(2, Tie::AwayFromZero));
+        check!(-3 == rounder.residual());
+        check!(Ok(10) == rounder.checked_round(8, Tie::AwayFromZero));
+        check!(-5 == rounder.residual());
+
+        let mut rounder = CarryRounder::new(10u128).unwrap();
+        check!(10 == rounder.round(5, Tie::AwayFromZero));
+        check!(Ok(0) == rounder.checked_round(0, Tie::AwayFromZero));
+        check!(-5 == rounder.residual());
+        check!(
+            Ok((0, i128::MIN + 1))
+                == 1u128.checked_round_carrying(i128::MIN, 10, Tie::Up)
+        );
+    }
+
+    #[test]
+    fn floats() {
+        let mut rounder = CarryRounder::new(1.0).unwrap();
+        let total: f64 = (0..10).map(|_| rounder.round(0.1, Tie::Up)).sum();
+        check!(1.0 == total);
+        check!(rounder.residual().abs() < 1e-15);
+
+        let mut rounder = CarryRounder::new(0.5f32).unwrap();
+        check!(0.5 == rounder.round(0.3, Tie::Up));
+        check!(0.0 == rounder.round(0.1, Tie::Up));
+        check!(Ok(0.5) == rounder.checked_round(0.4, Tie::Up));
+        check!(
+            Err(RoundError::InfiniteInput)
+                == rounder.checked_round(f32::INFINITY, Tie::Up)
+        );
+        check!(
+            Err(RoundError::InfiniteInput)
+                == f64::MAX.checked_round_carrying(f64::MAX, 1.0, Tie::Up)
+        );
+    }
+
+    #[test]
+    fn durations() {
+        let second = Duration::from_secs(1);
+        let mut rounder = CarryRounder::new(second).unwrap();
+        check!(2 * second == rounder.round(second * 3 / 2, Tie::AwayFromZero));
+        check!(-500_000_000 == rounder.residual());
+        check!(
+            Ok(Duration::ZERO)
+                == rounder.checked_round(Duration::ZERO, Tie::AwayFromZero)
+        );
+        check!(-500_000_000 == rounder.residual());
+        check!(Duration::ZERO == rounder.round(Duration::ZERO, Tie::Up));
+        check!(-500_000_000 == rounder.residual());
+
+        let mut rounder = CarryRounder::new(second).unwrap();
+        let value = Duration::new(u64::MAX, 400_000_000);
+        check!(Duration::from_secs(u64::MAX) == rounder.round(value, Tie::Up));
+        check!(400_000_000 == rounder.residual());
+        check!(None == rounder.try_round(value, Tie::Up));
+        check!(400_000_000 == rounder.residual());
+    }
+
+    #[test]
+    fn invalid_factors() {
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == CarryRounder::new(0u8).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == CarryRounder::new(-1i64).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == CarryRounder::new(f64::NAN).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == CarryRounder::new(-0.5f32).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == CarryRounder::new(f64::INFINITY).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == CarryRounder::new(f32::NEG_INFINITY).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 5.0f64.checked_round_carrying(0.0, f64::INFINITY, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == CarryRounder::new(Duration::ZERO).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 5u128.checked_round_carrying(0, 0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 5i128.checked_round_carrying(0, 0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 5i32.checked_round_carrying(0, -1, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == Duration::ZERO.checked_round_carrying(
+                    0,
+                    Duration::ZERO,
+                    Tie::Up
+                )
+        );
+    }
+
+    #[test]
+    fn reset() {
+        let mut rounder = CarryRounder::new(10i32).unwrap();
+        check!(0 == rounder.round(4, Tie::Up));
+        check!(10 == rounder.round(4, Tie::Up));
+        check!(-2 == rounder.residual());
+        rounder.reset();
+        check!(0 == rounder.residual());
+        check!(0 == rounder.round(4, Tie::Up));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow while rounding")]
+    fn round_overflow() {
+        let mut rounder = CarryRounder::new(10u8).unwrap();
+        let _ = rounder.round(255, Tie::Up);
+    }
+}
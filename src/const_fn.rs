@@ -0,0 +1,337 @@
+//! Rounding functions that can be used in `const` contexts.
+//!
+//! [`Roundable`] functions are trait methods, so they can’t be used to define
+//! constants. These functions work just like [`Roundable::round_to()`] and
+//! [`Roundable::try_round_to()`], but they are `const fn`:
+//!
+//! ```rust
+//! use roundable::{const_fn, Tie};
+//!
+//! /// Timeout in milliseconds, rounded to the nearest 100 ms.
+//! const TIMEOUT_MS: u64 = const_fn::round_u64_to(1_250, 100, Tie::TowardEven);
+//! assert!(TIMEOUT_MS == 1_200);
+//!
+//! const SMALL: Option<u8> = const_fn::try_round_u8_to(255, 10, Tie::Up);
+//! assert!(SMALL == None);
+//! ```
+//!
+//! If rounding fails while defining a constant, it fails to compile:
+//!
+//! ```rust,compile_fail
+//! use roundable::{const_fn, Tie};
+//!
+//! const OVERFLOW: u8 = const_fn::round_u8_to(255, 10, Tie::Up);
+//! ```
+//!
+//! `panic!()` can’t be used in a `const fn` before Rust 1.57, so these
+//! functions index past the end of an array to fail instead. The error (or
+//! panic, if they are called at run time) is therefore “index out of bounds”.
+//!
+//! Functions to round [`Duration`] require the
+//! `const-duration` feature, since they need Rust 1.58 or later.
+//!
+//! [`Roundable`]: crate::Roundable
+//! [`Roundable::round_to()`]: crate::Roundable::round_to()
+//! [`Roundable::try_round_to()`]: crate::Roundable::try_round_to()
+
+use crate::int::Rounding;
+use crate::Tie;
+#[cfg(any(doc, feature = "const-duration"))]
+use core::time::Duration;
+
+/// Fail if `failed` is true.
+///
+/// In a `const` context this fails to compile, and otherwise it panics.
+const fn fail_if(failed: bool) {
+    let () = [()][failed as usize];
+}
+
+/// Define `const fn` rounding functions for integer types.
+macro_rules! const_round_integer {
+    ($($ty:ident $round:ident $try_round:ident;)+) => {$(
+        #[doc = concat!(
+            "Round a `", stringify!($ty), "` to the nearest `factor`. Panics ",
+            "if there is an overflow.\n",
+            "\n",
+            "This is the same as [`Roundable::round_to()`](crate::Roundable::round_to()), ",
+            "but it is a `const fn`.\n",
+            "\n",
+            "```rust\n",
+            "use roundable::{const_fn::", stringify!($round), ", Tie};\n",
+            "\n",
+            "const ROUNDED: ", stringify!($ty), " = ",
+            stringify!($round), "(15, 10, Tie::Up);\n",
+            "assert!(ROUNDED == 20);\n",
+            "```\n",
+            "\n",
+            "# Panics\n",
+            "\n",
+            "Panics if `factor` is not positive, or if rounding would return ",
+            "a value that does not fit in the return type. In a `const` ",
+            "context, this fails to compile instead.",
+        )]
+        #[must_use]
+        pub const fn $round(value: $ty, factor: $ty, tie: Tie) -> $ty {
+            if let Some(rounded) = $try_round(value, factor, tie) {
+                rounded
+            } else {
+                fail_if(true);
+                value
+            }
+        }
+
+        #[doc = concat!(
+            "Round a `", stringify!($ty), "` to the nearest `factor`. ",
+            "Returns `None` if there is an overflow.\n",
+            "\n",
+            "This is the same as [`Roundable::try_round_to()`](crate::Roundable::try_round_to()), ",
+            "but it is a `const fn`.\n",
+            "\n",
+            "```rust\n",
+            "use roundable::{const_fn::", stringify!($try_round), ", Tie};\n",
+            "\n",
+            "const ROUNDED: Option<", stringify!($ty), "> = ",
+            stringify!($try_round), "(15, 10, Tie::Up);\n",
+            "assert!(ROUNDED == Some(20));\n",
+            "assert!(", stringify!($try_round), "(",
+            stringify!($ty), "::MAX, 10, Tie::Up) == None);\n",
+            "```\n",
+            "\n",
+            "# Panics\n",
+            "\n",
+            "Panics if `factor` is not positive. In a `const` context, this ",
+            "fails to compile instead.",
+        )]
+        #[allow(unused_comparisons)]
+        #[must_use]
+        pub const fn $try_round(
+            value: $ty,
+            factor: $ty,
+            tie: Tie,
+        ) -> Option<$ty> {
+            fail_if(factor <= 0);
+            Rounding::<$ty>::try_nearest_const(value, factor, tie)
+        }
+    )+}
+}
+
+const_round_integer! {
+    i8 round_i8_to try_round_i8_to;
+    i16 round_i16_to try_round_i16_to;
+    i32 round_i32_to try_round_i32_to;
+    i64 round_i64_to try_round_i64_to;
+    i128 round_i128_to try_round_i128_to;
+    isize round_isize_to try_round_isize_to;
+    u8 round_u8_to try_round_u8_to;
+    u16 round_u16_to try_round_u16_to;
+    u32 round_u32_to try_round_u32_to;
+    u64 round_u64_to try_round_u64_to;
+    u128 round_u128_to try_round_u128_to;
+    usize round_usize_to try_round_usize_to;
+}
+
+/// Round a [`Duration`] to the nearest `factor`. Panics if there is an
+/// overflow.
+///
+/// This is the same as
+/// [`Roundable::round_to()`](crate::Roundable::round_to()), but it is a
+/// `const fn`. Requires the `const-duration` feature.
+///
+/// ```rust
+/// use roundable::{const_fn::round_duration_to, Tie};
+/// use std::time::Duration;
+///
+/// const TIMEOUT: Duration = round_duration_to(
+///     Duration::from_millis(1_234),
+///     Duration::from_millis(100),
+///     Tie::Up,
+/// );
+/// assert!(TIMEOUT == Duration::from_millis(1_200));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `factor` is zero, or if rounding would return a value that does
+/// not fit in a `Duration`. In a `const` context, this fails to compile
+/// instead.
+#[cfg(feature = "const-duration")]
+#[must_use]
+pub const fn round_duration_to(
+    value: Duration,
+    factor: Duration,
+    tie: Tie,
+) -> Duration {
+    if let Some(rounded) = try_round_duration_to(value, factor, tie) {
+        rounded
+    } else {
+        fail_if(true);
+        value
+    }
+}
+
+/// Round a [`Duration`] to the nearest `factor`. Returns `None` if there is an
+/// overflow.
+///
+/// This is the same as
+/// [`Roundable::try_round_to()`](crate::Roundable::try_round_to()), but it is
+/// a `const fn`. Requires the `const-duration` feature.
+///
+/// ```rust
+/// use roundable::{const_fn::try_round_duration_to, Tie, SECOND};
+/// use std::time::Duration;
+///
+/// const ROUNDED: Option<Duration> =
+///     try_round_duration_to(Duration::from_millis(1_500), SECOND, Tie::Up);
+/// assert!(ROUNDED == Some(Duration::from_secs(2)));
+/// assert!(try_round_duration_to(Duration::MAX, SECOND, Tie::Up) == None);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `factor` is zero. In a `const` context, this fails to compile
+/// instead.
+#[cfg(feature = "const-duration")]
+#[must_use]
+pub const fn try_round_duration_to(
+    value: Duration,
+    factor: Duration,
+    tie: Tie,
+) -> Option<Duration> {
+    let factor = factor.as_nanos();
+    fail_if(factor == 0);
+
+    // Duration will always fit into u128 as nanoseconds.
+    match Rounding::<u128>::try_nearest_const(value.as_nanos(), factor, tie) {
+        Some(nanos) => crate::duration::try_nanos_to_duration_const(nanos),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TIES;
+    use crate::Roundable;
+    use assert2::check;
+
+    // Make sure these actually work in a const context.
+    const ROUNDED_I32: i32 = round_i32_to(-15, 10, Tie::TowardZero);
+    const OVERFLOWED_U8: Option<u8> = try_round_u8_to(255, 10, Tie::Up);
+
+    #[test]
+    fn const_context() {
+        check!(ROUNDED_I32 == -10);
+        check!(OVERFLOWED_U8 == None);
+    }
+
+    #[test]
+    fn u8_exhaustive() {
+        for tie in TIES {
+            for factor in 1..=u8::MAX {
+                for value in u8::MIN..=u8::MAX {
+                    check!(
+                        value.try_round_to(factor, tie)
+                            == try_round_u8_to(value, factor, tie),
+                        "{} {} {:?}",
+                        value,
+                        factor,
+                        tie
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn i8_exhaustive() {
+        for tie in TIES {
+            for factor in 1..=i8::MAX {
+                for value in i8::MIN..=i8::MAX {
+                    check!(
+                        value.try_round_to(factor, tie)
+                            == try_round_i8_to(value, factor, tie),
+                        "{} {} {:?}",
+                        value,
+                        factor,
+                        tie
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_integers() {
+        for tie in TIES {
+            for factor in [1, 2, 3, 10, 1_000, 1 << 40, i64::MAX] {
+                for value in [i64::MIN, -1_500, -5, 0, 5, 1_500, i64::MAX] {
+                    check!(
+                        value.try_round_to(factor, tie)
+                            == try_round_i64_to(value, factor, tie),
+                        "{} {} {:?}",
+                        value,
+                        factor,
+                        tie
+                    );
+                }
+            }
+            for factor in [1, 2, 3, 10, 1 << 100, u128::MAX] {
+                for value in [0, 5, 1_500, u128::MAX >> 1, u128::MAX] {
+                    check!(
+                        value.try_round_to(factor, tie)
+                            == try_round_u128_to(value, factor, tie),
+                        "{} {} {:?}",
+                        value,
+                        factor,
+                        tie
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "const-duration")]
+    #[test]
+    fn duration() {
+        use crate::{MILLISECOND, SECOND};
+
+        for tie in TIES {
+            for factor in [Duration::from_nanos(3), MILLISECOND, SECOND] {
+                for value in [
+                    Duration::ZERO,
+                    Duration::from_millis(500),
+                    Duration::from_millis(1_500),
+                    Duration::from_nanos(1_234_567_890),
+                    Duration::MAX,
+                ] {
+                    check!(
+                        value.try_round_to(factor, tie)
+                            == try_round_duration_to(value, factor, tie),
+                        "{:?} {:?} {:?}",
+                        value,
+                        factor,
+                        tie
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn overflow() {
+        let _ = round_u8_to(255, 10, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn zero_factor() {
+        let _ = try_round_i32_to(10, 0, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn negative_factor() {
+        let _ = try_round_i32_to(10, -10, Tie::Up);
+    }
+}
@@ -0,0 +1,183 @@
+//! Round integers to a factor known at compile time.
+
+use crate::{unwrap_rounded, Direction, RoundError, TieStrategy};
+
+/// Methods to round integers to a factor that is a constant.
+///
+/// This is implemented for integers. These work just like the [`Roundable`]
+/// functions, except that the factor is passed as a const generic parameter.
+/// That lets the compiler replace the division with cheaper operations, and
+/// means that the factor is checked at compile time instead of run time.
+///
+/// ```rust
+/// use roundable::{ConstRoundable, Direction, Tie};
+///
+/// assert!(2_000 == 1_500u64.round_to_const::<_, 1_000>(Tie::Up));
+/// assert!(-1_000 == (-1_500i32).round_to_const::<_, 1_000>(Tie::Up));
+/// assert!(None == 255u8.try_round_to_const::<_, 10>(Tie::Up));
+/// assert!(1_000 == 1u32.round_directed_to_const::<1_000>(Direction::Up));
+/// ```
+///
+/// The first generic parameter of the methods that round to the nearest
+/// `FACTOR` is the [`TieStrategy`], which can be inferred with `_`. Like with
+/// [`Roundable`], any tie strategy works, including the zero-sized types in
+/// [`tie`](crate::tie):
+///
+/// ```rust
+/// use roundable::{tie, ConstRoundable};
+///
+/// assert!(2_000 == 1_500u64.round_to_const::<_, 1_000>(tie::TowardEven));
+/// ```
+///
+/// The factor is always a `u128`. It must be positive and fit in the type being
+/// rounded, or the code will fail to compile:
+///
+/// ```rust,compile_fail
+/// use roundable::{ConstRoundable, Tie};
+///
+/// let _ = 10u64.round_to_const::<_, 0>(Tie::Up);
+/// ```
+///
+/// ```rust,compile_fail
+/// use roundable::{ConstRoundable, Tie};
+///
+/// let _ = 10u8.round_to_const::<_, 256>(Tie::Up);
+/// ```
+///
+/// [`Roundable`]: crate::Roundable
+pub trait ConstRoundable: Sized {
+    /// Round to the nearest `FACTOR`. Panics if there is an overflow.
+    ///
+    /// This is the same as
+    /// [`Roundable::round_to()`](crate::Roundable::round_to()).
+    ///
+    /// ```rust
+    /// use roundable::{ConstRoundable, Tie};
+    ///
+    /// assert!(315 == 314.round_to_const::<_, 5>(Tie::Up));
+    /// assert!(-10 == (-15).round_to_const::<_, 10>(Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if rounding would return a value that does not fit in the return
+    /// type.
+    #[must_use]
+    fn round_to_const<S, const FACTOR: u128>(self, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            self.checked_round_to_const::<S, FACTOR>(tie),
+            "round_to_const",
+        )
+    }
+
+    /// Round to the nearest `FACTOR`. Returns `None` if there is an overflow.
+    ///
+    /// This is the same as
+    /// [`Roundable::try_round_to()`](crate::Roundable::try_round_to()), but it
+    /// never panics since the factor is checked at compile time.
+    ///
+    /// ```rust
+    /// use roundable::{ConstRoundable, Tie};
+    ///
+    /// assert!(Some(250) == 254u8.try_round_to_const::<_, 10>(Tie::Up));
+    /// assert!(None == 255u8.try_round_to_const::<_, 10>(Tie::Up));
+    /// ```
+    #[must_use]
+    fn try_round_to_const<S, const FACTOR: u128>(self, tie: S) -> Option<Self>
+    where
+        S: TieStrategy,
+    {
+        self.checked_round_to_const::<S, FACTOR>(tie).ok()
+    }
+
+    /// Round to the nearest `FACTOR`. Returns a [`RoundError`] on failure.
+    ///
+    /// This is the same as
+    /// [`Roundable::checked_round_to()`](crate::Roundable::checked_round_to()).
+    ///
+    /// ```rust
+    /// use roundable::{ConstRoundable, RoundError, Tie};
+    ///
+    /// assert!(Ok(250) == 254u8.checked_round_to_const::<_, 10>(Tie::Up));
+    /// assert!(Err(RoundError::Overflow) == 255u8.checked_round_to_const::<_, 10>(Tie::Up));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::Overflow`] if the result does not fit in the
+    /// return type.
+    fn checked_round_to_const<S, const FACTOR: u128>(
+        self,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+
+    /// Round to the next `FACTOR` in `direction`. Panics if there is an
+    /// overflow.
+    ///
+    /// This is the same as
+    /// [`Roundable::round_directed_to()`](crate::Roundable::round_directed_to()).
+    ///
+    /// ```rust
+    /// use roundable::{ConstRoundable, Direction};
+    ///
+    /// assert!(300 == 314.round_directed_to_const::<50>(Direction::Down));
+    /// assert!(-350 == (-314).round_directed_to_const::<50>(Direction::Down));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if rounding would return a value that does not fit in the return
+    /// type.
+    #[must_use]
+    fn round_directed_to_const<const FACTOR: u128>(
+        self,
+        direction: Direction,
+    ) -> Self {
+        unwrap_rounded(
+            self.checked_round_directed_to_const::<FACTOR>(direction),
+            "round_directed_to_const",
+        )
+    }
+
+    /// Round to the next `FACTOR` in `direction`. Returns `None` if there is
+    /// an overflow.
+    ///
+    /// This is the same as
+    /// [`Roundable::try_round_directed_to()`](crate::Roundable::try_round_directed_to()),
+    /// but it never panics since the factor is checked at compile time.
+    ///
+    /// ```rust
+    /// use roundable::{ConstRoundable, Direction};
+    ///
+    /// assert!(Some(250) == 251u8.try_round_directed_to_const::<10>(Direction::Down));
+    /// assert!(None == 251u8.try_round_directed_to_const::<10>(Direction::Up));
+    /// ```
+    #[must_use]
+    fn try_round_directed_to_const<const FACTOR: u128>(
+        self,
+        direction: Direction,
+    ) -> Option<Self> {
+        self.checked_round_directed_to_const::<FACTOR>(direction)
+            .ok()
+    }
+
+    /// Round to the next `FACTOR` in `direction`. Returns a [`RoundError`] on
+    /// failure.
+    ///
+    /// This is the same as
+    /// [`Roundable::checked_round_directed_to()`](crate::Roundable::checked_round_directed_to()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::Overflow`] if the result does not fit in the
+    /// return type.
+    fn checked_round_directed_to_const<const FACTOR: u128>(
+        self,
+        direction: Direction,
+    ) -> Result<Self, RoundError>;
+}
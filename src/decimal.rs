@@ -0,0 +1,378 @@
+//! Round floats to a number of decimal places.
+
+use crate::scientific::{Buffer, Scientific};
+use crate::{rounded_or_none, unwrap_rounded, RoundError, TieStrategy};
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+/// Methods to round floats to a number of decimal places.
+///
+/// Rounding to `0.01` with [`Roundable::round_to()`](crate::Roundable) works
+/// with the exact binary value of the float, which is often not the decimal
+/// number it looks like. For example, `2.675_f64` is actually slightly less
+/// than 2.675, so it rounds down to 2.67.
+///
+/// These methods instead round the shortest decimal representation of the
+/// value (what `{}` prints), so `2.675_f64` is a tie:
+///
+/// ```rust
+/// use roundable::{DecimalRoundable, Roundable, Tie};
+///
+/// assert!(2.67 == 2.675.round_to(0.01, Tie::Up));
+/// assert!(2.68 == 2.675.round_to_decimal_places(2, Tie::Up));
+/// ```
+///
+/// Negative `places` round to tens, hundreds, and so on:
+///
+/// ```rust
+/// use roundable::{DecimalRoundable, Tie};
+///
+/// assert!(1200.0 == 1250.0.round_to_decimal_places(-2, Tie::TowardEven));
+/// ```
+pub trait DecimalRoundable: Sized {
+    /// Round to `places` digits after the decimal point. Panics if the value
+    /// can’t be rounded.
+    ///
+    /// Ties are decided by the shortest decimal representation of the value,
+    /// and resolved according to `tie`.
+    /// [`Tie::TowardEven`](crate::Tie::TowardEven) and
+    /// [`Tie::TowardOdd`](crate::Tie::TowardOdd) refer to the last digit that
+    /// is kept.
+    ///
+    /// ```rust
+    /// use roundable::{DecimalRoundable, Tie};
+    ///
+    /// assert!(1.01 == 1.005.round_to_decimal_places(2, Tie::Up));
+    /// assert!(-2.67 == (-2.675).round_to_decimal_places(2, Tie::Up));
+    /// assert!(2.66 == 2.665.round_to_decimal_places(2, Tie::TowardEven));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is NaN or infinite, or if the result is infinite.
+    #[must_use]
+    fn round_to_decimal_places<S>(self, places: i32, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            self.checked_round_to_decimal_places(places, tie),
+            "try_round_to_decimal_places",
+        )
+    }
+
+    /// Round to `places` digits after the decimal point. Returns `None` if the
+    /// value can’t be rounded.
+    ///
+    /// See [`DecimalRoundable::round_to_decimal_places()`].
+    ///
+    /// ```rust
+    /// use roundable::{DecimalRoundable, Tie};
+    ///
+    /// assert!(Some(0.01) == 0.005.try_round_to_decimal_places(2, Tie::Up));
+    /// assert!(None == f64::NAN.try_round_to_decimal_places(2, Tie::Up));
+    /// assert!(None == f64::MAX.try_round_to_decimal_places(-308, Tie::Up));
+    /// ```
+    #[must_use]
+    fn try_round_to_decimal_places<S>(self, places: i32, tie: S) -> Option<Self>
+    where
+        S: TieStrategy,
+    {
+        rounded_or_none(
+            self.checked_round_to_decimal_places(places, tie),
+            "try_round_to_decimal_places",
+        )
+    }
+
+    /// Round to `places` digits after the decimal point. Returns a
+    /// [`RoundError`] on failure.
+    ///
+    /// See [`DecimalRoundable::round_to_decimal_places()`].
+    ///
+    /// ```rust
+    /// use roundable::{DecimalRoundable, RoundError, Tie};
+    ///
+    /// assert!(Ok(3.1) == 3.14.checked_round_to_decimal_places(1, Tie::Up));
+    /// assert!(
+    ///     Err(RoundError::NanInput)
+    ///         == f64::NAN.checked_round_to_decimal_places(1, Tie::Up)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::NanInput`] or [`RoundError::InfiniteInput`] if
+    /// the value isn’t finite, and [`RoundError::NonFiniteResult`] if the
+    /// result is too large to represent.
+    fn checked_round_to_decimal_places<S>(
+        self,
+        places: i32,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+}
+
+/// Implement decimal rounding for float types.
+macro_rules! decimal_roundable_float {
+    ($($ty:ident)+) => {$(
+        impl DecimalRoundable for $ty {
+            fn checked_round_to_decimal_places<S>(
+                self,
+                places: i32,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if self.is_nan() {
+                    return Err(RoundError::NanInput);
+                } else if self.is_infinite() {
+                    return Err(RoundError::InfiniteInput);
+                }
+
+                round_decimal(self, |_| i64::from(places), tie)
+                    .filter(|value| value.is_finite())
+                    .ok_or(RoundError::NonFiniteResult)
+            }
+        }
+    )+}
+}
+
+decimal_roundable_float!(f32 f64);
+
+/// Round the shortest decimal representation of `value` to a number of decimal
+/// places.
+///
+/// `places` is passed the exponent of the first significant digit of `value`,
+/// and returns the number of decimal places to keep.
+///
+/// Returns `None` if the value can’t be formatted or parsed, which shouldn’t
+/// happen for finite floats. The result may be infinite.
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use decimal::*`.
+pub(crate) fn round_decimal<T, F, S>(value: T, places: F, tie: S) -> Option<T>
+where
+    T: fmt::LowerExp + FromStr,
+    F: FnOnce(i64) -> i64,
+    S: TieStrategy,
+{
+    let scientific = Scientific::new(&value)?;
+    match round_scientific(scientific, places(scientific.exponent), tie) {
+        None => Some(value),
+        Some(rounded) => rounded.as_str().parse().ok(),
+    }
+}
+
+/// Round a number in scientific notation to `places` decimal places.
+///
+/// Returns `None` if the number already has no more than `places` decimal
+/// places, or a buffer containing the rounded number in scientific notation.
+fn round_scientific<S>(
+    mut scientific: Scientific,
+    places: i64,
+    tie: S,
+) -> Option<Buffer>
+where
+    S: TieStrategy,
+{
+    let Scientific { negative, exponent, len, .. } = scientific;
+
+    // The first digit is in the 10^exponent place, so we keep the digits down
+    // to the 10^-places place.
+    let keep = exponent.saturating_add(places).saturating_add(1);
+    let keep = match usize::try_from(keep) {
+        Ok(keep) if keep >= len => return None,
+        Ok(keep) => keep,
+        // No digits are kept, and the value is less than a tenth of a unit in
+        // the last place, so it rounds to zero.
+        Err(_) => 0,
+    };
+
+    let away = if exponent.saturating_add(places) < -1 {
+        false
+    } else {
+        let (kept, dropped) = scientific.digits().split_at(keep);
+        match dropped.first().map(|&first| first.cmp(&b'5')) {
+            Some(Ordering::Greater) => true,
+            Some(Ordering::Equal)
+                if dropped[1..].iter().any(|&digit| digit != b'0') =>
+            {
+                true
+            }
+            Some(Ordering::Equal) => tie.is_away_from_zero(negative, || {
+                kept.last().map_or(false, |&digit| digit % 2 == 1)
+            }),
+            _ => false,
+        }
+    };
+
+    let digits = &mut scientific.digits;
+    let mut kept = &mut digits[..keep];
+    if away && !increment(kept) {
+        // All the digits were 9, so the carry adds a leading 1.
+        kept = &mut digits[..=keep];
+        kept[0] = b'1';
+        for digit in &mut kept[1..] {
+            *digit = b'0';
+        }
+    }
+
+    let mut rounded = Buffer::new();
+    if negative {
+        rounded.write_char('-').ok()?;
+    }
+    if kept.is_empty() {
+        rounded.write_char('0').ok()?;
+    }
+    for &digit in kept.iter() {
+        rounded.write_char(char::from(digit)).ok()?;
+    }
+    write!(rounded, "e{}", places.saturating_neg()).ok()?;
+    Some(rounded)
+}
+
+/// Add one to a string of decimal digits in place.
+///
+/// Returns `false` if it overflowed, i.e. if every digit was 9 (or there were
+/// no digits). In that case every digit is left as 0.
+fn increment(digits: &mut [u8]) -> bool {
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            #[allow(clippy::arithmetic_side_effects)]
+            {
+                *digit += 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::Tie;
+    use assert2::check;
+
+    #[test]
+    fn round_decimal_places_ties() {
+        check!(2.68 == 2.675_f64.round_to_decimal_places(2, Tie::Up));
+        check!(2.67 == 2.675_f64.round_to_decimal_places(2, Tie::Down));
+        check!(2.68 == 2.675_f64.round_to_decimal_places(2, Tie::TowardEven));
+        check!(2.67 == 2.675_f64.round_to_decimal_places(2, Tie::TowardOdd));
+        check!(2.66 == 2.665_f64.round_to_decimal_places(2, Tie::TowardEven));
+        check!(1.01 == 1.005_f64.round_to_decimal_places(2, Tie::Up));
+        check!(2.68 == 2.675_f32.round_to_decimal_places(2, Tie::Up));
+        check!(1.01 == 1.005_f32.round_to_decimal_places(2, Tie::Up));
+    }
+
+    #[test]
+    fn round_decimal_places_negative_values() {
+        check!(-2.67 == (-2.675_f64).round_to_decimal_places(2, Tie::Up));
+        check!(-2.68 == (-2.675_f64).round_to_decimal_places(2, Tie::Down));
+        check!(
+            -2.67 == (-2.675_f64).round_to_decimal_places(2, Tie::TowardZero)
+        );
+        check!(
+            -2.68 == (-2.675_f64).round_to_decimal_places(2, Tie::AwayFromZero)
+        );
+        check!(-1.23 == (-1.234_f64).round_to_decimal_places(2, Tie::Up));
+        check!(-1.24 == (-1.236_f64).round_to_decimal_places(2, Tie::Up));
+    }
+
+    #[test]
+    fn round_decimal_places_non_ties() {
+        check!(6.0 == 5.83619_f64.round_to_decimal_places(0, Tie::Up));
+        check!(5.8 == 5.83619_f64.round_to_decimal_places(1, Tie::Up));
+        check!(5.836 == 5.83619_f64.round_to_decimal_places(3, Tie::Up));
+        check!(5.8362 == 5.83619_f64.round_to_decimal_places(4, Tie::Down));
+        check!(2.0 == 2.0_f64.round_to_decimal_places(2, Tie::Up));
+        check!(
+            0.3 == 0.300_000_000_000_000_04_f64
+                .round_to_decimal_places(2, Tie::Up)
+        );
+        check!(
+            0.123_456_789
+                == 0.123_456_789_f64.round_to_decimal_places(20, Tie::Up)
+        );
+    }
+
+    #[test]
+    fn round_decimal_places_carry() {
+        check!(1.0 == 0.995_f64.round_to_decimal_places(2, Tie::Up));
+        check!(10.0 == 9.96_f64.round_to_decimal_places(1, Tie::Up));
+        check!(1000.0 == 999.5_f64.round_to_decimal_places(0, Tie::Up));
+        check!(-1000.0 == (-999.5_f64).round_to_decimal_places(0, Tie::Down));
+    }
+
+    #[test]
+    fn round_decimal_places_negative_places() {
+        check!(1230.0 == 1234.5_f64.round_to_decimal_places(-1, Tie::Up));
+        check!(120.0 == 125.0_f64.round_to_decimal_places(-1, Tie::TowardEven));
+        check!(130.0 == 125.0_f64.round_to_decimal_places(-1, Tie::TowardOdd));
+        check!(
+            1200.0 == 1250.0_f64.round_to_decimal_places(-2, Tie::TowardEven)
+        );
+        check!(
+            1400.0 == 1350.0_f64.round_to_decimal_places(-2, Tie::TowardEven)
+        );
+        check!(1e300 == 1.2e300_f64.round_to_decimal_places(-300, Tie::Up));
+        check!(1e308 == 9.9e307_f64.round_to_decimal_places(-308, Tie::Up));
+    }
+
+    #[test]
+    fn round_decimal_places_to_zero() {
+        check!(0.0 == 0.004_f64.round_to_decimal_places(2, Tie::Up));
+        check!(0.0 == 1e-10_f64.round_to_decimal_places(2, Tie::Up));
+        check!(0.0 == 0.005_f64.round_to_decimal_places(2, Tie::Down));
+        check!(0.01 == 0.005_f64.round_to_decimal_places(2, Tie::Up));
+        check!(0.0 == 0.005_f64.round_to_decimal_places(2, Tie::TowardEven));
+        check!(0.01 == 0.005_f64.round_to_decimal_places(2, Tie::TowardOdd));
+        check!(0.0 == 0.0_f64.round_to_decimal_places(2, Tie::Up));
+        check!(0.0 == 123.0_f64.round_to_decimal_places(i32::MIN, Tie::Up));
+
+        // The sign is preserved.
+        let zero = (-0.004_f64).round_to_decimal_places(2, Tie::Up);
+        check!(zero == 0.0);
+        check!(zero.is_sign_negative());
+    }
+
+    #[test]
+    fn round_decimal_places_extremes() {
+        check!(
+            f64::MIN_POSITIVE
+                == f64::MIN_POSITIVE.round_to_decimal_places(400, Tie::Up)
+        );
+        check!(123.0 == 123.0_f64.round_to_decimal_places(i32::MAX, Tie::Up));
+        check!(f64::MAX == f64::MAX.round_to_decimal_places(0, Tie::Up));
+        check!(f32::MAX == f32::MAX.round_to_decimal_places(0, Tie::Up));
+        check!(3e38 == f32::MAX.round_to_decimal_places(-38, Tie::Up));
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f64::MAX.checked_round_to_decimal_places(-308, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f32::MAX.checked_round_to_decimal_places(-32, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NanInput)
+                == f64::NAN.checked_round_to_decimal_places(2, Tie::Up)
+        );
+        check!(
+            Err(RoundError::InfiniteInput)
+                == f32::NEG_INFINITY
+                    .checked_round_to_decimal_places(2, Tie::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot round NaN")]
+    fn round_decimal_places_nan_panics() {
+        let _ = f64::NAN.round_to_decimal_places(2, Tie::Up);
+    }
+}
@@ -0,0 +1,276 @@
+//! Divide by a fixed divisor with multiplication instead of division.
+//!
+//! This uses the same technique as [libdivide](https://libdivide.com): find a
+//! “magic” multiplier `m` and shift `s` such that `n / d` is the high half of
+//! `n × m` shifted right by `s` for all `n` of a given size.
+
+use crate::power::multiply_wide;
+use crate::{Direction, RoundError, TieStrategy};
+
+/// A precomputed unsigned divisor.
+///
+/// Every unsigned integer type uses this, so values are stored as `u128`. The
+/// functions take the size of the type in bits so that the work can be done
+/// in the native size after inlining.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Divisor {
+    /// The divisor. Always positive.
+    value: u128,
+
+    /// The magic multiplier, or 0 if `divisor` is a power of two.
+    magic: u128,
+
+    /// How far to shift the high half of the product right.
+    shift: u32,
+
+    /// Whether the magic multiplier needs one more bit than the type has. If
+    /// so, `magic` holds the low bits and the extra bit is added separately.
+    add: bool,
+}
+
+impl Divisor {
+    /// Precompute division by `divisor`, which must be positive, for
+    /// `bits`-bit unsigned integers.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub const fn new(divisor: u128, bits: u32) -> Self {
+        let mask = u128::MAX >> (128 - bits);
+
+        // Safe: divisor is positive. This is floor(log2(divisor)).
+        let shift = 127 - divisor.leading_zeros();
+
+        if divisor.is_power_of_two() {
+            return Self { value: divisor, magic: 0, shift, add: false };
+        }
+
+        // Divide 2^(bits + shift) by divisor with long division, one bit at a
+        // time. 2^shift < divisor, so the quotient fits in bits.
+        let mut quotient: u128 = 0;
+        let mut remainder: u128 = 1 << shift;
+        let mut i = 0;
+        while i < bits {
+            // Shift the next (zero) bit of the dividend into the remainder.
+            let carry = remainder >> (bits - 1) != 0;
+            remainder = (remainder << 1) & mask;
+            quotient <<= 1;
+            if carry || remainder >= divisor {
+                remainder = remainder.wrapping_sub(divisor) & mask;
+                quotient |= 1;
+            }
+            i += 1;
+        }
+
+        // Safe: remainder < divisor.
+        if divisor - remainder < 1 << shift {
+            // 2^(bits + shift) / divisor is precise enough.
+            Self {
+                value: divisor,
+                magic: quotient.wrapping_add(1) & mask,
+                shift,
+                add: false,
+            }
+        } else {
+            // We need 2^(bits + shift + 1) / divisor, which needs an extra bit.
+            let twice_remainder = (remainder << 1) & mask;
+            let mut quotient = (quotient << 1) & mask;
+            if twice_remainder >= divisor || twice_remainder < remainder {
+                quotient = quotient.wrapping_add(1);
+            }
+            Self {
+                value: divisor,
+                magic: quotient.wrapping_add(1) & mask,
+                shift,
+                add: true,
+            }
+        }
+    }
+
+    /// Get `(n / divisor, n % divisor)` for a `bits`-bit unsigned `n`.
+    #[allow(clippy::arithmetic_side_effects)]
+    #[inline]
+    pub const fn divide(&self, n: u128, bits: u32) -> (u128, u128) {
+        if bits <= 64 {
+            // Doing the work in u64 is faster, and makes it possible to
+            // vectorize. Safe: n and divisor fit in 64 bits.
+            #[allow(clippy::cast_possible_truncation)]
+            let (quotient, remainder) = self.divide_u64(n as u64, bits);
+            return (quotient as u128, remainder as u128);
+        }
+
+        // Safe: quotient × divisor ≤ n.
+        let quotient = self.quotient(n);
+        (quotient, n - quotient * self.value)
+    }
+
+    /// Get `(n / divisor, n % divisor)` for a `bits`-bit unsigned `n`, where
+    /// `bits` ≤ 64.
+    #[allow(clippy::arithmetic_side_effects, clippy::cast_possible_truncation)]
+    #[inline]
+    const fn divide_u64(&self, n: u64, bits: u32) -> (u64, u64) {
+        // Masking tells the compiler that the values fit in bits.
+        let mask = u64::MAX >> (64 - bits);
+        let n = n & mask;
+        let divisor = self.value as u64 & mask;
+        let quotient = self.quotient_u64(n, bits);
+
+        // Safe: quotient × divisor ≤ n.
+        (quotient, n - quotient * divisor)
+    }
+
+    /// Get `n / divisor` for a 128-bit unsigned `n`.
+    #[allow(clippy::arithmetic_side_effects)]
+    #[inline]
+    const fn quotient(&self, n: u128) -> u128 {
+        if self.magic == 0 {
+            return n >> self.shift;
+        }
+
+        let high = multiply_wide(n, self.magic).0;
+        if self.add {
+            // Add n × 2^bits for the extra bit of magic, and then shift it
+            // all right one bit. This is (n + high) / 2 without overflowing.
+            // Safe: high ≤ n.
+            (((n - high) >> 1) + high) >> self.shift
+        } else {
+            high >> self.shift
+        }
+    }
+
+    /// Get `n / divisor` for a `bits`-bit unsigned `n`, where `bits` ≤ 64.
+    ///
+    /// See [`Self::quotient()`].
+    #[allow(clippy::arithmetic_side_effects, clippy::cast_possible_truncation)]
+    #[inline]
+    const fn quotient_u64(&self, n: u64, bits: u32) -> u64 {
+        if self.magic == 0 {
+            return n >> self.shift;
+        }
+
+        let magic = self.magic as u64 & (u64::MAX >> (64 - bits));
+        let high = if bits <= 32 {
+            // Safe: both are less than 2^32.
+            (n * magic) >> bits
+        } else {
+            // Safe: both are less than 2^64.
+            ((n as u128 * magic as u128) >> bits) as u64
+        };
+
+        if self.add {
+            // See quotient(). Safe: high ≤ n.
+            (((n - high) >> 1) + high) >> self.shift
+        } else {
+            high >> self.shift
+        }
+    }
+}
+
+/// Round with a precomputed [`Divisor`].
+///
+/// This is implemented by `roundable_integer!` and for `Duration`. This module
+/// is private, so [`Precomputable`](crate::Precomputable) can’t be implemented
+/// outside of this crate.
+pub trait RoundWithDivisor: Sized {
+    /// Precompute division by `factor`, or return an error if `factor` is not
+    /// positive.
+    fn divisor(factor: Self) -> Result<Divisor, RoundError>;
+
+    /// Round to the nearest `factor`. `divisor` must be
+    /// `Self::divisor(factor)`.
+    fn checked_round_with<S>(
+        self,
+        factor: Self,
+        divisor: &Divisor,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+
+    /// Round to the next `factor` in `direction`. `divisor` must be
+    /// `Self::divisor(factor)`.
+    fn checked_round_directed_with(
+        self,
+        factor: Self,
+        divisor: &Divisor,
+        direction: Direction,
+    ) -> Result<Self, RoundError>;
+}
+
+#[cfg(test)]
+#[allow(clippy::arithmetic_side_effects, clippy::integer_division)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+    use assert2::check;
+
+    /// Check that `Divisor` matches `/` and `%` for `bits`-bit values.
+    fn check_divide(n: u128, divisor: u128, bits: u32) {
+        check!(
+            (n / divisor, n % divisor)
+                == Divisor::new(divisor, bits).divide(n, bits),
+            "{} / {} ({} bits)",
+            n,
+            divisor,
+            bits
+        );
+    }
+
+    /// Generate pseudo-random `u128`s of varying size.
+    fn random_u128() -> impl Iterator<Item = u128> {
+        let mut random = test_util::random().map(u128::from);
+        core::iter::repeat_with(move || {
+            // Vary the size so small values are tested too.
+            let high = random.next().unwrap();
+            let low = random.next().unwrap();
+            ((high << 64) | low) >> (random.next().unwrap() % 128)
+        })
+    }
+
+    #[test]
+    fn divide_u8_exhaustive() {
+        for divisor in 1..=u128::from(u8::MAX) {
+            for n in 0..=u128::from(u8::MAX) {
+                check_divide(n, divisor, 8);
+            }
+        }
+    }
+
+    #[test]
+    fn divide_u16() {
+        for divisor in 1..=u128::from(u16::MAX) {
+            for n in [0, 1, divisor - 1, divisor, 65_534, 65_535] {
+                check_divide(n, divisor, 16);
+            }
+        }
+    }
+
+    #[test]
+    fn divide_large() {
+        for bits in [32, 64, 128] {
+            let max = u128::MAX >> (128 - bits);
+
+            // Check a divisor against edge cases and random values.
+            let check_divisor = |divisor: u128| {
+                for n in [0, 1, divisor - 1, divisor, max - 1, max] {
+                    check_divide(n, divisor, bits);
+                }
+                for n in random_u128().take(200) {
+                    check_divide(n & max, divisor, bits);
+                }
+            };
+
+            for divisor in [3, 7, 10, 1_000, max / 2, max - 1, max] {
+                check_divisor(divisor);
+            }
+            for shift in 1..bits {
+                let power = 1 << shift;
+                check_divisor(power - 1);
+                check_divisor(power);
+                check_divisor(power + 1);
+            }
+            for divisor in
+                random_u128().map(|n| n & max).filter(|&n| n > 0).take(200)
+            {
+                check_divisor(divisor);
+            }
+        }
+    }
+}
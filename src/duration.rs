@@ -1,6 +1,13 @@
 //! Functions, constants, etc. related to Duration.
 
-use crate::{Roundable, Tie};
+use crate::divisor::{Divisor, RoundWithDivisor};
+use crate::origin::round_u128_with_origin;
+use crate::set::nearest_index;
+use crate::{
+    Direction, OriginRoundable, Precomputable, RandomSource, RoundError,
+    Roundable, SaturatingRoundable, SetRoundable, StochasticRoundable,
+    TieBreaker, TieBreakerRoundable, TieStrategy,
+};
 use core::time::Duration;
 
 /// A microsecond. Useful for rounding [`Duration`].
@@ -54,11 +61,181 @@ pub const MINUTE: Duration = Duration::from_secs(60);
 pub const HOUR: Duration = Duration::from_secs(60 * 60);
 
 impl Roundable for Duration {
-    fn try_round_to(self, factor: Self, tie: Tie) -> Option<Self> {
+    fn checked_round_to<S>(
+        self,
+        factor: Self,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy,
+    {
         // Duration will always fit into u128 as nanoseconds.
         self.as_nanos()
-            .try_round_to(factor.as_nanos(), tie)
-            .map(nanos_to_duration)
+            .checked_round_to(factor.as_nanos(), tie)
+            .and_then(checked_nanos_to_duration)
+    }
+
+    fn checked_round_directed_to(
+        self,
+        factor: Self,
+        direction: Direction,
+    ) -> Result<Self, RoundError> {
+        self.as_nanos()
+            .checked_round_directed_to(factor.as_nanos(), direction)
+            .and_then(checked_nanos_to_duration)
+    }
+}
+
+impl SaturatingRoundable for Duration {
+    fn saturating_round_to<S>(self, factor: Self, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        assert!(
+            factor > Self::ZERO,
+            "saturating_round_to() requires positive factor",
+        );
+
+        // This can’t overflow a u128, since Duration uses at most 94 bits.
+        checked_nanos_to_duration(
+            self.as_nanos().round_to(factor.as_nanos(), tie),
+        )
+        .unwrap_or(Self::MAX)
+    }
+}
+
+impl TieBreakerRoundable for Duration {
+    #[allow(clippy::arithmetic_side_effects)]
+    fn checked_round_to_with_breaker<B>(
+        self,
+        factor: Self,
+        breaker: &mut B,
+    ) -> Result<Self, RoundError>
+    where
+        B: TieBreaker<Self> + ?Sized,
+    {
+        // Duration will always fit into u128 as nanoseconds.
+        let value = self.as_nanos();
+        let factor_nanos = factor.as_nanos();
+        let lower =
+            value.checked_round_directed_to(factor_nanos, Direction::Down)?;
+
+        // Safe: lower ≤ value < lower + factor_nanos, and both use at most 94
+        // bits, so their sum fits in a u128.
+        let remainder = value - lower;
+        let upper = lower + factor_nanos;
+
+        let half = factor_nanos >> 1;
+        let up = if factor_nanos & 1 == 0 && remainder == half {
+            !breaker
+                .break_tie(
+                    self,
+                    checked_nanos_to_duration(lower).ok(),
+                    checked_nanos_to_duration(upper).ok(),
+                    factor,
+                )
+                .is_toward_zero(false)
+        } else {
+            remainder > half
+        };
+
+        checked_nanos_to_duration(if up { upper } else { lower })
+    }
+}
+
+impl OriginRoundable for Duration {
+    fn checked_round_to_with_origin<S>(
+        self,
+        factor: Self,
+        origin: Self,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy,
+    {
+        if factor == Self::ZERO {
+            return Err(RoundError::NonPositiveFactor);
+        }
+
+        // Duration will always fit into u128 as nanoseconds.
+        round_u128_with_origin(
+            self.as_nanos(),
+            factor.as_nanos(),
+            origin.as_nanos(),
+            Self::MAX.as_nanos(),
+            false,
+            tie,
+        )
+        .and_then(checked_nanos_to_duration)
+    }
+}
+
+impl SetRoundable for Duration {
+    #[allow(clippy::arithmetic_side_effects)]
+    fn checked_round_to_set_index<S>(
+        self,
+        values: &[Self],
+        tie: S,
+    ) -> Result<usize, RoundError>
+    where
+        S: TieStrategy,
+    {
+        nearest_index(self, values, false, tie, |value, lower, upper| {
+            let value = value.as_nanos();
+
+            // Safe: lower < value < upper.
+            (value - lower.as_nanos()).cmp(&(upper.as_nanos() - value))
+        })
+    }
+}
+
+impl StochasticRoundable for Duration {
+    fn checked_round_stochastic_to<R>(
+        self,
+        factor: Self,
+        random: &mut R,
+    ) -> Result<Self, RoundError>
+    where
+        R: RandomSource + ?Sized,
+    {
+        self.as_nanos()
+            .checked_round_stochastic_to(factor.as_nanos(), random)
+            .and_then(checked_nanos_to_duration)
+    }
+}
+
+impl Precomputable for Duration {}
+
+impl RoundWithDivisor for Duration {
+    fn divisor(factor: Self) -> Result<Divisor, RoundError> {
+        u128::divisor(factor.as_nanos())
+    }
+
+    #[inline]
+    fn checked_round_with<S>(
+        self,
+        factor: Self,
+        divisor: &Divisor,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy,
+    {
+        self.as_nanos()
+            .checked_round_with(factor.as_nanos(), divisor, tie)
+            .and_then(checked_nanos_to_duration)
+    }
+
+    #[inline]
+    fn checked_round_directed_with(
+        self,
+        factor: Self,
+        divisor: &Divisor,
+        direction: Direction,
+    ) -> Result<Self, RoundError> {
+        self.as_nanos()
+            .checked_round_directed_with(factor.as_nanos(), divisor, direction)
+            .and_then(checked_nanos_to_duration)
     }
 }
 
@@ -85,8 +262,6 @@ impl Roundable for Duration {
 /// ```
 #[must_use]
 pub fn nanos_to_duration(total: u128) -> Duration {
-    /// Just to make things clear.
-    const NANOS_PER_SECOND: u128 = 1_000_000_000;
     #[allow(clippy::integer_division)]
     Duration::new(
         (total / NANOS_PER_SECOND).try_into().expect(
@@ -96,10 +271,55 @@ pub fn nanos_to_duration(total: u128) -> Duration {
     )
 }
 
+/// Nanoseconds in a second. Just to make things clear.
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// Precomputed division by [`NANOS_PER_SECOND`].
+///
+/// Dividing a `u128` is slow, and this is used every time a `Duration` is
+/// rounded.
+const NANOS_PER_SECOND_DIVISOR: Divisor = Divisor::new(NANOS_PER_SECOND, 128);
+
+/// Create a new [`Duration`] from a `u128` of nanoseconds, or return
+/// [`RoundError::Overflow`] if it won’t fit.
+///
+/// See [`nanos_to_duration()`].
+#[inline]
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use duration::*`.
+pub(crate) fn checked_nanos_to_duration(
+    total: u128,
+) -> Result<Duration, RoundError> {
+    let (seconds, nanos) = NANOS_PER_SECOND_DIVISOR.divide(total, 128);
+    let seconds = seconds.try_into().map_err(|_| RoundError::Overflow)?;
+    #[allow(clippy::cast_possible_truncation)] // Always less than 1e9.
+    Ok(Duration::new(seconds, nanos as u32))
+}
+
+/// Create a new [`Duration`] from a `u128` of nanoseconds, or return `None` if
+/// it won’t fit.
+///
+/// This is the same as [`checked_nanos_to_duration()`], but it can be used in a
+/// `const fn`. `Duration::new()` is only `const` in Rust 1.58 and later.
+#[cfg(feature = "const-duration")]
+#[allow(clippy::incompatible_msrv)] // The feature requires Rust 1.58.
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use duration::*`.
+pub(crate) const fn try_nanos_to_duration_const(
+    total: u128,
+) -> Option<Duration> {
+    let (seconds, nanos) = NANOS_PER_SECOND_DIVISOR.divide(total, 128);
+    if seconds > u64::MAX as u128 {
+        None
+    } else {
+        #[allow(clippy::cast_possible_truncation)] // Checked or less than 1e9.
+        Some(Duration::new(seconds as u64, nanos as u32))
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::cognitive_complexity)]
 mod tests {
     use super::*;
+    use crate::Tie;
     use assert2::check;
 
     /// Convenient alias for [`Duration::from_millis()`].
@@ -152,6 +372,86 @@ mod tests {
         check!(Duration::MAX == Duration::MAX.round_to(Duration::MAX, Tie::Up));
     }
 
+    #[test]
+    fn round_overflow() {
+        check!(None == Duration::MAX.try_round_to(SECOND, Tie::Down));
+        check!(
+            Some(Duration::new(u64::MAX, 0))
+                == Duration::new(u64::MAX, 500_000_000)
+                    .try_round_to(SECOND, Tie::Down)
+        );
+    }
+
+    #[test]
+    fn checked_round() {
+        check!(Ok(SECOND) == ms(700).checked_round_to(SECOND, Tie::Up));
+        check!(
+            Err(RoundError::Overflow)
+                == Duration::MAX.checked_round_to(SECOND, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == SECOND.checked_round_to(Duration::ZERO, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == Duration::MAX
+                    .checked_round_directed_to(ms(3), Direction::Up)
+        );
+    }
+
+    #[test]
+    fn saturating_round() {
+        check!(SECOND == ms(700).saturating_round_to(SECOND, Tie::Up));
+        check!(
+            Duration::MAX == Duration::MAX.saturating_round_to(SECOND, Tie::Up)
+        );
+        check!(
+            Duration::MAX
+                == Duration::MAX.saturating_round_to(Duration::MAX, Tie::Up)
+        );
+        check!(
+            Duration::MAX
+                == Duration::new(u64::MAX, 500_000_000)
+                    .saturating_round_to(SECOND, Tie::Up)
+        );
+        check!(
+            Duration::new(u64::MAX, 0)
+                == Duration::new(u64::MAX, 500_000_000)
+                    .saturating_round_to(SECOND, Tie::Down)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "saturating_round_to() requires positive factor")]
+    fn saturating_round_zero_factor() {
+        let _ = SECOND.saturating_round_to(Duration::ZERO, Tie::Up);
+    }
+
+    #[test]
+    fn round_directed() {
+        check!(SECOND == ms(1).round_directed_to(SECOND, Direction::Up));
+        check!(ms(0) == ms(999).round_directed_to(SECOND, Direction::Down));
+        check!(
+            ms(0) == ms(999).round_directed_to(SECOND, Direction::TowardZero)
+        );
+        check!(
+            SECOND == ms(1).round_directed_to(SECOND, Direction::AwayFromZero)
+        );
+        check!(SECOND == SECOND.round_directed_to(ms(500), Direction::Up));
+    }
+
+    #[test]
+    fn round_directed_overflow() {
+        check!(
+            None == Duration::MAX.try_round_directed_to(SECOND, Direction::Up)
+        );
+        check!(
+            Some(Duration::new(u64::MAX, 0))
+                == Duration::MAX.try_round_directed_to(SECOND, Direction::Down)
+        );
+    }
+
     #[test]
     #[should_panic(expected = "try_round_to() requires positive factor")]
     fn round_to_zero_factor() {
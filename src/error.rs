@@ -0,0 +1,98 @@
+//! Errors that can occur while rounding.
+
+use core::fmt;
+
+/// Why a value could not be rounded.
+///
+/// This is returned by the `checked_` functions, e.g.
+/// [`Roundable::checked_round_to()`](crate::Roundable::checked_round_to()).
+///
+/// ```rust
+/// use roundable::{RoundError, Roundable, Tie};
+///
+/// assert!(Ok(260) == 255.checked_round_to(10, Tie::Up));
+/// assert!(Err(RoundError::Overflow) == 255u8.checked_round_to(10, Tie::Up));
+/// assert!(Err(RoundError::NonPositiveFactor) == 255.checked_round_to(0, Tie::Up));
+/// assert!(Err(RoundError::NanInput) == f64::NAN.checked_round_to(1.0, Tie::Up));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum RoundError {
+    /// The rounded value does not fit in the type.
+    Overflow,
+
+    /// The factor was not positive, e.g. it was 0, negative, or NaN.
+    NonPositiveFactor,
+
+    /// The value to round was NaN.
+    NanInput,
+
+    /// The value to round was infinite.
+    InfiniteInput,
+
+    /// The rounded value was not finite, e.g. it overflowed to infinity.
+    NonFiniteResult,
+
+    /// The rounded value was zero, but the type can’t represent zero, e.g.
+    /// [`NonZeroU32`](core::num::NonZeroU32).
+    ZeroResult,
+
+    /// The number of significant digits to round to was zero.
+    ZeroDigits,
+
+    /// The alignment was not a power of two.
+    InvalidAlignment,
+
+    /// The ratios to split a value by were all zero.
+    ZeroRatios,
+
+    /// The set of values to round to was empty.
+    EmptySet,
+
+    /// The origin of the grid to round to was not finite, e.g. it was NaN.
+    InvalidOrigin,
+}
+
+impl fmt::Display for RoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Overflow => "overflow while rounding",
+            Self::NonPositiveFactor => "rounding requires positive factor",
+            Self::NanInput => "cannot round NaN",
+            Self::InfiniteInput => "cannot round infinite value",
+            Self::NonFiniteResult => "rounding produced non-finite value",
+            Self::ZeroResult => "rounding produced zero",
+            Self::ZeroDigits => {
+                "rounding requires at least one significant digit"
+            }
+            Self::InvalidAlignment => "alignment must be a power of two",
+            Self::ZeroRatios => "splitting requires a non-zero ratio",
+            Self::EmptySet => "rounding requires a non-empty set",
+            Self::InvalidOrigin => "rounding requires finite origin",
+        })
+    }
+}
+
+// `std::error::Error` is the same trait as `core::error::Error` in Rust 1.81
+// and later, but `core::error::Error` isn’t available in our MSRV.
+#[cfg(feature = "std")]
+impl std::error::Error for RoundError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+
+    extern crate std;
+    use std::string::ToString;
+
+    #[test]
+    fn display() {
+        check!(RoundError::Overflow.to_string() == "overflow while rounding");
+        check!(
+            RoundError::NonPositiveFactor.to_string()
+                == "rounding requires positive factor"
+        );
+        check!(RoundError::NanInput.to_string() == "cannot round NaN");
+    }
+}
@@ -1,64 +1,440 @@
 //! Implement `Roundable` for floats.
 
-use crate::{Roundable, Tie};
+use crate::set::nearest_index;
+use crate::slice::{round_in_place, round_into, TieTable};
+use crate::stochastic::is_away_ratio;
+use crate::{
+    Direction, OriginRoundable, RandomSource, RoundError, Roundable,
+    SetRoundable, SliceRoundable, StochasticRoundable, TieBreaker,
+    TieBreakerRoundable, TieStrategy,
+};
+use core::cmp::Ordering;
+
+/// Return an error from the current function if either the value or the factor
+/// can’t be used for rounding.
+macro_rules! check_float_arguments {
+    ($value:expr, $factor:expr) => {
+        if $factor.is_nan() || $factor <= 0.0 {
+            return Err(RoundError::NonPositiveFactor);
+        } else if $value.is_nan() {
+            return Err(RoundError::NanInput);
+        } else if $value.is_infinite() {
+            return Err(RoundError::InfiniteInput);
+        }
+    };
+}
+
+/// Evaluate to `Ok(value)` if it is finite, or an error if it is not.
+macro_rules! finite {
+    ($value:expr) => {{
+        let value = $value;
+        if value.is_finite() {
+            Ok(value)
+        } else {
+            Err(RoundError::NonFiniteResult)
+        }
+    }};
+}
+
+/// A float being rounded, split into a multiple of the factor and the rest.
+///
+/// Everything is stored as a magnitude so that rounding is symmetric around 0.
+#[derive(Clone, Copy, Debug)]
+struct Parts<T> {
+    /// The magnitude of the value.
+    magnitude: T,
+
+    /// The magnitude of the distance between the value and the multiple of
+    /// `factor` between the value and 0. Always less than `factor`.
+    remainder: T,
+
+    /// The factor the value is being rounded to. Always positive.
+    factor: T,
+
+    /// Whether the original value was negative.
+    negative: bool,
+}
 
 /// Implement rounding for floating point types.
 macro_rules! roundable_float {
     ($($ty:ident)+) => {$(
-        impl Roundable for $ty {
-            #[allow(clippy::arithmetic_side_effects, unused_comparisons)]
-            fn try_round_to(self, factor: Self, tie: Tie) -> Option<Self> {
-                /// `$ty::abs(self)` is not in std.
-                fn abs ( v: $ty ) -> $ty {
-                    if v < 0.0 {
-                        -v
-                    } else {
-                        v
-                    }
+        impl Parts<$ty> {
+            /// Split `value` into parts. `factor` must be positive and finite.
+            #[allow(clippy::arithmetic_side_effects)]
+            fn new(value: $ty, factor: $ty) -> Self {
+                // `$ty::abs()` is not in core.
+                let magnitude = if value < 0.0 { -value } else { value };
+
+                Self {
+                    magnitude,
+                    // This is always exact.
+                    remainder: magnitude % factor,
+                    factor,
+                    negative: value < 0.0,
                 }
+            }
 
-                assert!(
-                    factor > 0.0,
-                    "try_round_to() requires positive factor",
-                );
+            /// Get `a - b` and the error from rounding it, so that their sum is
+            /// exactly `a - b` (Knuth’s two-sum algorithm).
+            #[allow(clippy::arithmetic_side_effects)]
+            fn difference(a: $ty, b: $ty) -> ($ty, $ty) {
+                let difference = a - b;
+                let rounded_a = difference + b;
+                let rounded_b = rounded_a - difference;
+                (difference, (a - rounded_a) + (rounded_b - b))
+            }
 
-                let remainder = self % factor;
-                let base = self - remainder;
-
-                let use_smaller = || {
-                    match tie {
-                        Tie::Up => false,
-                        Tie::Down => true,
-                        Tie::TowardZero => self > 0.0,
-                        Tie::AwayFromZero => self < 0.0,
-                        Tie::TowardEven =>
-                            (abs((base / factor) % 2.0) < Self::EPSILON)
-                            ^ (self < 0.0),
-                        Tie::TowardOdd =>
-                            (abs((base / factor) % 2.0) >= Self::EPSILON)
-                            ^ (self < 0.0),
-                    }
+            /// Is the value closer to the multiple of `factor` toward 0 (`Less`),
+            /// the multiple away from 0 (`Greater`), or exactly halfway
+            /// between (`Equal`)?
+            ///
+            /// This is exact: if it returns `Equal`, the value is _exactly_
+            /// halfway between two multiples of `factor`.
+            #[allow(clippy::arithmetic_side_effects)]
+            fn compare_to_half(self) -> Ordering {
+                // Doubling and halving are exact unless they overflow or
+                // underflow, respectively. If remainder > MAX / 2, then
+                // factor > MAX / 2, so factor / 2 can’t underflow.
+                if self.remainder > $ty::MAX / 2.0 {
+                    self.remainder.partial_cmp(&(self.factor / 2.0))
+                } else {
+                    (2.0 * self.remainder).partial_cmp(&self.factor)
+                }
+                .unwrap_or(Ordering::Equal)
+            }
+
+            /// Is the multiple of `factor` toward 0 an odd multiple?
+            #[allow(clippy::arithmetic_side_effects)]
+            fn is_base_odd(self) -> bool {
+                // `%` is exact, so modulo 2 * factor, magnitude is either
+                // remainder (base is even) or factor + remainder (base is odd).
+                // If 2 * factor overflows to infinity, then base is odd only if
+                // magnitude ≥ factor, which still works.
+                self.magnitude % (2.0 * self.factor) >= self.factor
+            }
+
+            /// Should rounding to the nearest multiple move away from zero?
+            fn nearest_is_away<S>(self, tie: S) -> bool
+            where
+                S: TieStrategy,
+            {
+                match self.compare_to_half() {
+                    Ordering::Less => false,
+                    Ordering::Greater => true,
+                    Ordering::Equal => tie
+                        .is_away_from_zero(self.negative, || {
+                            self.is_base_odd()
+                        }),
+                }
+            }
+
+            /// Should rounding to the nearest multiple move away from zero,
+            /// if these are the parts of the distance from an origin to the
+            /// value being rounded? `error` is the exact distance minus the
+            /// distance these parts were made from. Ties are resolved based on
+            /// the value, which is negative if `negative` is true.
+            #[allow(clippy::arithmetic_side_effects)]
+            fn nearest_is_away_from_origin<S>(
+                self,
+                error: $ty,
+                tie: S,
+                negative: bool,
+            ) -> bool
+            where
+                S: TieStrategy,
+            {
+                // The exact remainder is the sum of remainder and error. Since
+                // rounding is monotonic, rounding that sum only changes how it
+                // compares to factor / 2 if it rounds to exactly factor / 2,
+                // and then the rounding error decides.
+                let error = if self.negative { -error } else { error };
+                let (remainder, remainder_error) =
+                    Self::difference(self.remainder, -error);
+                let ordering = match (Self { remainder, ..self })
+                    .compare_to_half()
+                {
+                    Ordering::Equal => remainder_error
+                        .partial_cmp(&0.0)
+                        .unwrap_or(Ordering::Equal),
+                    ordering => ordering,
                 };
 
-                if self > 0.0 {
-                    if remainder - factor / 2.0 < -Self::EPSILON
-                        || ( abs(remainder - factor / 2.0) < Self::EPSILON
-                            && use_smaller() )
+                match ordering {
+                    Ordering::Less => false,
+                    Ordering::Greater => true,
+                    Ordering::Equal => {
+                        // If the value and the distance have different signs,
+                        // then zero is in the opposite direction for each.
+                        let flip = negative != self.negative;
+                        tie.is_away_from_zero(negative, || {
+                            self.is_base_odd() != flip
+                        }) != flip
+                    }
+                }
+            }
+
+            /// Should rounding `value` to the nearest multiple move away from
+            /// zero? Ties are resolved by `breaker`.
+            fn nearest_is_away_with_breaker<B>(
+                self,
+                value: $ty,
+                breaker: &mut B,
+            ) -> bool
+            where
+                B: TieBreaker<$ty> + ?Sized,
+            {
+                match self.compare_to_half() {
+                    Ordering::Less => false,
+                    Ordering::Greater => true,
+                    Ordering::Equal => {
+                        let toward = self.finish(false).ok();
+                        let away = self.finish(true).ok();
+                        let (lower, upper) = if self.negative {
+                            (away, toward)
+                        } else {
+                            (toward, away)
+                        };
+                        !breaker
+                            .break_tie(value, lower, upper, self.factor)
+                            .is_toward_zero(self.negative)
+                    }
+                }
+            }
+
+            /// Should rounding in `direction` move away from zero?
+            fn directed_is_away(self, direction: Direction) -> bool {
+                self.remainder != 0.0
+                    && !direction.is_toward_zero(self.negative)
+            }
+
+            /// Should rounding stochastically move away from zero? The
+            /// probability is `remainder / factor`.
+            #[allow(clippy::arithmetic_side_effects)]
+            fn stochastic_is_away<R>(self, random: &mut R) -> bool
+            where
+                R: RandomSource + ?Sized,
+            {
+                // f64 is more precise than f32, and remainder < factor.
+                let ratio = f64::from(self.remainder) / f64::from(self.factor);
+                is_away_ratio(ratio, random)
+            }
+
+            /// Get the rounded value.
+            ///
+            /// This is the float closest to k × `factor`, where k is the
+            /// integer number of factors in the rounded value. If `factor` is
+            /// the float closest to 1/n for some integer n, and k is less than
+            /// 2^(`MANTISSA_DIGITS` - 3), then this returns the float closest
+            /// to k/n instead, so that e.g. rounding to 0.1 returns 0.3 rather
+            /// than 0.30000000000000004.
+            #[allow(clippy::arithmetic_side_effects)]
+            fn finish(self, away: bool) -> Result<$ty, RoundError> {
+                // Rounding `x + LIMIT` rounds `x` to an integer, as long as
+                // 0 ≤ x < LIMIT. LIMIT is 2^(MANTISSA_DIGITS - 1).
+                const LIMIT: $ty = 1.0 / $ty::EPSILON;
+
+                /// Round a non-negative value less than `LIMIT` to an integer.
+                fn round(value: $ty) -> $ty {
+                    (value + LIMIT) - LIMIT
+                }
+
+                // The exact difference is k × factor, so this is the float
+                // closest to it.
+                let base = self.magnitude - self.remainder;
+
+                // This is within a few ULPs of k. If it’s less than LIMIT / 4,
+                // that error is less than 0.5, so rounding produces exactly k.
+                let multiple = base / self.factor;
+
+                let magnitude = if multiple < LIMIT / 4.0 {
+                    let multiple = if away {
+                        round(multiple) + 1.0
+                    } else {
+                        round(multiple)
+                    };
+                    let reciprocal = 1.0 / self.factor;
+
+                    #[allow(clippy::float_cmp)] // Exact comparison is intended.
+                    if multiple == 0.0 {
+                        // Avoid 0 × ∞ if factor is infinite.
+                        0.0
+                    } else if reciprocal < LIMIT
+                        && round(reciprocal) == reciprocal
+                        && 1.0 / reciprocal == self.factor
                     {
-                        Some(base)
+                        multiple / reciprocal
                     } else {
-                        Some(base + factor)
+                        multiple * self.factor
                     }
-                } else { // self <= 0.0
-                    if remainder - factor / 2.0 + factor < -Self::EPSILON
-                        || ( abs(remainder + factor / 2.0) < Self::EPSILON
-                            && use_smaller() )
+                } else if away {
+                    // The exact result is magnitude + (factor - remainder).
+                    // Adding `base + factor` would round twice, so add the
+                    // exact difference, which is `difference + error`.
+                    let (difference, error) =
+                        Self::difference(self.factor, self.remainder);
+                    let (sum, sum_error) =
+                        Self::difference(self.magnitude, -difference);
+
+                    // The factor is tiny relative to the magnitude, so `error`
+                    // can only change how the sum rounds if it was exactly
+                    // halfway between `sum` and its neighbor `sum + step`.
+                    let step = 2.0 * sum_error;
+                    #[allow(clippy::float_cmp)] // Exact comparison is intended.
+                    let halfway =
+                        sum_error != 0.0 && (sum + step) - sum == step;
+                    if halfway && error != 0.0 && (error > 0.0) == (step > 0.0)
                     {
-                        Some(base - factor)
+                        sum + step
                     } else {
-                        Some(base)
+                        sum
                     }
+                } else {
+                    base
+                };
+
+                finite!(if self.negative { -magnitude } else { magnitude })
+            }
+        }
+
+        impl Roundable for $ty {
+            fn checked_round_to<S>(
+                self,
+                factor: Self,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                check_float_arguments!(self, factor);
+
+                let parts = Parts::<$ty>::new(self, factor);
+                parts.finish(parts.nearest_is_away(tie))
+            }
+
+            fn checked_round_directed_to(
+                self,
+                factor: Self,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                check_float_arguments!(self, factor);
+
+                let parts = Parts::<$ty>::new(self, factor);
+                parts.finish(parts.directed_is_away(direction))
+            }
+        }
+
+        impl TieBreakerRoundable for $ty {
+            fn checked_round_to_with_breaker<B>(
+                self,
+                factor: Self,
+                breaker: &mut B,
+            ) -> Result<Self, RoundError>
+            where
+                B: TieBreaker<Self> + ?Sized,
+            {
+                check_float_arguments!(self, factor);
+
+                let parts = Parts::<$ty>::new(self, factor);
+                parts.finish(parts.nearest_is_away_with_breaker(self, breaker))
+            }
+        }
+
+        impl OriginRoundable for $ty {
+            #[allow(clippy::arithmetic_side_effects)]
+            fn checked_round_to_with_origin<S>(
+                self,
+                factor: Self,
+                origin: Self,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                // Like an invalid factor, an invalid origin is reported even
+                // if the value can’t be rounded either.
+                if !origin.is_finite() {
+                    return Err(RoundError::InvalidOrigin);
                 }
+                check_float_arguments!(self, factor);
+
+                // `%` is exact, and moving the origin by an even number of
+                // factors changes neither the grid nor which points are odd.
+                // If 2 × factor overflows to infinity, this does nothing.
+                let origin = origin % (2.0 * factor);
+
+                // If the distance from origin overflows, the value and origin
+                // are so large that halving everything is exact.
+                let scale = if (self - origin).is_finite() { 1.0 } else { 0.5 };
+                let origin = origin * scale;
+
+                // Subtracting can round, so keep the error to compare the
+                // exact distance to the multiples around it.
+                let (distance, error) =
+                    Parts::<$ty>::difference(self * scale, origin);
+                let parts = Parts::<$ty>::new(distance, factor * scale);
+                let away =
+                    parts.nearest_is_away_from_origin(error, tie, self < 0.0);
+                finite!((origin + parts.finish(away)?) / scale)
+            }
+        }
+
+        impl SetRoundable for $ty {
+            #[allow(clippy::arithmetic_side_effects)]
+            fn checked_round_to_set_index<S>(
+                self,
+                values: &[Self],
+                tie: S,
+            ) -> Result<usize, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if self.is_nan() {
+                    return Err(RoundError::NanInput);
+                } else if self.is_infinite() {
+                    return Err(RoundError::InfiniteInput);
+                }
+
+                let compare = |value: $ty, lower: $ty, upper: $ty| {
+                    // If a distance overflows, either a member is infinite,
+                    // or the values are so large that halving them is exact.
+                    let scale = if (value - lower).is_finite()
+                        && (upper - value).is_finite()
+                    {
+                        1.0
+                    } else {
+                        0.5
+                    };
+                    let value = value * scale;
+                    let (below, below_error) =
+                        Parts::<$ty>::difference(value, lower * scale);
+                    let (above, above_error) =
+                        Parts::<$ty>::difference(upper * scale, value);
+
+                    match below.partial_cmp(&above) {
+                        Some(Ordering::Equal) => {
+                            below_error.partial_cmp(&above_error)
+                        }
+                        ordering => ordering,
+                    }
+                    .unwrap_or(Ordering::Equal)
+                };
+                nearest_index(self, values, self < 0.0, tie, compare)
+            }
+        }
+
+        impl StochasticRoundable for $ty {
+            fn checked_round_stochastic_to<R>(
+                self,
+                factor: Self,
+                random: &mut R,
+            ) -> Result<Self, RoundError>
+            where
+                R: RandomSource + ?Sized,
+            {
+                check_float_arguments!(self, factor);
+
+                let parts = Parts::<$ty>::new(self, factor);
+                parts.finish(parts.stochastic_is_away(random))
             }
         }
     )+}
@@ -66,10 +442,146 @@ macro_rules! roundable_float {
 
 roundable_float!(f32 f64);
 
+/// Implement [`SliceRoundable`] for floating point types.
+///
+/// `$kernel` takes the factor and tie strategy, and returns a kernel for
+/// [`round_in_place()`] and [`round_into()`].
+macro_rules! slice_roundable_float {
+    ($($ty:ident $kernel:path;)+) => {$(
+        impl SliceRoundable for $ty {
+            fn try_round_slice_to<S>(
+                values: &mut [Self],
+                factor: Self,
+                tie: S,
+            ) -> Result<(), usize>
+            where
+                S: TieStrategy,
+            {
+                assert!(
+                    factor > 0.0,
+                    "try_round_slice_to() requires positive factor",
+                );
+                round_in_place(values, factor, tie, $kernel(factor, tie))
+            }
+
+            fn try_round_slice_into<S>(
+                input: &[Self],
+                output: &mut [Self],
+                factor: Self,
+                tie: S,
+            ) -> Result<(), usize>
+            where
+                S: TieStrategy,
+            {
+                assert!(
+                    factor > 0.0,
+                    "try_round_slice_into() requires positive factor",
+                );
+                round_into(input, output, factor, tie, $kernel(factor, tie))
+            }
+        }
+    )+}
+}
+
+slice_roundable_float! {
+    f32 f32_kernel;
+    f64 scalar_kernel;
+}
+
+/// Get a kernel that rounds `f32`s to `factor` without branches.
+///
+/// This does the work in `f64`, which has enough precision to find the exact
+/// remainder as long as the value is less than 2^24 × `factor`. Values that are
+/// much larger than `factor`, or that aren’t finite, are left for
+/// [`Roundable::checked_round_to()`].
+///
+/// The result is calculated the same way [`Parts::finish()`] does it, so it is
+/// exactly the same.
+#[allow(
+    clippy::arithmetic_side_effects,
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::float_cmp
+)]
+#[inline]
+fn f32_kernel<S>(factor: f32, tie: S) -> impl Fn(f32) -> (f32, bool)
+where
+    S: TieStrategy,
+{
+    /// Only values less than this many factors are rounded by the kernel. It
+    /// must be well under `LIMIT / 4.0` in [`Parts::finish()`].
+    const MAX_MULTIPLE: f64 = (1 << 20) as f64;
+
+    // See Parts::finish().
+    let limit = 1.0 / f32::EPSILON;
+    let reciprocal = 1.0 / factor;
+    let use_reciprocal = reciprocal < limit
+        && (reciprocal + limit) - limit == reciprocal
+        && 1.0 / reciprocal == factor;
+    let wide_factor = factor as f64;
+    let table = TieTable::new(tie);
+
+    // Parts::finish() avoids 0 × ∞, but with an infinite factor everything
+    // rounds to 0 or fails anyway, so leave it for checked_round_to().
+    let usable = factor.is_finite();
+
+    move |value| {
+        let negative = value < 0.0;
+        let magnitude = if negative { -value } else { value } as f64;
+
+        // This is exactly the integer part of magnitude / factor as long as it
+        // is less than 2^24 (see below), and false if value isn’t finite.
+        let quotient = magnitude / wide_factor;
+        let ok = usable && quotient < MAX_MULTIPLE;
+
+        // Both magnitude and factor have 24 significant bits, so the exact
+        // quotient is within 2^-24 of an integer only if it is an integer. The
+        // error in quotient is much smaller than that, so truncating it is
+        // exact. Then multiple × factor and the subtraction are also exact,
+        // since they need less than 53 bits.
+        let multiple = if ok { quotient as u32 } else { 0 };
+        let remainder = magnitude - multiple as f64 * wide_factor;
+
+        // Doubling is exact, too.
+        let twice_remainder = 2.0 * remainder;
+        let away = twice_remainder > wide_factor
+            || (twice_remainder == wide_factor
+                && table.is_away_from_zero(negative, || multiple & 1 != 0));
+
+        // Safe: multiple < 2^20.
+        let multiple = (multiple + away as u32) as f32;
+        let rounded = if use_reciprocal {
+            multiple / reciprocal
+        } else {
+            multiple * factor
+        };
+        let rounded = if negative { -rounded } else { rounded };
+
+        (rounded, ok && rounded.is_finite())
+    }
+}
+
+/// Get a kernel that just uses [`Roundable::checked_round_to()`].
+#[inline]
+fn scalar_kernel<T, S>(factor: T, tie: S) -> impl Fn(T) -> (T, bool)
+where
+    T: Roundable + Copy,
+    S: TieStrategy,
+{
+    move |value| match value.checked_round_to(factor, tie) {
+        Ok(rounded) => (rounded, true),
+        Err(_) => (value, false),
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::cognitive_complexity, clippy::float_cmp)]
 mod tests {
     use super::*;
+    use crate::test_util::DIRECTIONS;
+    use crate::Tie;
     use assert2::check;
 
     #[test]
@@ -259,14 +771,126 @@ mod tests {
 
     #[test]
     fn round_awkward_float_tie() {
-        check!(0.4 == 0.3.round_to(0.2, Tie::Up));
+        check!(0.5 == 0.375.round_to(0.25, Tie::Up));
+        check!(0.25 == 0.375.round_to(0.25, Tie::Down));
+        check!(0.25 == 0.375.round_to(0.25, Tie::TowardZero));
+        check!(0.5 == 0.375.round_to(0.25, Tie::AwayFromZero));
+        check!(0.5 == 0.375.round_to(0.25, Tie::TowardEven));
+        check!(0.25 == 0.375.round_to(0.25, Tie::TowardOdd));
+    }
+
+    #[test]
+    fn round_inexact_float_non_tie() {
+        // 0.3 and 0.2 can’t be represented exactly. In binary, 0.3 is slightly
+        // less than 1.5 * 0.2, so it’s not a tie.
+        check!(0.2 == 0.3.round_to(0.2, Tie::Up));
         check!(0.2 == 0.3.round_to(0.2, Tie::Down));
         check!(0.2 == 0.3.round_to(0.2, Tie::TowardZero));
-        check!(0.4 == 0.3.round_to(0.2, Tie::AwayFromZero));
-        check!(0.4 == 0.3.round_to(0.2, Tie::TowardEven));
+        check!(0.2 == 0.3.round_to(0.2, Tie::AwayFromZero));
+        check!(0.2 == 0.3.round_to(0.2, Tie::TowardEven));
         check!(0.2 == 0.3.round_to(0.2, Tie::TowardOdd));
     }
 
+    #[test]
+    fn round_large_float_tie() {
+        check!(1e12 == (1e12 + 0.5).round_to(1.0, Tie::TowardEven));
+        check!(1e12 + 2.0 == (1e12 + 1.5).round_to(1.0, Tie::TowardEven));
+        check!(1e12 + 1.0 == (1e12 + 0.5).round_to(1.0, Tie::TowardOdd));
+        check!(-1e12 == (-1e12 - 0.5).round_to(1.0, Tie::TowardEven));
+        check!(-1e12 == (-1e12 - 0.5).round_to(1.0, Tie::Up));
+        check!(-1e12 - 1.0 == (-1e12 - 0.5).round_to(1.0, Tie::Down));
+
+        // 2^51 + 0.5 is in the last range of values with fractional parts.
+        let big = 2_251_799_813_685_248.0_f64;
+        check!(big == (big + 0.5).round_to(1.0, Tie::TowardEven));
+        check!(big + 1.0 == (big + 0.5).round_to(1.0, Tie::TowardOdd));
+        check!(big + 2.0 == (big + 1.5).round_to(1.0, Tie::TowardEven));
+        check!(big + 1.0 == (big + 1.5).round_to(1.0, Tie::TowardOdd));
+
+        // Huge multiples of the factor.
+        let huge = f64::from_bits((1000 + 1023) << 52); // 2^1000
+        check!(huge == (1.5 * huge).round_to(huge, Tie::Down));
+        check!(2.0 * huge == (1.5 * huge).round_to(huge, Tie::Up));
+        check!(2.0 * huge == (1.5 * huge).round_to(huge, Tie::TowardEven));
+        check!(2.0 * huge == (2.5 * huge).round_to(huge, Tie::TowardEven));
+        check!(3.0 * huge == (2.5 * huge).round_to(huge, Tie::TowardOdd));
+        check!(f64::MAX == (f64::MAX / 2.0).round_to(f64::MAX, Tie::TowardOdd));
+        check!(0.0 == (f64::MAX / 2.0).round_to(f64::MAX, Tie::TowardEven));
+    }
+
+    #[test]
+    fn round_small_float_tie() {
+        let factor = 1.0 / 1_099_511_627_776.0; // 2^-40
+        check!(0.0 == (factor / 2.0).round_to(factor, Tie::Down));
+        check!(factor == (factor / 2.0).round_to(factor, Tie::Up));
+        check!(factor == (1.5 * factor).round_to(factor, Tie::Down));
+        check!(
+            2.0 * factor == (2.5 * factor).round_to(factor, Tie::TowardEven)
+        );
+
+        // Slightly more than half is not a tie, even though the difference
+        // is much smaller than EPSILON.
+        let value = factor / 2.0 + factor * factor;
+        check!(factor == value.round_to(factor, Tie::Down));
+        check!(factor == value.round_to(factor, Tie::TowardEven));
+        check!(-factor == (-value).round_to(factor, Tie::Up));
+
+        // Subnormal factors.
+        let tiny = f64::from_bits(2);
+        check!(tiny == f64::from_bits(3).round_to(tiny, Tie::Down));
+        check!(2.0 * tiny == f64::from_bits(3).round_to(tiny, Tie::Up));
+        check!(2.0 * tiny == f64::from_bits(3).round_to(tiny, Tie::TowardEven));
+        check!(0.0 == f64::from_bits(1).round_to(tiny, Tie::TowardEven));
+        check!(tiny == f64::from_bits(1).round_to(tiny, Tie::TowardOdd));
+    }
+
+    #[test]
+    fn round_float_to_decimal_factor() {
+        check!(0.3 == 0.28.round_to(0.1, Tie::Up));
+        check!(0.7 == 0.66.round_to(0.1, Tie::Up));
+        check!(-0.7 == (-0.66).round_to(0.1, Tie::Up));
+        check!(0.15 == 0.14.round_to(0.05, Tie::Up));
+        check!(0.35 == 0.36.round_to(0.05, Tie::Up));
+        check!(0.57 == 0.5678.round_to(0.01, Tie::Up));
+        check!(0.57 == 0.5612.round_directed_to(0.01, Direction::Up));
+        check!(1234.57 == 1234.5678.round_to(0.01, Tie::Up));
+        check!(0.3_f32 == 0.28_f32.round_to(0.1, Tie::Up));
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn round_float_to_decimal_factor_exhaustive() {
+        // Any value within 0.4 factors of k × factor should round to the
+        // float closest to k × factor. For factors like 0.01, the float closest
+        // to k × 0.01 is k / 100.0.
+        for (factor, n) in [(0.1, 10.0), (0.05, 20.0), (0.01, 100.0)] {
+            for k in -10_000_i32..=10_000 {
+                let expected = f64::from(k) / n;
+                for offset in [-0.4, -0.1, 0.0, 0.1, 0.4] {
+                    let value = (f64::from(k) + offset) / n;
+                    check!(expected == value.round_to(factor, Tie::Up));
+                    check!(expected == value.round_to(factor, Tie::TowardEven));
+                }
+            }
+        }
+
+        for (factor, n) in [(0.1_f32, 10.0_f32), (0.05, 20.0), (0.01, 100.0)] {
+            for k in -10_000_i16..=10_000 {
+                let expected = f32::from(k) / n;
+                let value = (f32::from(k) + 0.25) / n;
+                check!(expected == value.round_to(factor, Tie::Up));
+            }
+        }
+    }
+
+    #[test]
+    fn round_float_to_non_decimal_factor() {
+        check!(9.0 == 10.0.round_to(3.0, Tie::Up));
+        check!(12.0 == 10.5.round_to(3.0, Tie::Up));
+        check!(0.75 == 0.7.round_to(0.25, Tie::Up));
+        check!(3.0 * 0.3 == 1.0.round_to(0.3, Tie::Up));
+    }
+
     #[test]
     fn round_max_float() {
         check!(0.0 == 10.0.round_to(f32::MAX, Tie::Up));
@@ -283,6 +907,173 @@ mod tests {
         check!(-f32::MAX == (f32::MIN * 0.6).round_to(f32::MAX, Tie::Up));
     }
 
+    #[test]
+    fn round_float_directed() {
+        check!(10.0 == 0.1.round_directed_to(10.0, Direction::Up));
+        check!(0.0 == 0.1.round_directed_to(10.0, Direction::Down));
+        check!(0.0 == 0.1.round_directed_to(10.0, Direction::TowardZero));
+        check!(10.0 == 0.1.round_directed_to(10.0, Direction::AwayFromZero));
+
+        check!(10.0 == 9.9.round_directed_to(10.0, Direction::Up));
+        check!(0.0 == 9.9.round_directed_to(10.0, Direction::Down));
+        check!(0.0 == 9.9.round_directed_to(10.0, Direction::TowardZero));
+        check!(10.0 == 9.9.round_directed_to(10.0, Direction::AwayFromZero));
+
+        check!(0.0 == (-0.1).round_directed_to(10.0, Direction::Up));
+        check!(-10.0 == (-0.1).round_directed_to(10.0, Direction::Down));
+        check!(0.0 == (-0.1).round_directed_to(10.0, Direction::TowardZero));
+        check!(
+            -10.0 == (-0.1).round_directed_to(10.0, Direction::AwayFromZero)
+        );
+
+        check!(-20.0 == (-15.0).round_directed_to(10.0, Direction::Down));
+        check!(-10.0 == (-15.0).round_directed_to(10.0, Direction::Up));
+    }
+
+    #[test]
+    fn round_float_directed_multiple() {
+        // Direction should be irrelevant for values that are already round.
+        for direction in DIRECTIONS {
+            check!(0.0 == 0.0.round_directed_to(0.5, direction));
+            check!(1.5 == 1.5.round_directed_to(0.5, direction));
+            check!(-1.5 == (-1.5).round_directed_to(0.5, direction));
+        }
+    }
+
+    #[test]
+    fn checked_round_float() {
+        check!(Ok(310.0) == 314.0.checked_round_to(10.0, Tie::Up));
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 1.0.checked_round_to(0.0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 1.0.checked_round_to(-1.0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 1.0.checked_round_to(f64::NAN, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NanInput)
+                == f64::NAN.checked_round_to(1.0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::InfiniteInput)
+                == f64::INFINITY.checked_round_to(1.0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::InfiniteInput)
+                == f64::NEG_INFINITY
+                    .checked_round_directed_to(1.0, Direction::Up)
+        );
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f64::MAX.checked_round_to(f64::MAX / 3.0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f64::MIN
+                    .checked_round_directed_to(f64::MAX / 3.0, Direction::Down)
+        );
+    }
+
+    #[test]
+    fn try_round_float_non_finite() {
+        check!(None == f32::NAN.try_round_to(1.0, Tie::Up));
+        check!(None == f32::INFINITY.try_round_to(1.0, Tie::Up));
+        check!(None == f32::NEG_INFINITY.try_round_to(1.0, Tie::Up));
+        check!(None == f32::NAN.try_round_directed_to(1.0, Direction::Up));
+        check!(None == f64::MAX.try_round_to(f64::MAX / 3.0, Tie::Up));
+        check!(None == f64::MIN.try_round_to(f64::MAX / 3.0, Tie::Up));
+        check!(None == f64::MAX.try_round_directed_to(1e300, Direction::Up));
+        check!(
+            Some(f64::MAX)
+                == f64::MAX.try_round_directed_to(f64::MAX, Direction::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "try_round_to() requires positive factor")]
+    fn try_round_float_nan_factor() {
+        let _ = 1.0.try_round_to(f64::NAN, Tie::Up);
+    }
+
+    #[test]
+    fn round_float_infinite_factor() {
+        check!(0.0 == 1e300.round_to(f64::INFINITY, Tie::Up));
+        check!(0.0 == f64::MAX.round_to(f64::INFINITY, Tie::Up));
+        check!(0.0 == (-5.0).round_to(f64::INFINITY, Tie::Up));
+        check!(
+            0.0 == 5.0.round_directed_to(f64::INFINITY, Direction::TowardZero)
+        );
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == 5.0.checked_round_directed_to(f64::INFINITY, Direction::Up)
+        );
+    }
+
+    #[test]
+    fn round_subnormal_float() {
+        let tiny = f64::from_bits(1); // Smallest positive subnormal.
+        check!(0.0 == tiny.round_to(1.0, Tie::Up));
+        check!(1.0 == tiny.round_directed_to(1.0, Direction::Up));
+        check!(-1.0 == (-tiny).round_directed_to(1.0, Direction::Down));
+        check!(tiny == f64::from_bits(1).round_to(tiny, Tie::Up));
+        check!(
+            f64::from_bits(12)
+                == f64::from_bits(11).round_to(f64::from_bits(4), Tie::Up)
+        );
+        check!(
+            f64::MIN_POSITIVE
+                == (f64::MIN_POSITIVE * 0.75)
+                    .round_to(f64::MIN_POSITIVE / 2.0, Tie::Up)
+        );
+    }
+
+    #[test]
+    fn round_float_to_factor_smaller_than_ulp() {
+        // The spacing between floats around 1e20 is 16384, so any multiple of
+        // 3 nearby is rounded back to the original value.
+        for tie in [Tie::Up, Tie::Down, Tie::TowardEven, Tie::TowardOdd] {
+            check!(1e20 == 1e20.round_to(3.0, tie));
+            check!(-1e20 == (-1e20).round_to(3.0, tie));
+        }
+        check!(1e20 == 1e20.round_directed_to(3.0, Direction::Up));
+        check!(1e20 == 1e20.round_directed_to(0.1, Direction::Down));
+        check!(f64::MAX == f64::MAX.round_to(1.0, Tie::Up));
+        check!(f64::MAX == f64::MAX.round_to(0.1, Tie::Up));
+    }
+
+    #[test]
+    fn round_float_to_many_factors() {
+        // k × factor is only rounded once, even when k is at least 2^21 (f32)
+        // or 2^50 (f64).
+        check!(390_856.5 == 390_856.5f32.round_to(0.05, Tie::Up));
+        check!(787_091.0 == 787_091.0f32.round_to(0.1, Tie::Up));
+        check!(-787_091.0 == (-787_091.0f32).round_to(0.1, Tie::Up));
+        check!(787_091.0 == 787_091.0f32.round_directed_to(0.1, Direction::Up));
+        check!(
+            787_090.94 == 787_091.0f32.round_directed_to(0.1, Direction::Down)
+        );
+        check!(
+            554_185_084_658_126.3
+                == 554_185_084_658_126.3f64.round_to(0.1, Tie::Up)
+        );
+        check!(
+            554_185_084_658_126.25
+                == 554_185_084_658_126.3f64
+                    .round_directed_to(0.1, Direction::Down)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot round NaN")]
+    fn round_float_nan() {
+        let _ = f64::NAN.round_to(1.0, Tie::Up);
+    }
+
     #[test]
     #[should_panic(expected = "try_round_to() requires positive factor")]
     fn round_float_zero_factor() {
@@ -294,4 +1085,12 @@ mod tests {
     fn round_float_negative_factor() {
         let _ = 0.0.round_to(-1.0, Tie::Up);
     }
+
+    #[test]
+    #[should_panic(
+        expected = "try_round_directed_to() requires positive factor"
+    )]
+    fn round_float_directed_negative_factor() {
+        let _ = 0.0.round_directed_to(-1.0, Direction::Down);
+    }
 }
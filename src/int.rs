@@ -1,60 +1,831 @@
 //! Implement `Roundable` for integers.
 
-use crate::{Roundable, Tie};
+use crate::divisor::{Divisor, RoundWithDivisor};
+use crate::origin::round_u128_with_origin;
+use crate::set::nearest_index;
+use crate::slice::{round_in_place, round_into, TieTable};
+use crate::stochastic::is_away;
+use crate::{
+    unwrap_rounded, ConstRoundable, Direction, OriginRoundable, Precomputable,
+    RandomSource, RoundError, Roundable, SaturatingRoundable, SetRoundable,
+    SliceRoundable, StochasticRoundable, Tie, TieBreaker, TieBreakerRoundable,
+    TieStrategy, WrappingRoundable,
+};
+#[cfg(feature = "saturating")]
+use core::num::Saturating;
+use core::num::Wrapping;
+
+/// An integer that has been rounded, but not yet checked for overflow.
+///
+/// This is `pub` so that [`crate::const_fn`] can use it, but it isn’t exported.
+#[derive(Clone, Copy, Debug)]
+pub struct Rounding<T> {
+    /// The multiple of `factor` between the original value and 0 (inclusive).
+    base: T,
+
+    /// The factor the value is being rounded to. Always positive.
+    factor: T,
+
+    /// Whether the result is the next multiple of `factor` away from 0, rather
+    /// than `base`.
+    away: bool,
+
+    /// Whether the original value was negative.
+    negative: bool,
+}
+
+/// A factor passed as a const generic parameter.
+///
+/// See [`ConstFactor`].
+#[derive(Clone, Copy, Debug)]
+struct Factor<const FACTOR: u128>;
+
+/// Convert a [`Factor`] to a specific type at compile time.
+trait ConstFactor<T> {
+    /// `FACTOR` as `T`. Using this fails to compile if `FACTOR` is 0 or does
+    /// not fit in `T`.
+    const VALUE: T;
+}
 
 /// Implement rounding for integer types.
 macro_rules! roundable_integer {
     ($($ty:ident)+) => {$(
-        impl Roundable for $ty {
+        impl Rounding<$ty> {
+            /// Round `value` to the nearest `factor`, which must be positive.
+            #[allow(clippy::integer_division, clippy::arithmetic_side_effects)]
+            #[inline]
+            fn nearest<S>(value: $ty, factor: $ty, tie: S) -> Self
+            where
+                S: TieStrategy,
+            {
+                let power_of_two = Self::is_power_of_two(factor);
+                let remainder = Self::remainder(value, factor, power_of_two);
+                Self::nearest_from_remainder(
+                    value,
+                    factor,
+                    remainder,
+                    tie,
+                    |base| Self::is_multiple_odd(base, factor, power_of_two),
+                )
+            }
+
+            /// Round `value` to the nearest `factor`, which must be positive.
+            /// Returns `None` if the result overflows.
+            ///
+            /// This is the same as `nearest()`, but it can be used in a
+            /// `const fn` since it doesn’t call trait methods or closures.
+            #[allow(clippy::arithmetic_side_effects, unused_comparisons)]
+            pub const fn try_nearest_const(
+                value: $ty,
+                factor: $ty,
+                tie: Tie,
+            ) -> Option<$ty> {
+                let power_of_two = Self::is_power_of_two(factor);
+                let remainder = Self::remainder(value, factor, power_of_two);
+
+                // Safe: see nearest_from_remainder() below.
+                let base = value - remainder;
+                let negative = value < 0;
+                let half = factor >> 1;
+                let (past_half, at_half) = if value > 0 {
+                    (remainder > half, remainder == half)
+                } else {
+                    (remainder + half < 0, remainder + half == 0)
+                };
+
+                let away = past_half
+                    || (factor & 1 == 0
+                        && at_half
+                        && tie.is_away_from_zero_const(
+                            negative,
+                            Self::is_multiple_odd(base, factor, power_of_two),
+                        ));
+
+                Self { base, factor, away, negative }.result()
+            }
+
+            /// Round `value` to the nearest `factor`, which must be positive,
+            /// using `divisor` precomputed for `factor`.
+            #[inline]
+            fn nearest_with<S>(
+                value: $ty,
+                factor: $ty,
+                divisor: &Divisor,
+                tie: S,
+            ) -> Self
+            where
+                S: TieStrategy,
+            {
+                let (remainder, is_base_odd) = Self::divide(value, divisor);
+                Self::nearest_from_remainder(
+                    value,
+                    factor,
+                    remainder,
+                    tie,
+                    |_| is_base_odd,
+                )
+            }
+
+            /// Round `value` to the nearest `factor`, which must be positive,
+            /// using `divisor` precomputed for `factor`. Returns the rounded
+            /// value and whether it didn’t overflow.
+            ///
+            /// This is the same as `nearest_with()`, but it avoids branches so
+            /// that it can be vectorized.
+            #[allow(clippy::arithmetic_side_effects, unused_comparisons)]
+            #[inline]
+            fn nearest_branchless<S>(
+                value: $ty,
+                factor: $ty,
+                divisor: &Divisor,
+                tie: S,
+            ) -> ($ty, bool)
+            where
+                S: TieStrategy,
+            {
+                let (remainder, is_base_odd) = Self::divide(value, divisor);
+                let negative = value < 0;
+
+                // Safe: see nearest_from_remainder() below.
+                let base = value - remainder;
+
+                // remainder has the same sign as value and is closer to 0
+                // than factor, so it can always be negated.
+                let distance = if negative {
+                    remainder.wrapping_neg()
+                } else {
+                    remainder
+                };
+
+                // Use & and | rather than && and || to avoid branches.
+                let half = factor >> 1;
+                let is_tie = (factor & 1 == 0) & (distance == half);
+                let away = (distance > half)
+                    | (is_tie
+                        & tie.is_away_from_zero(negative, || is_base_odd));
+
+                Self { base, factor, away, negative }.overflowing()
+            }
+
+            /// Round `value` to the nearest `factor`, which must be positive,
+            /// given `remainder`, which must be `value % factor`.
+            ///
+            /// `is_base_odd` takes the multiple of `factor` between `value`
+            /// and 0, and returns whether it divided by `factor` is odd. It is
+            /// only called to break ties.
+            #[allow(clippy::arithmetic_side_effects, unused_comparisons)]
+            #[inline]
+            fn nearest_from_remainder<F, S>(
+                value: $ty,
+                factor: $ty,
+                remainder: $ty,
+                tie: S,
+                is_base_odd: F,
+            ) -> Self
+            where
+                F: FnOnce($ty) -> bool,
+                S: TieStrategy,
+            {
+                // Safe: remainder has the same sign as value, so subtracting
+                // remainder will always be closer to 0. Also, remainder is
+                // always between 0 and value, so it base can never switch
+                // signs.
+                let base = value - remainder;
+
+                let negative = value < 0;
+
+                // factor is positive, so this is factor / 2. A tie is only
+                // possible when factor is even.
+                let half = factor >> 1;
+                let even = factor & 1 == 0;
+
+                let is_tie_away = || {
+                    tie.is_away_from_zero(negative, || is_base_odd(base))
+                };
+
+                let away = if value > 0 {
+                    remainder > half
+                        || ( even && remainder == half && is_tie_away() )
+                } else { // value <= 0
+                    // Safe: -factor < remainder ≤ 0 and 0 ≤ half < factor.
+                    remainder + half < 0
+                        || ( even && remainder + half == 0 && is_tie_away() )
+                };
+
+                Self { base, factor, away, negative }
+            }
+
+            /// Round `value` to the next `factor` in `direction`. `factor` must
+            /// be positive.
+            const fn directed(
+                value: $ty,
+                factor: $ty,
+                direction: Direction,
+            ) -> Self {
+                Self::directed_from_remainder(
+                    value,
+                    factor,
+                    Self::remainder(
+                        value,
+                        factor,
+                        Self::is_power_of_two(factor),
+                    ),
+                    direction,
+                )
+            }
+
+            /// Round `value` to the next `factor` in `direction` given
+            /// `remainder`, which must be `value % factor`. `factor` must be
+            /// positive.
+            #[allow(clippy::arithmetic_side_effects, unused_comparisons)]
+            const fn directed_from_remainder(
+                value: $ty,
+                factor: $ty,
+                remainder: $ty,
+                direction: Direction,
+            ) -> Self {
+                Self {
+                    // Safe: see nearest_from_remainder() above.
+                    base: value - remainder,
+                    factor,
+                    away: remainder != 0
+                        && !direction.is_toward_zero(value < 0),
+                    negative: value < 0,
+                }
+            }
+
+            /// Get `value % factor` and whether `value / factor` is odd, using
+            /// `divisor` precomputed for `factor`.
             #[allow(
-                clippy::integer_division,
-                clippy::arithmetic_side_effects,
+                clippy::cast_possible_truncation,
+                clippy::cast_possible_wrap,
+                clippy::cast_sign_loss,
+                clippy::cast_lossless,
                 unused_comparisons,
             )]
-            fn try_round_to(self, factor: Self, tie: Tie) -> Option<Self> {
-                assert!(factor > 0, "try_round_to() requires positive factor");
+            #[inline]
+            const fn divide(value: $ty, divisor: &Divisor) -> ($ty, bool) {
+                let negative = value < 0;
 
-                let remainder = self % factor;
+                // Casting a negative value to u128 sign extends it, so
+                // negating it and masking off the extra bits leaves its
+                // magnitude.
+                let mask = u128::MAX >> (128 - $ty::BITS);
+                let magnitude = if negative {
+                    (value as u128).wrapping_neg() & mask
+                } else {
+                    value as u128
+                };
 
-                // Safe: remainder has the same sign as self, so subtracting
-                // remainder will always be closer to 0. Also, remainder is
-                // always between 0 and self, so it base can never switch signs.
-                let base = self - remainder;
+                let (quotient, remainder) =
+                    divisor.divide(magnitude, $ty::BITS);
 
-                let use_smaller = || {
-                    match tie {
-                        Tie::Up => false,
-                        Tie::Down => true,
-                        Tie::TowardZero => self > 0,
-                        Tie::AwayFromZero => self < 0,
-                        Tie::TowardEven =>
-                            ((base / factor) % 2 == 0) ^ (self < 0),
-                        Tie::TowardOdd =>
-                            ((base / factor) % 2 != 0) ^ (self < 0),
-                    }
-                };
+                // The remainder is less than factor, so it fits.
+                let remainder = remainder as $ty;
+                if negative {
+                    (remainder.wrapping_neg(), quotient & 1 != 0)
+                } else {
+                    (remainder, quotient & 1 != 0)
+                }
+            }
+
+            /// Is `base / factor` odd? `base` must be a multiple of `factor`,
+            /// which must be positive.
+            #[allow(clippy::integer_division, clippy::arithmetic_side_effects)]
+            #[inline]
+            const fn is_multiple_odd(
+                base: $ty,
+                factor: $ty,
+                power_of_two: bool,
+            ) -> bool {
+                if power_of_two {
+                    // base is a multiple of factor, so the bit for factor is
+                    // the lowest bit of base / factor (in two’s complement).
+                    base & factor != 0
+                } else {
+                    (base / factor) % 2 != 0
+                }
+            }
 
-                if self > 0 {
-                    // Add factor % 2 to make things work when factor is odd.
-                    if remainder < factor / 2 + factor % 2
-                        || ( remainder == factor / 2 && use_smaller() )
-                    {
-                        Some(base)
+            /// Is `factor`, which must be positive, a power of two?
+            #[allow(clippy::arithmetic_side_effects)]
+            const fn is_power_of_two(factor: $ty) -> bool {
+                // Safe: factor is positive.
+                factor & (factor - 1) == 0
+            }
+
+            /// Get `value % factor`. `factor` must be positive.
+            ///
+            /// If `power_of_two` is set, this uses a bit mask instead of
+            /// division.
+            #[allow(clippy::arithmetic_side_effects, unused_comparisons)]
+            #[inline]
+            const fn remainder(
+                value: $ty,
+                factor: $ty,
+                power_of_two: bool,
+            ) -> $ty {
+                if power_of_two {
+                    // Masking rounds toward negative infinity, but `%`
+                    // truncates toward zero, so negative remainders are off
+                    // by factor. Safe: 0 < mask < factor.
+                    let mask = value & (factor - 1);
+                    if value < 0 && mask != 0 {
+                        mask - factor
                     } else {
-                        base.checked_add(factor)
+                        mask
                     }
-                } else { // self <= 0
-                    // Add factor % 2 to make things work when factor is odd.
-                    // Safe: 0 ≤ -remainder ≤ factor
-                    if remainder + factor < factor / 2 + factor % 2
-                        || ( remainder + factor / 2 + factor % 2 == 0
-                            && use_smaller() )
-                    {
-                        base.checked_sub(factor)
+                } else {
+                    value % factor
+                }
+            }
+
+            /// Get the rounded value, or an error if it overflowed.
+            fn checked(self) -> Result<$ty, RoundError> {
+                self.result().ok_or(RoundError::Overflow)
+            }
+
+            /// Get the rounded value, or `None` if it overflowed.
+            const fn result(self) -> Option<$ty> {
+                if !self.away {
+                    Some(self.base)
+                } else if self.negative {
+                    self.base.checked_sub(self.factor)
+                } else {
+                    self.base.checked_add(self.factor)
+                }
+            }
+
+            /// Get the rounded value, and whether it didn’t overflow.
+            ///
+            /// This avoids branches so that it can be vectorized. The rounded
+            /// value is wrapped around on overflow.
+            #[inline]
+            const fn overflowing(self) -> ($ty, bool) {
+                let (up, up_overflow) = self.base.overflowing_add(self.factor);
+                let (down, down_overflow) =
+                    self.base.overflowing_sub(self.factor);
+                let (away, overflow) = if self.negative {
+                    (down, down_overflow)
+                } else {
+                    (up, up_overflow)
+                };
+
+                if self.away {
+                    (away, !overflow)
+                } else {
+                    (self.base, true)
+                }
+            }
+
+            /// Get the rounded value, wrapping around on overflow.
+            const fn wrapping(self) -> $ty {
+                if !self.away {
+                    self.base
+                } else if self.negative {
+                    self.base.wrapping_sub(self.factor)
+                } else {
+                    self.base.wrapping_add(self.factor)
+                }
+            }
+
+            /// Get the rounded value, or `MIN` or `MAX` if it overflowed.
+            fn saturating(self) -> $ty {
+                if self.negative {
+                    self.checked().unwrap_or($ty::MIN)
+                } else {
+                    self.checked().unwrap_or($ty::MAX)
+                }
+            }
+
+            /// Map `value` to a `u128` by subtracting `MIN`. This preserves
+            /// order and differences, so `to_u128(MIN)` is 0.
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_wrap,
+                clippy::cast_sign_loss
+            )]
+            pub const fn to_u128(value: $ty) -> u128 {
+                (value as i128).wrapping_sub($ty::MIN as i128) as u128
+            }
+
+            /// Map a `u128` from [`Self::to_u128()`] back to a value. It must
+            /// be at most `to_u128(MAX)`.
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_possible_wrap,
+                clippy::cast_sign_loss
+            )]
+            pub const fn from_u128(value: u128) -> $ty {
+                (value as i128).wrapping_add($ty::MIN as i128) as $ty
+            }
+        }
+
+        impl Roundable for $ty {
+            #[allow(unused_comparisons)]
+            fn checked_round_to<S>(
+                self,
+                factor: Self,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if factor <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                Rounding::<$ty>::nearest(self, factor, tie).checked()
+            }
+
+            #[allow(unused_comparisons)]
+            fn checked_round_directed_to(
+                self,
+                factor: Self,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                if factor <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                Rounding::<$ty>::directed(self, factor, direction).checked()
+            }
+        }
+
+        impl SaturatingRoundable for $ty {
+            #[allow(unused_comparisons)]
+            fn saturating_round_to<S>(self, factor: Self, tie: S) -> Self
+            where
+                S: TieStrategy,
+            {
+                assert!(
+                    factor > 0,
+                    "saturating_round_to() requires positive factor",
+                );
+                Rounding::<$ty>::nearest(self, factor, tie).saturating()
+            }
+        }
+
+        impl WrappingRoundable for $ty {
+            #[allow(unused_comparisons)]
+            fn wrapping_round_to<S>(self, factor: Self, tie: S) -> Self
+            where
+                S: TieStrategy,
+            {
+                assert!(
+                    factor > 0,
+                    "wrapping_round_to() requires positive factor",
+                );
+                Rounding::<$ty>::nearest(self, factor, tie).wrapping()
+            }
+        }
+
+        impl TieBreakerRoundable for $ty {
+            #[allow(clippy::arithmetic_side_effects, unused_comparisons)]
+            fn checked_round_to_with_breaker<B>(
+                self,
+                factor: Self,
+                breaker: &mut B,
+            ) -> Result<Self, RoundError>
+            where
+                B: TieBreaker<Self> + ?Sized,
+            {
+                if factor <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                let power_of_two = Rounding::<$ty>::is_power_of_two(factor);
+                let remainder =
+                    Rounding::<$ty>::remainder(self, factor, power_of_two);
+                let negative = self < 0;
+
+                // Safe: see nearest_from_remainder().
+                let base = self - remainder;
+
+                // remainder has the same sign as self and is closer to 0 than
+                // factor, so it can always be negated.
+                let distance = if negative {
+                    remainder.wrapping_neg()
+                } else {
+                    remainder
+                };
+
+                let half = factor >> 1;
+                let away = if factor & 1 == 0 && distance == half {
+                    let other =
+                        Rounding::<$ty> { base, factor, away: true, negative }
+                            .checked()
+                            .ok();
+                    let (lower, upper) = if negative {
+                        (other, Some(base))
                     } else {
-                        Some(base)
-                    }
+                        (Some(base), other)
+                    };
+                    !breaker
+                        .break_tie(self, lower, upper, factor)
+                        .is_toward_zero(negative)
+                } else {
+                    distance > half
+                };
+
+                Rounding::<$ty> { base, factor, away, negative }.checked()
+            }
+        }
+
+        impl OriginRoundable for $ty {
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_sign_loss,
+                unused_comparisons
+            )]
+            fn checked_round_to_with_origin<S>(
+                self,
+                factor: Self,
+                origin: Self,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if factor <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                // Safe: factor is positive.
+                round_u128_with_origin(
+                    Rounding::<$ty>::to_u128(self),
+                    factor as u128,
+                    Rounding::<$ty>::to_u128(origin),
+                    Rounding::<$ty>::to_u128($ty::MAX),
+                    self < 0,
+                    tie,
+                )
+                .map(Rounding::<$ty>::from_u128)
+            }
+        }
+
+        impl SetRoundable for $ty {
+            #[allow(clippy::arithmetic_side_effects, unused_comparisons)]
+            fn checked_round_to_set_index<S>(
+                self,
+                values: &[Self],
+                tie: S,
+            ) -> Result<usize, RoundError>
+            where
+                S: TieStrategy,
+            {
+                let compare = |value: $ty, lower: $ty, upper: $ty| {
+                    let value = Rounding::<$ty>::to_u128(value);
+
+                    // Safe: lower < value < upper.
+                    (value - Rounding::<$ty>::to_u128(lower))
+                        .cmp(&(Rounding::<$ty>::to_u128(upper) - value))
+                };
+                nearest_index(self, values, self < 0, tie, compare)
+            }
+        }
+
+        impl StochasticRoundable for $ty {
+            #[allow(
+                clippy::arithmetic_side_effects,
+                clippy::cast_lossless,
+                clippy::cast_sign_loss,
+                unused_comparisons
+            )]
+            fn checked_round_stochastic_to<R>(
+                self,
+                factor: Self,
+                random: &mut R,
+            ) -> Result<Self, RoundError>
+            where
+                R: RandomSource + ?Sized,
+            {
+                if factor <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
                 }
+
+                // Safe: factor is positive.
+                let remainder = self % factor;
+                let negative = self < 0;
+
+                // remainder has the same sign as self and is closer to 0 than
+                // factor, so it can always be negated.
+                let distance = if negative {
+                    remainder.wrapping_neg()
+                } else {
+                    remainder
+                };
+                let away = is_away(distance as u128, factor as u128, random);
+
+                // Safe: see nearest_from_remainder().
+                let base = self - remainder;
+                Rounding::<$ty> { base, factor, away, negative }.checked()
+            }
+        }
+
+        impl<const FACTOR: u128> ConstFactor<$ty> for Factor<FACTOR> {
+            // Panicking in a constant requires Rust 1.57, so instead this
+            // indexes past the end of an array if FACTOR is invalid, which
+            // also fails to compile.
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_possible_wrap,
+                clippy::cast_sign_loss,
+                clippy::cast_lossless,
+            )]
+            const VALUE: $ty = [FACTOR as $ty][
+                (FACTOR == 0 || FACTOR > $ty::MAX as u128) as usize
+            ];
+        }
+
+        impl ConstRoundable for $ty {
+            fn checked_round_to_const<S, const FACTOR: u128>(
+                self,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                let factor = <Factor<FACTOR> as ConstFactor<$ty>>::VALUE;
+                Rounding::<$ty>::nearest(self, factor, tie).checked()
+            }
+
+            fn checked_round_directed_to_const<const FACTOR: u128>(
+                self,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                let factor = <Factor<FACTOR> as ConstFactor<$ty>>::VALUE;
+                Rounding::<$ty>::directed(self, factor, direction).checked()
+            }
+        }
+
+        impl Precomputable for $ty {}
+
+        impl RoundWithDivisor for $ty {
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_sign_loss,
+                unused_comparisons,
+            )]
+            fn divisor(factor: Self) -> Result<Divisor, RoundError> {
+                if factor <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                // Safe: factor is positive.
+                Ok(Divisor::new(factor as u128, $ty::BITS))
+            }
+
+            #[inline]
+            fn checked_round_with<S>(
+                self,
+                factor: Self,
+                divisor: &Divisor,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                Rounding::<$ty>::nearest_with(self, factor, divisor, tie)
+                    .checked()
+            }
+
+            #[inline]
+            fn checked_round_directed_with(
+                self,
+                factor: Self,
+                divisor: &Divisor,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                let (remainder, _) = Rounding::<$ty>::divide(self, divisor);
+                Rounding::<$ty>::directed_from_remainder(
+                    self,
+                    factor,
+                    remainder,
+                    direction,
+                )
+                .checked()
+            }
+        }
+
+        impl SliceRoundable for $ty {
+            fn try_round_slice_to<S>(
+                values: &mut [Self],
+                factor: Self,
+                tie: S,
+            ) -> Result<(), usize>
+            where
+                S: TieStrategy,
+            {
+                let divisor = unwrap_rounded(
+                    Self::divisor(factor),
+                    "try_round_slice_to",
+                );
+                let table = TieTable::new(tie);
+                round_in_place(values, factor, tie, |value| {
+                    Rounding::<$ty>::nearest_branchless(
+                        value, factor, &divisor, table,
+                    )
+                })
+            }
+
+            fn try_round_slice_into<S>(
+                input: &[Self],
+                output: &mut [Self],
+                factor: Self,
+                tie: S,
+            ) -> Result<(), usize>
+            where
+                S: TieStrategy,
+            {
+                let divisor = unwrap_rounded(
+                    Self::divisor(factor),
+                    "try_round_slice_into",
+                );
+                let table = TieTable::new(tie);
+                round_into(input, output, factor, tie, |value| {
+                    Rounding::<$ty>::nearest_branchless(
+                        value, factor, &divisor, table,
+                    )
+                })
+            }
+        }
+
+        impl Roundable for Wrapping<$ty> {
+            #[allow(unused_comparisons)]
+            fn checked_round_to<S>(
+                self,
+                factor: Self,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if factor.0 <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                Ok(Wrapping(
+                    Rounding::<$ty>::nearest(self.0, factor.0, tie).wrapping()
+                ))
+            }
+
+            #[allow(unused_comparisons)]
+            fn checked_round_directed_to(
+                self,
+                factor: Self,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                if factor.0 <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                Ok(Wrapping(
+                    Rounding::<$ty>::directed(self.0, factor.0, direction)
+                        .wrapping()
+                ))
+            }
+        }
+
+        // `Saturating` requires Rust 1.74, so it is only used with the
+        // `saturating` feature.
+        #[cfg(feature = "saturating")]
+        #[allow(clippy::incompatible_msrv)]
+        impl Roundable for Saturating<$ty> {
+            #[allow(unused_comparisons)]
+            fn checked_round_to<S>(
+                self,
+                factor: Self,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if factor.0 <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                Ok(Saturating(
+                    Rounding::<$ty>::nearest(self.0, factor.0, tie)
+                        .saturating()
+                ))
+            }
+
+            #[allow(unused_comparisons)]
+            fn checked_round_directed_to(
+                self,
+                factor: Self,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                if factor.0 <= 0 {
+                    return Err(RoundError::NonPositiveFactor);
+                }
+
+                Ok(Saturating(
+                    Rounding::<$ty>::directed(self.0, factor.0, direction)
+                        .saturating()
+                ))
             }
         }
     )+}
@@ -67,6 +838,7 @@ roundable_integer!(i8 i16 i32 i64 i128 isize);
 #[allow(clippy::cognitive_complexity, clippy::integer_division)]
 mod tests {
     use super::*;
+    use crate::test_util::{DIRECTIONS, TIES};
     use assert2::check;
 
     #[test]
@@ -274,20 +1046,10 @@ mod tests {
         check!(-3 == (-3).round_to(3, Tie::TowardOdd));
     }
 
-    /// All tie behaviors.
-    const TIE_BEHAVIORS: [Tie; 6] = [
-        Tie::Up,
-        Tie::Down,
-        Tie::TowardZero,
-        Tie::AwayFromZero,
-        Tie::TowardEven,
-        Tie::TowardOdd,
-    ];
-
     #[test]
     fn round_max_integer() {
         // Tie behaviors should be irrelevant in all these cases.
-        for behavior in TIE_BEHAVIORS {
+        for behavior in TIES {
             check!(0 == 10.round_to(u32::MAX, behavior));
             check!(0 == (u32::MAX / 2).round_to(u32::MAX, behavior));
             check!(u32::MAX == (u32::MAX / 2 + 1).round_to(u32::MAX, behavior));
@@ -303,7 +1065,7 @@ mod tests {
     #[test]
     fn round_min_integer() {
         // Tie behaviors should be irrelevant in all these cases.
-        for behavior in TIE_BEHAVIORS {
+        for behavior in TIES {
             check!(-i32::MAX == i32::MIN.round_to(i32::MAX, behavior));
             check!(-i32::MAX == (i32::MIN / 2).round_to(i32::MAX, behavior));
             check!(0 == (i32::MIN / 2 + 1).round_to(i32::MAX, behavior));
@@ -323,7 +1085,7 @@ mod tests {
     #[test]
     fn round_all_u8s() {
         // Just make sure they don’t panic.
-        for behavior in TIE_BEHAVIORS {
+        for behavior in TIES {
             for value in u8::MIN..=u8::MAX {
                 for factor in 1..=u8::MAX {
                     let _ = value.try_round_to(factor, behavior);
@@ -335,7 +1097,7 @@ mod tests {
     #[test]
     fn round_all_i8s() {
         // Just make sure they don’t panic.
-        for behavior in TIE_BEHAVIORS {
+        for behavior in TIES {
             for value in i8::MIN..=i8::MAX {
                 for factor in 1..=i8::MAX {
                     let _ = value.try_round_to(factor, behavior);
@@ -344,6 +1106,338 @@ mod tests {
         }
     }
 
+    /// Check that rounding `value` to power of two `factor` matches rounding
+    /// `value * 3` to `factor * 3`, which doesn’t use bit masks.
+    #[allow(clippy::arithmetic_side_effects, clippy::integer_division)]
+    fn check_power_of_two_factor<T>(value: T, factor: T)
+    where
+        T: Roundable + Copy + core::fmt::Debug + TryFrom<i32>,
+        i32: From<T>,
+    {
+        let scaled = i32::from(value) * 3;
+        let reference = i32::from(factor) * 3;
+        let fits = |rounded: &i32| T::try_from(rounded / 3).is_ok();
+        for behavior in TIES {
+            check!(
+                value
+                    .try_round_to(factor, behavior)
+                    .map(|rounded| i32::from(rounded) * 3)
+                    == Some(scaled.round_to(reference, behavior)).filter(fits),
+                "{:?} {:?} {:?}",
+                value,
+                factor,
+                behavior
+            );
+        }
+        for direction in DIRECTIONS {
+            check!(
+                value
+                    .try_round_directed_to(factor, direction)
+                    .map(|rounded| i32::from(rounded) * 3)
+                    == Some(scaled.round_directed_to(reference, direction))
+                        .filter(fits),
+                "{:?} {:?} {:?}",
+                value,
+                factor,
+                direction
+            );
+        }
+    }
+
+    #[test]
+    fn power_of_two_factors_match_division() {
+        for shift in 0..8 {
+            for value in u8::MIN..=u8::MAX {
+                check_power_of_two_factor(value, 1u8 << shift);
+            }
+        }
+        for shift in 0..7 {
+            for value in i8::MIN..=i8::MAX {
+                check_power_of_two_factor(value, 1i8 << shift);
+            }
+        }
+    }
+
+    #[test]
+    fn round_integer_directed() {
+        check!(10 == 1.round_directed_to(10, Direction::Up));
+        check!(0 == 1.round_directed_to(10, Direction::Down));
+        check!(0 == 1.round_directed_to(10, Direction::TowardZero));
+        check!(10 == 1.round_directed_to(10, Direction::AwayFromZero));
+
+        check!(10 == 9.round_directed_to(10, Direction::Up));
+        check!(0 == 9.round_directed_to(10, Direction::Down));
+        check!(0 == 9.round_directed_to(10, Direction::TowardZero));
+        check!(10 == 9.round_directed_to(10, Direction::AwayFromZero));
+
+        check!(0 == (-1).round_directed_to(10, Direction::Up));
+        check!(-10 == (-1).round_directed_to(10, Direction::Down));
+        check!(0 == (-1).round_directed_to(10, Direction::TowardZero));
+        check!(-10 == (-1).round_directed_to(10, Direction::AwayFromZero));
+
+        check!(0 == (-9).round_directed_to(10, Direction::Up));
+        check!(-10 == (-9).round_directed_to(10, Direction::Down));
+        check!(0 == (-9).round_directed_to(10, Direction::TowardZero));
+        check!(-10 == (-9).round_directed_to(10, Direction::AwayFromZero));
+    }
+
+    #[test]
+    fn round_integer_directed_multiple() {
+        // Direction should be irrelevant for values that are already round.
+        for direction in DIRECTIONS {
+            check!(0 == 0.round_directed_to(10, direction));
+            check!(20 == 20.round_directed_to(10, direction));
+            check!(-20 == (-20).round_directed_to(10, direction));
+            check!(i8::MIN == i8::MIN.round_directed_to(2, direction));
+            check!(u8::MAX == u8::MAX.round_directed_to(u8::MAX, direction));
+        }
+    }
+
+    #[test]
+    fn round_integer_directed_overflow() {
+        check!(None == 251u8.try_round_directed_to(10, Direction::Up));
+        check!(Some(250) == 251u8.try_round_directed_to(10, Direction::Down));
+        check!(
+            Some(250) == 251u8.try_round_directed_to(10, Direction::TowardZero)
+        );
+        check!(
+            None == 251u8.try_round_directed_to(10, Direction::AwayFromZero)
+        );
+
+        check!(Some(-120) == i8::MIN.try_round_directed_to(10, Direction::Up));
+        check!(None == i8::MIN.try_round_directed_to(10, Direction::Down));
+        check!(
+            Some(-120)
+                == i8::MIN.try_round_directed_to(10, Direction::TowardZero)
+        );
+        check!(
+            None == i8::MIN.try_round_directed_to(10, Direction::AwayFromZero)
+        );
+    }
+
+    /// Reference implementation of directed rounding using `i16` arithmetic.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn round_directed_i16(
+        value: i16,
+        factor: i16,
+        direction: Direction,
+    ) -> i16 {
+        let up = match direction {
+            Direction::Up => true,
+            Direction::Down => false,
+            Direction::TowardZero => value < 0,
+            Direction::AwayFromZero => value >= 0,
+        };
+        if up {
+            -(-value).div_euclid(factor) * factor
+        } else {
+            value.div_euclid(factor) * factor
+        }
+    }
+
+    #[test]
+    fn round_all_u8s_directed() {
+        for direction in DIRECTIONS {
+            for value in u8::MIN..=u8::MAX {
+                for factor in 1..=u8::MAX {
+                    let expected = round_directed_i16(
+                        value.into(),
+                        factor.into(),
+                        direction,
+                    );
+                    check!(
+                        u8::try_from(expected).ok()
+                            == value.try_round_directed_to(factor, direction)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_all_i8s_directed() {
+        for direction in DIRECTIONS {
+            for value in i8::MIN..=i8::MAX {
+                for factor in 1..=i8::MAX {
+                    let expected = round_directed_i16(
+                        value.into(),
+                        factor.into(),
+                        direction,
+                    );
+                    check!(
+                        i8::try_from(expected).ok()
+                            == value.try_round_directed_to(factor, direction)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn saturating_round_integer() {
+        check!(260 == 255.saturating_round_to(10, Tie::Up));
+        check!(255 == 255u8.saturating_round_to(10, Tie::Up));
+        check!(250 == 254u8.saturating_round_to(10, Tie::Down));
+        check!(127 == 126i8.saturating_round_to(10, Tie::Up));
+        check!(-128 == (-125i8).saturating_round_to(10, Tie::Down));
+        check!(-120 == (-125i8).saturating_round_to(10, Tie::Up));
+    }
+
+    #[test]
+    fn wrapping_round_integer() {
+        check!(260 == 255.wrapping_round_to(10, Tie::Up));
+        check!(4 == 255u8.wrapping_round_to(10, Tie::Up));
+        check!(250 == 254u8.wrapping_round_to(10, Tie::Down));
+        check!(-126 == 126i8.wrapping_round_to(10, Tie::Up));
+        check!(126 == (-125i8).wrapping_round_to(10, Tie::Down));
+        check!(-120 == (-125i8).wrapping_round_to(10, Tie::Up));
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn overflow_all_u8s() {
+        for behavior in TIES {
+            for value in u8::MIN..=u8::MAX {
+                for factor in 1..=u8::MAX {
+                    let expected =
+                        i16::from(value).round_to(factor.into(), behavior);
+                    let saturated = u8::try_from(expected).unwrap_or(u8::MAX);
+                    check!(
+                        saturated
+                            == value.saturating_round_to(factor, behavior)
+                    );
+                    check!(
+                        expected as u8
+                            == value.wrapping_round_to(factor, behavior)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn overflow_all_i8s() {
+        for behavior in TIES {
+            for value in i8::MIN..=i8::MAX {
+                for factor in 1..=i8::MAX {
+                    let expected =
+                        i16::from(value).round_to(factor.into(), behavior);
+                    let saturated =
+                        i8::try_from(expected).unwrap_or(if expected < 0 {
+                            i8::MIN
+                        } else {
+                            i8::MAX
+                        });
+                    check!(
+                        saturated
+                            == value.saturating_round_to(factor, behavior)
+                    );
+                    check!(
+                        expected as i8
+                            == value.wrapping_round_to(factor, behavior)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_wrapping_integer() {
+        check!(Wrapping(260) == Wrapping(255).round_to(Wrapping(10), Tie::Up));
+        check!(Wrapping(4) == Wrapping(255u8).round_to(Wrapping(10), Tie::Up));
+        check!(
+            Wrapping(250) == Wrapping(255u8).round_to(Wrapping(10), Tie::Down)
+        );
+        check!(
+            Wrapping(126) == Wrapping(-125i8).round_to(Wrapping(10), Tie::Down)
+        );
+        check!(
+            Wrapping(4)
+                == Wrapping(251u8)
+                    .round_directed_to(Wrapping(10), Direction::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == Wrapping(251u8).checked_round_to(Wrapping(0), Tie::Up)
+        );
+    }
+
+    #[cfg(feature = "saturating")]
+    #[test]
+    fn round_saturating_integer() {
+        check!(
+            Saturating(260)
+                == Saturating(255).round_to(Saturating(10), Tie::Up)
+        );
+        check!(
+            Saturating(255)
+                == Saturating(255u8).round_to(Saturating(10), Tie::Up)
+        );
+        check!(
+            Saturating(250)
+                == Saturating(255u8).round_to(Saturating(10), Tie::Down)
+        );
+        check!(
+            Saturating(-128)
+                == Saturating(-125i8).round_to(Saturating(10), Tie::Down)
+        );
+        check!(
+            Saturating(255)
+                == Saturating(251u8)
+                    .round_directed_to(Saturating(10), Direction::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == Saturating(251u8).checked_round_to(Saturating(0), Tie::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "saturating_round_to() requires positive factor")]
+    fn saturating_round_integer_zero_factor() {
+        let _ = 0.saturating_round_to(0, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrapping_round_to() requires positive factor")]
+    fn wrapping_round_integer_negative_factor() {
+        let _ = 0.wrapping_round_to(-1, Tie::Up);
+    }
+
+    #[test]
+    fn checked_round_integer() {
+        check!(Ok(310) == 314.checked_round_to(10, Tie::Up));
+        check!(
+            Err(RoundError::Overflow) == 255u8.checked_round_to(10, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow) == i8::MIN.checked_round_to(3, Tie::Down)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 10.checked_round_to(0, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 10.checked_round_to(-10, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 10u8.checked_round_directed_to(0, Direction::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == 251u8.checked_round_directed_to(10, Direction::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "try_round_to() requires positive factor")]
+    fn try_round_integer_zero_factor() {
+        let _ = 0.try_round_to(0, Tie::Up);
+    }
+
     #[test]
     #[should_panic(expected = "try_round_to() requires positive factor")]
     fn round_integer_zero_factor() {
@@ -355,4 +1449,82 @@ mod tests {
     fn round_integer_negative_factor() {
         let _ = 0.round_to(-1, Tie::Up);
     }
+
+    #[test]
+    #[should_panic(
+        expected = "try_round_directed_to() requires positive factor"
+    )]
+    fn round_integer_directed_zero_factor() {
+        let _ = 0.round_directed_to(0, Direction::Up);
+    }
+
+    /// Check that rounding to the constant `FACTOR` matches rounding to it as
+    /// a variable for every `value`.
+    fn check_const_factor<T, I, const FACTOR: u128>(values: I)
+    where
+        T: ConstRoundable + Roundable + Copy + core::fmt::Debug + PartialEq,
+        T: TryFrom<u128>,
+        I: IntoIterator<Item = T>,
+    {
+        let factor = T::try_from(FACTOR).ok().unwrap();
+        for value in values {
+            for behavior in TIES {
+                check!(
+                    value.checked_round_to(factor, behavior)
+                        == value.checked_round_to_const::<_, FACTOR>(behavior),
+                    "{:?} {} {:?}",
+                    value,
+                    FACTOR,
+                    behavior
+                );
+            }
+            check!(
+                value.checked_round_to(factor, Tie::TowardEven)
+                    == value.checked_round_to_const::<_, FACTOR>(
+                        crate::tie::TowardEven
+                    ),
+                "{:?} {}",
+                value,
+                FACTOR
+            );
+            for direction in DIRECTIONS {
+                check!(
+                    value.checked_round_directed_to(factor, direction)
+                        == value.checked_round_directed_to_const::<FACTOR>(
+                            direction
+                        ),
+                    "{:?} {} {:?}",
+                    value,
+                    FACTOR,
+                    direction
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn round_to_const_matches_round_to() {
+        check_const_factor::<u8, _, 1>(u8::MIN..=u8::MAX);
+        check_const_factor::<u8, _, 10>(u8::MIN..=u8::MAX);
+        check_const_factor::<u8, _, 64>(u8::MIN..=u8::MAX);
+        check_const_factor::<u8, _, 255>(u8::MIN..=u8::MAX);
+        check_const_factor::<i8, _, 3>(i8::MIN..=i8::MAX);
+        check_const_factor::<i8, _, 16>(i8::MIN..=i8::MAX);
+        check_const_factor::<i8, _, 127>(i8::MIN..=i8::MAX);
+        check_const_factor::<u64, _, 1_000>([0, 499, 500, 1_500, u64::MAX]);
+        check_const_factor::<i64, _, 1_000>([
+            i64::MIN,
+            -1_500,
+            -500,
+            0,
+            500,
+            i64::MAX,
+        ]);
+        check_const_factor::<
+            i128,
+            _,
+            170_141_183_460_469_231_731_687_303_715_884_105_727,
+        >([i128::MIN, -1, 0, 1, i128::MAX]);
+        check_const_factor::<usize, _, 4_096>([0, 2_048, 6_144, usize::MAX]);
+    }
 }
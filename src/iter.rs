@@ -0,0 +1,495 @@
+//! Round every value produced by an iterator.
+
+use crate::{
+    rounded_or_none, unwrap_rounded, RoundError, Roundable, TieStrategy,
+};
+use core::iter::FusedIterator;
+
+/// Methods to round every value produced by an iterator.
+///
+/// This is implemented for every [`Iterator`]. The methods can be used on any
+/// iterator whose items are [`Roundable`], and return adapters that round each
+/// value to `factor` as it is produced, just like the [`Roundable`] functions.
+///
+/// ```rust
+/// use roundable::{RoundableIterator, Tie};
+///
+/// let values = [14, 15, 25, -15];
+/// let rounded: Vec<i32> =
+///     values.iter().copied().rounded_to(10, Tie::TowardEven).collect();
+/// assert!(rounded == [10, 20, 20, -20]);
+/// ```
+pub trait RoundableIterator: Iterator + Sized {
+    /// Round every value to the nearest `factor`. Panics if a value can’t be
+    /// rounded.
+    ///
+    /// This uses [`Roundable::round_to()`] on each value.
+    ///
+    /// ```rust
+    /// use roundable::{RoundableIterator, Tie};
+    ///
+    /// let values = [1.2, 2.5, -3.75];
+    /// let mut rounded = values.iter().copied().rounded_to(0.5, Tie::Up);
+    /// assert!(Some(1.0) == rounded.next());
+    /// assert!(Some(2.5) == rounded.next());
+    /// assert!(Some(-3.5) == rounded.next());
+    /// assert!(None == rounded.next());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// The adapter panics when it produces a value if `factor` is not
+    /// positive, or if the value can’t be rounded, e.g. because the result
+    /// does not fit in the type.
+    fn rounded_to<S>(self, factor: Self::Item, tie: S) -> RoundedTo<Self, S>
+    where
+        Self::Item: Roundable + Copy,
+        S: TieStrategy,
+    {
+        RoundedTo { iter: self, factor, tie }
+    }
+
+    /// Round every value to the nearest `factor`. Produces `None` for values
+    /// that can’t be rounded.
+    ///
+    /// This uses [`Roundable::try_round_to()`] on each value.
+    ///
+    /// ```rust
+    /// use roundable::{RoundableIterator, Tie};
+    ///
+    /// let values = [254u8, 255, 4];
+    /// let rounded: Vec<Option<u8>> =
+    ///     values.iter().copied().try_rounded_to(10, Tie::Up).collect();
+    /// assert!(rounded == [Some(250), None, Some(0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// The adapter panics when it produces a value if `factor` is not
+    /// positive.
+    fn try_rounded_to<S>(
+        self,
+        factor: Self::Item,
+        tie: S,
+    ) -> TryRoundedTo<Self, S>
+    where
+        Self::Item: Roundable + Copy,
+        S: TieStrategy,
+    {
+        TryRoundedTo { iter: self, factor, tie }
+    }
+
+    /// Round every value to the nearest `factor`. Produces a [`RoundError`]
+    /// for values that can’t be rounded.
+    ///
+    /// This uses [`Roundable::checked_round_to()`] on each value, so it never
+    /// panics. It can be collected into a `Result` to stop at the first error.
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, RoundableIterator, Tie};
+    ///
+    /// let values = [254u8, 255, 4];
+    /// let rounded: Result<Vec<u8>, RoundError> =
+    ///     values.iter().copied().checked_rounded_to(10, Tie::Up).collect();
+    /// assert!(Err(RoundError::Overflow) == rounded);
+    /// ```
+    fn checked_rounded_to<S>(
+        self,
+        factor: Self::Item,
+        tie: S,
+    ) -> CheckedRoundedTo<Self, S>
+    where
+        Self::Item: Roundable + Copy,
+        S: TieStrategy,
+    {
+        CheckedRoundedTo { iter: self, factor, tie }
+    }
+
+    /// Round every value to the nearest `factor` until a value can’t be
+    /// rounded.
+    ///
+    /// This stops at the first value that can’t be rounded. Afterward,
+    /// [`RoundedToWhileOk::error()`] returns the reason, so you can tell
+    /// whether the iterator was exhausted.
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, RoundableIterator, Tie};
+    ///
+    /// let values = [254u8, 255, 4];
+    /// let mut rounded =
+    ///     values.iter().copied().rounded_to_while_ok(10, Tie::Up);
+    /// assert!(rounded.by_ref().eq([250]));
+    /// assert!(Some(RoundError::Overflow) == rounded.error());
+    /// ```
+    fn rounded_to_while_ok<S>(
+        self,
+        factor: Self::Item,
+        tie: S,
+    ) -> RoundedToWhileOk<Self, S>
+    where
+        Self::Item: Roundable + Copy,
+        S: TieStrategy,
+    {
+        RoundedToWhileOk { iter: self, factor, tie, error: None }
+    }
+}
+
+impl<I: Iterator> RoundableIterator for I {}
+
+/// Define an adapter that rounds each value independently.
+///
+/// `$round` takes the adapter and a value, and returns the item to produce.
+macro_rules! map_adapter {
+    (
+        $(#[$meta:meta])*
+        $name:ident -> $item:ty,
+        |$adapter:ident, $value:ident| $round:expr
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug)]
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct $name<I: Iterator, S> {
+            /// The iterator producing values to round.
+            iter: I,
+
+            /// The factor to round to.
+            factor: I::Item,
+
+            /// How to break ties.
+            tie: S,
+        }
+
+        impl<I, S> $name<I, S>
+        where
+            I: Iterator,
+            I::Item: Roundable + Copy,
+            S: TieStrategy,
+        {
+            /// Round one value.
+            #[inline]
+            fn round(&self, $value: I::Item) -> $item {
+                let $adapter = self;
+                $round
+            }
+        }
+
+        impl<I, S> Iterator for $name<I, S>
+        where
+            I: Iterator,
+            I::Item: Roundable + Copy,
+            S: TieStrategy,
+        {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let value = self.iter.next()?;
+                Some(self.round(value))
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<I, S> DoubleEndedIterator for $name<I, S>
+        where
+            I: DoubleEndedIterator,
+            I::Item: Roundable + Copy,
+            S: TieStrategy,
+        {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let value = self.iter.next_back()?;
+                Some(self.round(value))
+            }
+        }
+
+        impl<I, S> ExactSizeIterator for $name<I, S>
+        where
+            I: ExactSizeIterator,
+            I::Item: Roundable + Copy,
+            S: TieStrategy,
+        {
+        }
+
+        impl<I, S> FusedIterator for $name<I, S>
+        where
+            I: FusedIterator,
+            I::Item: Roundable + Copy,
+            S: TieStrategy,
+        {
+        }
+    };
+}
+
+map_adapter! {
+    /// An iterator that rounds every value, and panics if a value can’t be
+    /// rounded.
+    ///
+    /// This is returned by [`RoundableIterator::rounded_to()`].
+    RoundedTo -> I::Item,
+    |adapter, value| unwrap_rounded(
+        value.checked_round_to(adapter.factor, adapter.tie),
+        "rounded_to",
+    )
+}
+
+map_adapter! {
+    /// An iterator that rounds every value, and produces `None` for values
+    /// that can’t be rounded.
+    ///
+    /// This is returned by [`RoundableIterator::try_rounded_to()`].
+    TryRoundedTo -> Option<I::Item>,
+    |adapter, value| rounded_or_none(
+        value.checked_round_to(adapter.factor, adapter.tie),
+        "try_rounded_to",
+    )
+}
+
+map_adapter! {
+    /// An iterator that rounds every value, and produces a [`RoundError`] for
+    /// values that can’t be rounded.
+    ///
+    /// This is returned by [`RoundableIterator::checked_rounded_to()`].
+    CheckedRoundedTo -> Result<I::Item, RoundError>,
+    |adapter, value| value.checked_round_to(adapter.factor, adapter.tie)
+}
+
+/// An iterator that rounds every value until a value can’t be rounded.
+///
+/// This is returned by [`RoundableIterator::rounded_to_while_ok()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RoundedToWhileOk<I: Iterator, S> {
+    /// The iterator producing values to round.
+    iter: I,
+
+    /// The factor to round to.
+    factor: I::Item,
+
+    /// How to break ties.
+    tie: S,
+
+    /// Why rounding stopped, if it did.
+    error: Option<RoundError>,
+}
+
+impl<I: Iterator, S> RoundedToWhileOk<I, S> {
+    /// Get the error that stopped rounding, or `None` if no value has failed
+    /// to round yet.
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, RoundableIterator, Tie};
+    ///
+    /// let mut rounded = (5u32..15).rounded_to_while_ok(0, Tie::Up);
+    /// assert!(None == rounded.error());
+    /// assert!(None == rounded.next());
+    /// assert!(Some(RoundError::NonPositiveFactor) == rounded.error());
+    /// ```
+    #[must_use]
+    pub fn error(&self) -> Option<RoundError> {
+        self.error
+    }
+}
+
+impl<I, S> Iterator for RoundedToWhileOk<I, S>
+where
+    I: Iterator,
+    I::Item: Roundable + Copy,
+    S: TieStrategy,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        match self.iter.next()?.checked_round_to(self.factor, self.tie) {
+            Ok(rounded) => Some(rounded),
+            Err(error) => {
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.error.is_some() {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+impl<I, S> FusedIterator for RoundedToWhileOk<I, S>
+where
+    I: FusedIterator,
+    I::Item: Roundable + Copy,
+    S: TieStrategy,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TIES;
+    use crate::{tie, Tie};
+    use assert2::check;
+    use core::time::Duration;
+
+    #[test]
+    fn rounded_to_matches_round_to() {
+        for tie in TIES {
+            for factor in 1..=i8::MAX {
+                let values = (i8::MIN..=i8::MAX)
+                    .filter(|value| value.try_round_to(factor, tie).is_some());
+                check!(values
+                    .clone()
+                    .rounded_to(factor, tie)
+                    .eq(values.map(|value| value.round_to(factor, tie))));
+            }
+        }
+    }
+
+    #[test]
+    fn try_rounded_to_matches_try_round_to() {
+        for tie in TIES {
+            for factor in 1..=u8::MAX {
+                let values = u8::MIN..=u8::MAX;
+                check!(values
+                    .clone()
+                    .try_rounded_to(factor, tie)
+                    .eq(values.map(|value| value.try_round_to(factor, tie))));
+            }
+        }
+    }
+
+    #[test]
+    fn checked_rounded_to_matches_checked_round_to() {
+        let values = [0.0, 1.25, -2.5, f64::MAX, f64::NAN, f64::INFINITY];
+        for factor in [0.5, 2.0, f64::MAX, 0.0, -1.0] {
+            for tie in TIES {
+                check!(values
+                    .iter()
+                    .copied()
+                    .checked_rounded_to(factor, tie)
+                    .eq(values
+                        .iter()
+                        .map(|value| value.checked_round_to(factor, tie))));
+            }
+        }
+    }
+
+    #[test]
+    fn rounded_to_duration() {
+        let values = [
+            Duration::from_millis(1_499),
+            Duration::from_millis(1_500),
+            Duration::from_millis(2_500),
+        ];
+        check!(values
+            .iter()
+            .copied()
+            .rounded_to(crate::SECOND, tie::TowardEven)
+            .eq([crate::SECOND, 2 * crate::SECOND, 2 * crate::SECOND]));
+    }
+
+    #[test]
+    fn double_ended() {
+        check!((1..=5)
+            .rounded_to(2, Tie::Up)
+            .rev()
+            .eq([6, 4, 4, 2, 2].iter().copied()));
+        check!((250u8..=255).try_rounded_to(10, Tie::Up).rev().eq([
+            None,
+            Some(250),
+            Some(250),
+            Some(250),
+            Some(250),
+            Some(250)
+        ]));
+        check!((1..=3).checked_rounded_to(2, Tie::Up).rev().eq([
+            Ok(4),
+            Ok(2),
+            Ok(2)
+        ]));
+    }
+
+    #[test]
+    fn size_hint() {
+        check!(5 == (1..6).rounded_to(2, Tie::Up).len());
+        check!(5 == (1..6).try_rounded_to(2, Tie::Up).len());
+        check!(5 == (1..6).checked_rounded_to(2, Tie::Up).len());
+        check!(
+            (0, Some(5)) == (1..=5).rounded_to_while_ok(2, Tie::Up).size_hint()
+        );
+    }
+
+    #[test]
+    fn rounded_to_while_ok() {
+        let mut rounded = [251u8, 245, 255, 5]
+            .iter()
+            .copied()
+            .rounded_to_while_ok(10, Tie::Up);
+        check!(None == rounded.error());
+        check!(Some(250) == rounded.next());
+        check!(Some(250) == rounded.next());
+        check!(None == rounded.next());
+        check!(Some(RoundError::Overflow) == rounded.error());
+        check!((0, Some(0)) == rounded.size_hint());
+
+        // It stays stopped, even though there are more values.
+        check!(None == rounded.next());
+        check!(Some(RoundError::Overflow) == rounded.error());
+    }
+
+    #[test]
+    fn rounded_to_while_ok_exhausted() {
+        let mut rounded = [1.25f32, 2.5]
+            .iter()
+            .copied()
+            .rounded_to_while_ok(0.5, Tie::Up);
+        check!(rounded.by_ref().eq([1.5, 2.5]));
+        check!(None == rounded.error());
+    }
+
+    #[test]
+    fn rounded_to_while_ok_nan() {
+        let mut rounded = [1.0, f64::NAN, 2.0]
+            .iter()
+            .copied()
+            .rounded_to_while_ok(1.0, Tie::Up);
+        check!(rounded.by_ref().eq([1.0]));
+        check!(Some(RoundError::NanInput) == rounded.error());
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow while rounding")]
+    fn rounded_to_overflow() {
+        let _ = core::iter::once(255u8).rounded_to(10, Tie::Up).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "rounded_to() requires positive factor")]
+    fn rounded_to_zero_factor() {
+        let _ = core::iter::once(10u8).rounded_to(0, Tie::Up).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "try_rounded_to() requires positive factor")]
+    fn try_rounded_to_zero_factor() {
+        let _ = core::iter::once(10u8).try_rounded_to(0, Tie::Up).count();
+    }
+
+    #[test]
+    fn lazy() {
+        // Nothing is rounded until values are requested.
+        let _ = core::iter::once(255u8).rounded_to(0, Tie::Up);
+    }
+}
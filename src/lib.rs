@@ -10,6 +10,17 @@
 //!    tie_strategy)`](Roundable::try_round_to()) (returns `None` on overflow)
 //!  * [`Roundable::round_to(factor, tie_strategy)`](Roundable::round_to())
 //!    (panics on overflow)
+//!  * [`Roundable::checked_round_to(factor,
+//!    tie_strategy)`](Roundable::checked_round_to()) (returns a [`RoundError`]
+//!    on failure and never panics)
+//!  * [`Roundable::try_round_directed_to(factor,
+//!    direction)`](Roundable::try_round_directed_to()) (returns `None` on
+//!    overflow)
+//!  * [`Roundable::round_directed_to(factor,
+//!    direction)`](Roundable::round_directed_to()) (panics on overflow)
+//!  * [`Roundable::checked_round_directed_to(factor,
+//!    direction)`](Roundable::checked_round_directed_to()) (returns a
+//!    [`RoundError`] on failure and never panics)
 //!
 //! ### Example
 //!
@@ -38,6 +49,277 @@
 //!   * [`Tie::TowardEven`]: Round ties toward the “even” number (see docs).
 //!   * [`Tie::TowardOdd`]: Round ties toward the “odd” number (see docs).
 //!
+//! If you always use the same strategy, the types in the [`tie`] module choose
+//! it at compile time instead. Every function that accepts a [`Tie`] also
+//! accepts these (or anything else that implements [`TieStrategy`]), and the
+//! compiler can optimize the rounding code for just that strategy.
+//!
+//! ```rust
+//! use roundable::{tie, Roundable, Tie};
+//!
+//! assert!(20 == 15.round_to(10, Tie::TowardEven));
+//! assert!(20 == 15.round_to(10, tie::TowardEven));
+//! ```
+//!
+//! For rules that depend on the value or change between values, implement
+//! [`TieBreaker`] and use [`TieBreakerRoundable`]. A tie breaker gets the
+//! value, the candidates above and below it (`None` if one doesn’t fit in the
+//! type), and the factor, and returns the [`Direction`] to round. It can keep
+//! state, e.g. to alternate between rounding ties up and down. Every tie
+//! strategy, including [`Tie`], is also a tie breaker.
+//!
+//! ```rust
+//! use roundable::{Direction, TieBreaker, TieBreakerRoundable};
+//!
+//! struct Alternate(bool);
+//!
+//! impl<T> TieBreaker<T> for Alternate {
+//!     fn break_tie(
+//!         &mut self,
+//!         _: T,
+//!         _: Option<T>,
+//!         _: Option<T>,
+//!         _: T,
+//!     ) -> Direction {
+//!         self.0 = !self.0;
+//!         if self.0 { Direction::Up } else { Direction::Down }
+//!     }
+//! }
+//!
+//! let mut breaker = Alternate(false);
+//! assert!(20 == 15.round_to_with_breaker(10, &mut breaker));
+//! assert!(10 == 15.round_to_with_breaker(10, &mut breaker));
+//! ```
+//!
+//! ## Directed rounding
+//!
+//! Sometimes you don’t want the nearest round number, but the next round number
+//! in a particular [`Direction`], e.g. to find the start of the 5 minute bucket
+//! that contains a time. This is like `floor()`, `ceil()`, and `trunc()`, but
+//! for an arbitrary factor:
+//!
+//!   * [`Direction::Up`]: Round toward positive infinity (ceiling).
+//!   * [`Direction::Down`]: Round toward negative infinity (floor).
+//!   * [`Direction::TowardZero`]: Round toward zero (truncate).
+//!   * [`Direction::AwayFromZero`]: Round away from zero.
+//!
+//! ```rust
+//! use roundable::{Direction, Roundable};
+//!
+//! assert!(310 == 301.round_directed_to(10, Direction::Up));
+//! assert!(-310 == (-301).round_directed_to(10, Direction::Down));
+//! assert!(-300 == (-301).round_directed_to(10, Direction::TowardZero));
+//! assert!(None == 251u8.try_round_directed_to(10, Direction::Up));
+//! ```
+//!
+//! ## Rounding to a grid with an origin
+//!
+//! [`Roundable`] rounds to multiples of the factor, i.e. to a grid that passes
+//! through zero. [`OriginRoundable`] rounds to a grid that passes through
+//! another origin instead, e.g. every 10 starting at 3 (…, -7, 3, 13, 23, …),
+//! or every 15 minutes starting at :05. The origin can be larger than the
+//! value, and ties are resolved with any tie strategy.
+//!
+//! ```rust
+//! use roundable::{OriginRoundable, Tie, MINUTE};
+//!
+//! assert!(13 == 10.round_to_with_origin(10, 3, Tie::Up));
+//! assert!(-7 == (-4).round_to_with_origin(10, 3, Tie::Up));
+//! assert!(None == 0u8.try_round_to_with_origin(10, 7, Tie::Up));
+//!
+//! let time = 17 * MINUTE;
+//! assert!(20 * MINUTE == time.round_to_with_origin(15 * MINUTE, 5 * MINUTE, Tie::Up));
+//! ```
+//!
+//! ## Rounding to a set of values
+//!
+//! When the allowed values aren’t evenly spaced, e.g. standard timeouts or
+//! discount levels, use [`SetRoundable`] to round to the nearest member of a
+//! sorted slice. Ties are resolved with any tie strategy, and the index of the
+//! chosen value is also available.
+//!
+//! ```rust
+//! use roundable::{SetRoundable, Tie};
+//!
+//! let timeouts = [1, 5, 10, 30, 60];
+//! assert!(30 == 25.round_to_set(&timeouts, Tie::Up));
+//! assert!(10 == 20.round_to_set(&timeouts, Tie::Down));
+//! assert!(3 == 25.round_to_set_index(&timeouts, Tie::Up));
+//! ```
+//!
+//! ## Handling overflow
+//!
+//! By default, rounding to a value that doesn’t fit in the type either panics
+//! ([`Roundable::round_to()`]), returns `None` ([`Roundable::try_round_to()`]),
+//! or returns [`RoundError::Overflow`] ([`Roundable::checked_round_to()`]).
+//! Integers also support saturating and wrapping like the standard library’s
+//! `saturating_add()` and `wrapping_add()`:
+//!
+//!   * [`SaturatingRoundable::saturating_round_to()`] (also implemented for
+//!     [`Duration`](core::time::Duration))
+//!   * [`WrappingRoundable::wrapping_round_to()`]
+//!
+//! Note that saturating clamps to the bounds of the type, which are usually not
+//! multiples of the factor.
+//!
+//! ```rust
+//! use roundable::{SaturatingRoundable, Tie, WrappingRoundable};
+//!
+//! assert!(255 == 255u8.saturating_round_to(10, Tie::Up));
+//! assert!(4 == 255u8.wrapping_round_to(10, Tie::Up));
+//! ```
+//!
+//! [`Roundable`] is also implemented for [`core::num::Wrapping`], which always
+//! wraps on overflow instead of returning `None`. If you enable the
+//! `saturating` feature (requires Rust 1.74), it is implemented for
+//! `core::num::Saturating` as well.
+//!
+//! ```rust
+//! use roundable::{Roundable, Tie};
+//! use std::num::Wrapping;
+//!
+//! assert!(Some(Wrapping(4)) == Wrapping(255u8).try_round_to(Wrapping(10), Tie::Up));
+//! ```
+//!
+//! ## Rounding floats
+//!
+//! Floating point values are rounded as exactly as possible:
+//!
+//!   * Ties are detected exactly, so a value is only a tie if it is _exactly_
+//!     halfway between two multiples of the factor. Many decimal numbers can’t
+//!     be represented exactly in binary, so they might not be ties even if they
+//!     look like it. For example, `0.15_f64` is actually slightly less than
+//!     `0.15`, so it’s not a tie when rounding to `0.1`.
+//!   * The result is the float closest to the exact multiple of the factor, so
+//!     it is only rounded once. When the factor is the float closest to 1/n for
+//!     some integer n, like `0.1` or `0.01`, and the result is less than 2^21
+//!     (`f32`) or 2^50 (`f64`) times the factor, the result is the float
+//!     closest to a multiple of 1/n instead. This avoids results like
+//!     `0.30000000000000004`.
+//!
+//! ```rust
+//! use roundable::{Roundable, Tie};
+//!
+//! assert!(0.5 == 0.375.round_to(0.25, Tie::Up)); // Exact tie.
+//! assert!(0.1 == 0.15.round_to(0.1, Tie::Up)); // Not actually a tie.
+//! assert!(0.3 == 0.28.round_to(0.1, Tie::Up));
+//! ```
+//!
+//! NaN and infinite values can’t be rounded, so the `try_` functions return
+//! `None` and the `checked_` functions return [`RoundError::NanInput`] or
+//! [`RoundError::InfiniteInput`]. Results that would overflow to infinity are
+//! handled the same way, with [`RoundError::NonFiniteResult`].
+//!
+//! ```rust
+//! use roundable::{RoundError, Roundable, Tie};
+//!
+//! assert!(None == f64::NAN.try_round_to(1.0, Tie::Up));
+//! assert!(Err(RoundError::InfiniteInput) == f64::INFINITY.checked_round_to(1.0, Tie::Up));
+//! assert!(None == f64::MAX.try_round_to(1e308, Tie::Up));
+//! ```
+//!
+//! Subnormal values and factors work like any other value. If the factor is
+//! smaller than the spacing between floats around the value (its ULP), then the
+//! exact multiple is less than one ULP from the value, so rounding returns the
+//! value itself or an adjacent float. Similarly, an infinite factor rounds
+//! every value to zero, unless the rounding is directed away from zero, in
+//! which case it fails with [`RoundError::NonFiniteResult`].
+//!
+//! ## Rounding to decimal places
+//!
+//! Rounding a float to `0.01` uses its exact binary value, which often isn’t
+//! the decimal number it looks like. For example, `2.675_f64` is actually
+//! slightly less than 2.675, so it isn’t a tie. [`DecimalRoundable`] rounds the
+//! shortest decimal representation of the value instead, which is usually what
+//! you want for money and reports:
+//!
+//! ```rust
+//! use roundable::{DecimalRoundable, Roundable, Tie};
+//!
+//! assert!(2.67 == 2.675.round_to(0.01, Tie::Up));
+//! assert!(2.68 == 2.675.round_to_decimal_places(2, Tie::Up));
+//! assert!(1200.0 == 1234.5.round_to_decimal_places(-2, Tie::Up));
+//! ```
+//!
+//! ## Rounding to significant digits
+//!
+//! [`SignificantRoundable`] rounds integers and floats to a number of
+//! significant digits instead of to a fixed factor. Floats are rounded based on
+//! their shortest decimal representation, like
+//! [`DecimalRoundable::round_to_decimal_places()`].
+//!
+//! ```rust
+//! use roundable::{SignificantRoundable, Tie};
+//!
+//! assert!(123_000 == 123_456.round_to_significant(3, Tie::Up));
+//! assert!(0.00123 == 0.001_234_5.round_to_significant(3, Tie::Up));
+//! assert!(None == 255u8.try_round_to_significant(1, Tie::Up));
+//! ```
+//!
+//! ## Rounding to powers of two or ten
+//!
+//! [`PowerRoundable`] rounds integers and floats to the nearest power of two
+//! (e.g. for buffer sizes) or power of ten (e.g. for orders of magnitude). The
+//! [`Midpoint`] determines where values switch from rounding down to rounding
+//! up: halfway between the powers, or halfway on a log scale.
+//!
+//! ```rust
+//! use roundable::{Midpoint, PowerRoundable, Tie};
+//!
+//! assert!(64 == 48.round_to_power_of_two(Midpoint::Arithmetic, Tie::Up));
+//! assert!(32 == 48.round_to_power_of_two(Midpoint::Arithmetic, Tie::Down));
+//! assert!(64 == 46.round_to_power_of_two(Midpoint::Geometric, Tie::Up));
+//! assert!(0.001 == 0.0042.round_to_power_of_ten(Midpoint::Arithmetic, Tie::Up));
+//! ```
+//!
+//! ## Aligning offsets
+//!
+//! [`Alignable`] aligns unsigned integers, such as buffer offsets and sizes, to
+//! a power of two. This is equivalent to rounding up or down, but uses bit
+//! masks instead of division.
+//!
+//! ```rust
+//! use roundable::Alignable;
+//!
+//! assert!(4096 == 1usize.align_up(4096));
+//! assert!(4096 == 4097usize.align_down(4096));
+//! assert!(8192usize.is_aligned(4096));
+//! assert!(None == usize::MAX.try_align_up(4096));
+//! ```
+//!
+//! ## Rounding non-zero integers
+//!
+//! [`Roundable`] is implemented for the non-zero integer types like
+//! [`NonZeroU32`](core::num::NonZeroU32). Since the factor is also non-zero, it
+//! can’t be invalid: negative factors are treated like positive ones.
+//!
+//! Rounding a non-zero integer can produce zero, which fails with
+//! [`RoundError::ZeroResult`] (or `None`, or a panic, depending on the
+//! function). To make sure the result is never zero, round away from zero with
+//! [`Roundable::round_directed_to()`]:
+//!
+//! ```rust
+//! use roundable::{Direction, Roundable, Tie};
+//! use std::num::NonZeroU32;
+//!
+//! let value = NonZeroU32::new(3).unwrap();
+//! let factor = NonZeroU32::new(10).unwrap();
+//! assert!(None == value.try_round_to(factor, Tie::Up));
+//! assert!(factor == value.round_directed_to(factor, Direction::AwayFromZero));
+//! ```
+//!
+//! To round other integers to a factor that can’t be invalid, use
+//! [`NonZeroFactorRoundable`]. The factor is a non-zero unsigned integer of the
+//! same size, e.g. [`NonZeroU32`](core::num::NonZeroU32) for `i32`:
+//!
+//! ```rust
+//! use roundable::{NonZeroFactorRoundable, Tie};
+//! use std::num::NonZeroU32;
+//!
+//! let factor = NonZeroU32::new(10).unwrap();
+//! assert!(-10 == (-15i32).round_to_nonzero_factor(factor, Tie::Up));
+//! ```
+//!
 //! ## Rounding `Duration`
 //!
 //! [`Duration`](core::time::Duration) can be rounded to a `Duration` factor,
@@ -52,11 +334,200 @@
 //! assert!(MINUTE == Duration::from_millis(59_500).round_to(SECOND, Tie::Up));
 //! ```
 //!
+//! ## Rounding many values to the same factor
+//!
+//! Rounding with [`Roundable::try_round_to()`] divides by the factor every
+//! time. If you need to round many values to the same factor, a [`Rounder`]
+//! checks the factor once and precomputes a multiplier to use instead of
+//! division (like [libdivide](https://libdivide.com)). It works for integers
+//! and [`Duration`](core::time::Duration), and produces the same results as
+//! [`Roundable`].
+//!
+//! ```rust
+//! use roundable::{Rounder, Tie, SECOND};
+//! use std::time::Duration;
+//!
+//! let rounder = Rounder::new(1_000u64).unwrap();
+//! assert!(2_000 == rounder.round(1_500, Tie::Up));
+//! assert!(None == rounder.try_round(u64::MAX, Tie::Up));
+//!
+//! let rounder = Rounder::new(SECOND).unwrap();
+//! assert!(SECOND == rounder.round(Duration::from_millis(700), Tie::Up));
+//! ```
+//!
+//! ## Rounding slices
+//!
+//! [`SliceRoundable`] rounds every value in a slice to the same factor, either
+//! in place or into another slice. It produces the same results as
+//! [`Roundable::try_round_to()`], but it checks the factor once and rounds
+//! integers and `f32`s with a kernel that the compiler can vectorize. If a
+//! value can’t be rounded, it returns the index of the first value that failed.
+//!
+//! ```rust
+//! use roundable::{SliceRoundable, Tie};
+//!
+//! let mut values = [1.2f32, 2.5, -3.75];
+//! f32::round_slice_to(&mut values, 0.5, Tie::Up);
+//! assert!(values == [1.0, 2.5, -3.5]);
+//!
+//! let input = [100u8, 249, 255, 120];
+//! let mut output = [0; 4];
+//! assert!(Err(2) == u8::try_round_slice_into(&input, &mut output, 10, Tie::Up));
+//! ```
+//!
+//! How much faster this is depends on the target. Vector instructions beyond
+//! the baseline, e.g. enabled with `-C target-cpu=native`, help integers the
+//! most.
+//!
+//! ## Rounding iterators
+//!
+//! [`RoundableIterator`] adds methods to every [`Iterator`] to round the values
+//! it produces. [`rounded_to()`](RoundableIterator::rounded_to()) panics if a
+//! value can’t be rounded,
+//! [`try_rounded_to()`](RoundableIterator::try_rounded_to())
+//! and [`checked_rounded_to()`](RoundableIterator::checked_rounded_to())
+//! produce an `Option` or a `Result` for each value, and
+//! [`rounded_to_while_ok()`](RoundableIterator::rounded_to_while_ok()) stops at
+//! the first value that can’t be rounded.
+//!
+//! ```rust
+//! use roundable::{RoundError, RoundableIterator, Tie};
+//!
+//! let values = [14, 15, 25, -15];
+//! let rounded: Vec<i32> =
+//!     values.iter().copied().rounded_to(10, Tie::TowardEven).collect();
+//! assert!(rounded == [10, 20, 20, -20]);
+//!
+//! let values = [254u8, 255, 4];
+//! let rounded: Result<Vec<u8>, RoundError> =
+//!     values.iter().copied().checked_rounded_to(10, Tie::Up).collect();
+//! assert!(Err(RoundError::Overflow) == rounded);
+//! ```
+//!
+//! ## Rounding parts of a total
+//!
+//! Rounding parts independently can change their sum: 33 + 33 + 33 is not 100.
+//! [`Apportionable`] uses the largest remainder method to round parts so that
+//! they still add up to the rounded total. It can round a slice of values, or
+//! split a total by integer ratios. It works for integers, and breaks ties
+//! between parts with equal remainders according to the tie strategy. To round
+//! floats, scale them to integers first, e.g. use cents instead of dollars.
+//!
+//! ```rust
+//! use roundable::{Apportionable, Tie};
+//!
+//! let mut parts = [0; 3];
+//! i64::split_to(100, &[1, 1, 1], &mut parts, 1, Tie::Up);
+//! assert!(parts == [34, 33, 33]);
+//!
+//! // Round prices in cents to whole dollars.
+//! let mut rounded = [0; 3];
+//! i32::apportion_to(&[1_40, 2_30, 3_30], &mut rounded, 100, Tie::Up);
+//! assert!(rounded == [2_00, 2_00, 3_00]);
+//! ```
+//!
+//! ## Carrying rounding errors
+//!
+//! Rounding each value in a sequence independently can add up to a large error:
+//! 0.4 rounds to 0 every time. [`CarryRounder`] adds the residual from rounding
+//! each value to the next value before rounding it, so the running sum of the
+//! rounded values stays within half of the factor of the running sum of the
+//! original values. It works for integers, floats, and
+//! [`Duration`](core::time::Duration), and the residual can be inspected or
+//! reset.
+//!
+//! ```rust
+//! use roundable::{CarryRounder, Tie};
+//!
+//! let mut rounder = CarryRounder::new(10u32).unwrap();
+//! let rounded: Vec<u32> =
+//!     [3; 5].iter().map(|&value| rounder.round(value, Tie::Up)).collect();
+//! assert!(rounded == [0, 10, 0, 0, 10]);
+//! assert!(-5 == rounder.residual());
+//! ```
+//!
+//! ## Stochastic rounding
+//!
+//! Rounding many values to the nearest multiple can add up to a large error.
+//! [`StochasticRoundable`] rounds at random instead, with the probability of
+//! rounding away from zero proportional to the remainder: 3 rounds to 10 about
+//! 30% of the time when rounding to 10. On average, the result is the original
+//! value. It works for integers, floats, and
+//! [`Duration`](core::time::Duration).
+//!
+//! Random numbers come from a [`RandomSource`], a one-function trait that is
+//! easy to implement for any generator. With the `rand` feature, it is
+//! implemented for every `rand_core::RngCore`.
+//!
+//! ```rust
+//! use roundable::{RandomSource, StochasticRoundable};
+//!
+//! struct Xorshift(u64);
+//!
+//! impl RandomSource for Xorshift {
+//!     fn next_u64(&mut self) -> u64 {
+//!         self.0 ^= self.0 << 13;
+//!         self.0 ^= self.0 >> 7;
+//!         self.0 ^= self.0 << 17;
+//!         self.0
+//!     }
+//! }
+//!
+//! let mut random = Xorshift(0x2545_f491_4f6c_dd1d);
+//! let rounded = 0.25.round_stochastic_to(1.0, &mut random);
+//! assert!(rounded == 0.0 || rounded == 1.0);
+//! ```
+//!
+//! ## Rounding to a constant factor
+//!
+//! If the factor is known at compile time, [`ConstRoundable`] takes it as a
+//! const generic parameter. This lets the compiler optimize away the division,
+//! and checks that the factor is valid at compile time.
+//!
+//! ```rust
+//! use roundable::{ConstRoundable, Direction, Tie};
+//!
+//! assert!(2_000 == 1_500u64.round_to_const::<_, 1_000>(Tie::Up));
+//! assert!(None == 255u8.try_round_to_const::<_, 10>(Tie::Up));
+//! assert!(1_000 == 1u32.round_directed_to_const::<1_000>(Direction::Up));
+//! ```
+//!
+//! ## Rounding in `const` contexts
+//!
+//! [`Roundable`] methods can’t be called when defining a constant. The
+//! [`const_fn`] module has `const fn` versions of [`Roundable::round_to()`] and
+//! [`Roundable::try_round_to()`] for each integer type, which take a [`Tie`].
+//! If rounding fails while defining a constant, it fails to compile.
+//!
+//! ```rust
+//! use roundable::{const_fn, Tie};
+//!
+//! const TIMEOUT_MS: u64 = const_fn::round_u64_to(1_250, 100, Tie::TowardEven);
+//! assert!(TIMEOUT_MS == 1_200);
+//! ```
+//!
+//! Rounding [`Duration`](core::time::Duration) in a `const fn` requires Rust
+//! 1.58, so it is only available with the `const-duration` feature.
+//!
 //! ## `#![no_std]` by default
 //!
 //! You can use this crate with or without `std` and `alloc`. You do not need to
 //! enable or disable features either way.
 //!
+//! If you do enable the `std` feature, [`RoundError`] will implement
+//! `std::error::Error` (which is the same as `core::error::Error` in Rust 1.81
+//! and later).
+//!
+//! ## Features
+//!
+//!   * `std`: Implement `std::error::Error` for [`RoundError`].
+//!   * `const-duration`: Add `const fn` rounding for `Duration` to
+//!     [`const_fn`]. Requires Rust 1.58.
+//!   * `rand`: Implement [`RandomSource`] for random number generators that
+//!     implement `rand_core::RngCore`.
+//!   * `saturating`: Implement [`Roundable`] for `core::num::Saturating`.
+//!     Requires Rust 1.74.
+//!
 //! ## Minimum supported Rust version
 //!
 //! Currently the minimum supported Rust version (MSRV) is **1.56.1**. Future
@@ -66,10 +537,51 @@
 #![forbid(unsafe_code)]
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod align;
+pub use align::*;
+mod apportion;
+pub use apportion::*;
+mod breaker;
+pub use breaker::*;
+mod carry;
+pub use carry::*;
+pub mod const_fn;
+mod constant;
+pub use constant::*;
+mod decimal;
+pub use decimal::*;
+mod divisor;
 mod duration;
 pub use duration::*;
+mod error;
+pub use error::*;
 mod float;
 mod int;
+mod iter;
+pub use iter::*;
+mod nonzero;
+pub use nonzero::*;
+mod origin;
+pub use origin::*;
+mod power;
+pub use power::*;
+mod rounder;
+pub use rounder::*;
+mod scientific;
+mod set;
+pub use set::*;
+mod significant;
+pub use significant::*;
+mod slice;
+pub use slice::*;
+mod stochastic;
+pub use stochastic::*;
+#[cfg(test)]
+mod test_util;
+pub mod tie;
 
 /// How to handle a value that is exactly half, e.g. `5.round_to(10, ...)`.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -198,6 +710,159 @@ pub enum Tie {
     TowardOdd,
 }
 
+/// How to resolve ties.
+///
+/// This is implemented by [`Tie`], which picks a strategy at run time, and by
+/// the zero-sized types in the [`tie`] module, which pick a strategy at compile
+/// time. All of the functions that take a tie strategy accept either, but the
+/// types in [`tie`] allow the compiler to optimize rounding for one strategy.
+///
+/// ```rust
+/// use roundable::{tie, Roundable, Tie};
+///
+/// assert!(10 == 5.round_to(10, Tie::Up));
+/// assert!(10 == 5.round_to(10, tie::Up));
+/// ```
+///
+/// You can implement this to add your own strategies:
+///
+/// ```rust
+/// use roundable::{Roundable, TieStrategy};
+///
+/// /// Round positive ties up and negative ties toward zero.
+/// #[derive(Clone, Copy)]
+/// struct UpOrTowardZero;
+///
+/// impl TieStrategy for UpOrTowardZero {
+///     fn is_away_from_zero<F>(self, negative: bool, _is_base_odd: F) -> bool
+///     where
+///         F: FnOnce() -> bool,
+///     {
+///         !negative
+///     }
+/// }
+///
+/// assert!(10 == 5.round_to(10, UpOrTowardZero));
+/// assert!(0 == (-5).round_to(10, UpOrTowardZero));
+/// ```
+pub trait TieStrategy: Copy {
+    /// Should a tie be resolved by rounding away from zero?
+    ///
+    /// `negative` is whether the value is negative, and `is_base_odd` returns
+    /// whether the multiple of the factor between the value and zero is odd
+    /// (see [`Tie::TowardEven`]).
+    fn is_away_from_zero<F>(self, negative: bool, is_base_odd: F) -> bool
+    where
+        F: FnOnce() -> bool;
+}
+
+impl Tie {
+    /// Should a tie be resolved by rounding away from zero?
+    ///
+    /// This is the same as [`TieStrategy::is_away_from_zero()`], but it can be
+    /// used in a `const fn` since it doesn’t call a closure.
+    pub(crate) const fn is_away_from_zero_const(
+        self,
+        negative: bool,
+        is_base_odd: bool,
+    ) -> bool {
+        match self {
+            Self::Up => !negative,
+            Self::Down => negative,
+            Self::TowardZero => false,
+            Self::AwayFromZero => true,
+            Self::TowardEven => is_base_odd,
+            Self::TowardOdd => !is_base_odd,
+        }
+    }
+}
+
+impl TieStrategy for Tie {
+    #[inline]
+    fn is_away_from_zero<F>(self, negative: bool, is_base_odd: F) -> bool
+    where
+        F: FnOnce() -> bool,
+    {
+        match self {
+            Self::Up => tie::Up.is_away_from_zero(negative, is_base_odd),
+            Self::Down => tie::Down.is_away_from_zero(negative, is_base_odd),
+            Self::TowardZero => {
+                tie::TowardZero.is_away_from_zero(negative, is_base_odd)
+            }
+            Self::AwayFromZero => {
+                tie::AwayFromZero.is_away_from_zero(negative, is_base_odd)
+            }
+            Self::TowardEven => {
+                tie::TowardEven.is_away_from_zero(negative, is_base_odd)
+            }
+            Self::TowardOdd => {
+                tie::TowardOdd.is_away_from_zero(negative, is_base_odd)
+            }
+        }
+    }
+}
+
+/// Which way to round a value that is not already a multiple of the factor.
+///
+/// Values that are already multiples of the factor are never changed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    /// Round toward positive infinity (like `ceil()`).
+    ///
+    /// ```rust
+    /// use roundable::{Direction, Roundable};
+    ///
+    /// assert!(10 == 1.round_directed_to(10, Direction::Up));
+    /// assert!(0 == (-9).round_directed_to(10, Direction::Up));
+    /// assert!(10 == 10.round_directed_to(10, Direction::Up));
+    /// ```
+    Up,
+
+    /// Round toward negative infinity (like `floor()`).
+    ///
+    /// ```rust
+    /// use roundable::{Direction, Roundable};
+    ///
+    /// assert!(0 == 9.round_directed_to(10, Direction::Down));
+    /// assert!(-10 == (-1).round_directed_to(10, Direction::Down));
+    /// assert!(-10 == (-10).round_directed_to(10, Direction::Down));
+    /// ```
+    Down,
+
+    /// Round toward zero (like `trunc()`).
+    ///
+    /// ```rust
+    /// use roundable::{Direction, Roundable};
+    ///
+    /// assert!(0 == 9.round_directed_to(10, Direction::TowardZero));
+    /// assert!(0 == (-9).round_directed_to(10, Direction::TowardZero));
+    /// ```
+    TowardZero,
+
+    /// Round away from zero.
+    ///
+    /// ```rust
+    /// use roundable::{Direction, Roundable};
+    ///
+    /// assert!(10 == 1.round_directed_to(10, Direction::AwayFromZero));
+    /// assert!(-10 == (-1).round_directed_to(10, Direction::AwayFromZero));
+    /// ```
+    AwayFromZero,
+}
+
+impl Direction {
+    /// Does rounding a value with the given sign in this direction move it
+    /// toward zero?
+    pub(crate) const fn is_toward_zero(self, negative: bool) -> bool {
+        match self {
+            Self::Up => negative,
+            Self::Down => !negative,
+            Self::TowardZero => true,
+            Self::AwayFromZero => false,
+        }
+    }
+}
+
 /// Methods to round to an arbitrary factor.
 ///
 /// For example, you might wish to round an integer to the nearest ten or
@@ -217,7 +882,8 @@ pub trait Roundable: Sized {
     /// [`Tie::Up`], which will cause ties to be resolved by choosing the higher
     /// round number.
     ///
-    /// See [`Tie`] for other tie strategies.
+    /// See [`Tie`] for other tie strategies, and [`TieStrategy`] to choose
+    /// one at compile time.
     ///
     /// ```rust
     /// use roundable::{Roundable, Tie};
@@ -239,9 +905,11 @@ pub trait Roundable: Sized {
     /// Panics if `factor` is not positive, e.g. if it’s 0, or if rounding would
     /// return a value that does not fit in the return type.
     #[must_use]
-    fn round_to(self, factor: Self, tie: Tie) -> Self {
-        self.try_round_to(factor, tie)
-            .expect("overflow while rounding")
+    fn round_to<S>(self, factor: Self, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(self.checked_round_to(factor, tie), "try_round_to")
     }
 
     /// Round to the nearest `factor`. Returns `None` if there is an overflow.
@@ -251,7 +919,8 @@ pub trait Roundable: Sized {
     /// [`Tie::Up`], which will cause ties to be resolved by choosing the higher
     /// round number.
     ///
-    /// See [`Tie`] for other tie strategies.
+    /// See [`Tie`] for other tie strategies, and [`TieStrategy`] to choose
+    /// one at compile time.
     ///
     /// ```rust
     /// use roundable::{Roundable, Tie};
@@ -268,9 +937,285 @@ pub trait Roundable: Sized {
     /// assert!(None == 255u8.try_round_to(10, Tie::Up));
     /// ```
     ///
+    /// This also returns `None` for values that can’t be rounded, such as NaN.
+    /// Use [`Roundable::checked_round_to()`] to find out why rounding failed.
+    ///
     /// # Panics
     ///
     /// Panics if `factor` is not positive, e.g. if it’s 0.
     #[must_use]
-    fn try_round_to(self, factor: Self, tie: Tie) -> Option<Self>;
+    fn try_round_to<S>(self, factor: Self, tie: S) -> Option<Self>
+    where
+        S: TieStrategy,
+    {
+        rounded_or_none(self.checked_round_to(factor, tie), "try_round_to")
+    }
+
+    /// Round to the nearest `factor`. Returns a [`RoundError`] on failure.
+    ///
+    /// This is just like [`Roundable::try_round_to()`], except that it never
+    /// panics. Instead, it reports why rounding failed.
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, Roundable, Tie};
+    ///
+    /// assert!(Ok(315) == 314.checked_round_to(5, Tie::Up));
+    /// assert!(Err(RoundError::Overflow) == 255u8.checked_round_to(10, Tie::Up));
+    /// assert!(Err(RoundError::NonPositiveFactor) == 314.checked_round_to(0, Tie::Up));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::NonPositiveFactor`] if `factor` is not positive,
+    /// and [`RoundError::Overflow`] if the result does not fit in the return
+    /// type. Floating point types can also return
+    /// [`RoundError::NanInput`], [`RoundError::InfiniteInput`], or
+    /// [`RoundError::NonFiniteResult`].
+    fn checked_round_to<S>(
+        self,
+        factor: Self,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+
+    /// Round to the next `factor` in `direction`. Panics if there is an
+    /// overflow.
+    ///
+    /// Unlike [`Roundable::round_to()`], this does not find the nearest round
+    /// number. Instead, it always rounds in the [`Direction`] passed. Values
+    /// that are already multiples of `factor` are returned unchanged.
+    ///
+    /// ```rust
+    /// use roundable::{Direction, Roundable};
+    ///
+    /// assert!(300 == 314.round_directed_to(50, Direction::Down));
+    /// assert!(350 == 314.round_directed_to(50, Direction::Up));
+    /// assert!(-300 == (-314).round_directed_to(50, Direction::TowardZero));
+    /// assert!(-350 == (-314).round_directed_to(50, Direction::AwayFromZero));
+    /// ```
+    ///
+    /// `251u8` can’t be rounded up to the next 10 (which would be 260) because
+    /// 260 won’t fit in a `u8`:
+    ///
+    /// ```rust,should_panic
+    /// # use roundable::{Direction, Roundable};
+    /// let _ = 251u8.round_directed_to(10u8, Direction::Up);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0, or if rounding would
+    /// return a value that does not fit in the return type.
+    #[must_use]
+    fn round_directed_to(self, factor: Self, direction: Direction) -> Self {
+        unwrap_rounded(
+            self.checked_round_directed_to(factor, direction),
+            "try_round_directed_to",
+        )
+    }
+
+    /// Round to the next `factor` in `direction`. Returns `None` if there is
+    /// an overflow.
+    ///
+    /// Unlike [`Roundable::try_round_to()`], this does not find the nearest
+    /// round number. Instead, it always rounds in the [`Direction`] passed.
+    /// Values that are already multiples of `factor` are returned unchanged.
+    ///
+    /// ```rust
+    /// use roundable::{Direction, Roundable};
+    ///
+    /// assert!(Some(300) == 314.try_round_directed_to(50, Direction::Down));
+    /// assert!(Some(350) == 314.try_round_directed_to(50, Direction::Up));
+    /// ```
+    ///
+    /// `251u8` can’t be rounded up to the next 10 (which would be 260) because
+    /// 260 won’t fit in a `u8`:
+    ///
+    /// ```rust
+    /// # use roundable::{Direction, Roundable};
+    /// assert!(None == 251u8.try_round_directed_to(10, Direction::Up));
+    /// assert!(Some(250) == 251u8.try_round_directed_to(10, Direction::Down));
+    /// ```
+    ///
+    /// This also returns `None` for values that can’t be rounded, such as NaN.
+    /// Use [`Roundable::checked_round_directed_to()`] to find out why rounding
+    /// failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0.
+    #[must_use]
+    fn try_round_directed_to(
+        self,
+        factor: Self,
+        direction: Direction,
+    ) -> Option<Self> {
+        rounded_or_none(
+            self.checked_round_directed_to(factor, direction),
+            "try_round_directed_to",
+        )
+    }
+
+    /// Round to the next `factor` in `direction`. Returns a [`RoundError`] on
+    /// failure.
+    ///
+    /// This is just like [`Roundable::try_round_directed_to()`], except that
+    /// it never panics. Instead, it reports why rounding failed.
+    ///
+    /// ```rust
+    /// use roundable::{Direction, RoundError, Roundable};
+    ///
+    /// assert!(Ok(250) == 251u8.checked_round_directed_to(10, Direction::Down));
+    /// assert!(
+    ///     Err(RoundError::Overflow)
+    ///         == 251u8.checked_round_directed_to(10, Direction::Up)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`Roundable::checked_round_to()`].
+    fn checked_round_directed_to(
+        self,
+        factor: Self,
+        direction: Direction,
+    ) -> Result<Self, RoundError>;
+}
+
+/// Round to an arbitrary factor, saturating at the numeric bounds instead of
+/// overflowing.
+///
+/// This is implemented for integers and [`Duration`](core::time::Duration).
+///
+/// When the rounded value doesn’t fit, the result is clamped to `MIN` or `MAX`
+/// of the type, which is usually **not** a multiple of the factor. For example,
+/// `255u8` rounded up to 10 would be 260, so it saturates to 255 rather than
+/// returning 250, the largest multiple that fits.
+///
+/// ```rust
+/// use roundable::{SaturatingRoundable, Tie};
+///
+/// // 255 is not a multiple of 10.
+/// assert!(255 == 255u8.saturating_round_to(10, Tie::Up));
+/// assert!(250 == 254u8.saturating_round_to(10, Tie::Down));
+/// assert!(i8::MIN == (-125i8).saturating_round_to(10, Tie::Down));
+/// ```
+pub trait SaturatingRoundable: Roundable {
+    /// Round to the nearest `factor`. Returns `MAX` or `MIN` if the result is
+    /// too large or too small to fit in the type.
+    ///
+    /// This is just like [`Roundable::round_to()`] except for how it handles
+    /// overflow. Note that `MAX` and `MIN` are generally not multiples of
+    /// `factor`. If you need a multiple of `factor` that fits, use
+    /// [`Roundable::try_round_directed_to()`] with [`Direction::TowardZero`].
+    ///
+    /// ```rust
+    /// use roundable::{SaturatingRoundable, Tie, SECOND};
+    /// use std::time::Duration;
+    ///
+    /// assert!(260 == 255u16.saturating_round_to(10, Tie::Up));
+    /// assert!(u16::MAX == u16::MAX.saturating_round_to(10, Tie::Up));
+    /// assert!(Duration::MAX == Duration::MAX.saturating_round_to(SECOND, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0.
+    #[must_use]
+    fn saturating_round_to<S>(self, factor: Self, tie: S) -> Self
+    where
+        S: TieStrategy;
+}
+
+/// Round to an arbitrary factor, wrapping around at the numeric bounds instead
+/// of overflowing.
+///
+/// This is implemented for integers.
+///
+/// ```rust
+/// use roundable::{WrappingRoundable, Tie};
+///
+/// assert!(4 == 255u8.wrapping_round_to(10, Tie::Up));
+/// assert!(126 == (-125i8).wrapping_round_to(10, Tie::Down));
+/// ```
+pub trait WrappingRoundable: Roundable {
+    /// Round to the nearest `factor`, wrapping around at the boundary of the
+    /// type.
+    ///
+    /// This is just like [`Roundable::round_to()`] except for how it handles
+    /// overflow. The result is the same as if the value was rounded without
+    /// overflowing, and then wrapped into the type like
+    /// [`u32::wrapping_add()`].
+    ///
+    /// ```rust
+    /// use roundable::{WrappingRoundable, Tie};
+    ///
+    /// assert!(260 == 255u16.wrapping_round_to(10, Tie::Up));
+    /// assert!(4 == 255u8.wrapping_round_to(10, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0.
+    #[must_use]
+    fn wrapping_round_to<S>(self, factor: Self, tie: S) -> Self
+    where
+        S: TieStrategy;
+}
+
+/// Get the value out of a `checked_` result, or panic.
+///
+/// `name` is the function to blame for an invalid argument.
+fn unwrap_rounded<T>(result: Result<T, RoundError>, name: &str) -> T {
+    match result {
+        Ok(value) => value,
+        Err(RoundError::NonPositiveFactor) => {
+            panic!("{}() requires positive factor", name)
+        }
+        Err(RoundError::ZeroDigits) => {
+            panic!("{}() requires at least one digit", name)
+        }
+        Err(RoundError::InvalidAlignment) => {
+            panic!("{}() requires power of two alignment", name)
+        }
+        Err(RoundError::ZeroRatios) => {
+            panic!("{}() requires a non-zero ratio", name)
+        }
+        Err(RoundError::EmptySet) => {
+            panic!("{}() requires a non-empty set", name)
+        }
+        Err(RoundError::InvalidOrigin) => {
+            panic!("{}() requires finite origin", name)
+        }
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Convert a `checked_` result into an `Option`, or panic if an argument was
+/// invalid, e.g. if `factor` was not positive.
+///
+/// `name` is the function to blame for the invalid argument.
+fn rounded_or_none<T>(result: Result<T, RoundError>, name: &str) -> Option<T> {
+    match result {
+        Err(RoundError::NonPositiveFactor) => {
+            panic!("{}() requires positive factor", name)
+        }
+        Err(RoundError::ZeroDigits) => {
+            panic!("{}() requires at least one digit", name)
+        }
+        Err(RoundError::InvalidAlignment) => {
+            panic!("{}() requires power of two alignment", name)
+        }
+        Err(RoundError::ZeroRatios) => {
+            panic!("{}() requires a non-zero ratio", name)
+        }
+        Err(RoundError::EmptySet) => {
+            panic!("{}() requires a non-empty set", name)
+        }
+        Err(RoundError::InvalidOrigin) => {
+            panic!("{}() requires finite origin", name)
+        }
+        result => result.ok(),
+    }
 }
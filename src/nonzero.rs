@@ -0,0 +1,509 @@
+//! Implement `Roundable` for non-zero integers, and round integers to
+//! non-zero factors.
+
+use crate::int::Rounding;
+use crate::origin::{round_u128_directed_with_origin, round_u128_with_origin};
+use crate::{
+    rounded_or_none, unwrap_rounded, Direction, RoundError, Roundable,
+    TieStrategy,
+};
+use core::convert::identity;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+    NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+/// Round integers to a factor that is guaranteed to be positive.
+///
+/// This is implemented for integers and non-zero integers. The factor is the
+/// non-zero unsigned integer of the same size, e.g. [`NonZeroU32`] for `i32`,
+/// `u32`, [`NonZeroI32`], and [`NonZeroU32`]. Since the factor can’t be zero
+/// or negative, these functions can’t fail because of it: they only fail if
+/// the result doesn’t fit in the type, or is zero for a non-zero type.
+///
+/// The factor can be larger than the largest value of a signed type, e.g.
+/// rounding an `i8` to 200 produces -200, 0, or 200, and only 0 fits.
+///
+/// ```rust
+/// use roundable::{NonZeroFactorRoundable, Tie};
+/// use std::num::NonZeroU32;
+///
+/// let factor = NonZeroU32::new(10).unwrap();
+/// assert!(20 == 15u32.round_to_nonzero_factor(factor, Tie::Up));
+/// assert!(-20 == (-15i32).round_to_nonzero_factor(factor, Tie::Down));
+/// ```
+pub trait NonZeroFactorRoundable: Roundable {
+    /// The type of the factor.
+    type Factor: Copy;
+
+    /// Round to the nearest `factor`. Panics if the result does not fit in the
+    /// type.
+    ///
+    /// ```rust
+    /// use roundable::{NonZeroFactorRoundable, Tie};
+    /// use std::num::NonZeroU8;
+    ///
+    /// let factor = NonZeroU8::new(200).unwrap();
+    /// assert!(0 == 99i8.round_to_nonzero_factor(factor, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in the return type, or if it is zero
+    /// and the type is non-zero.
+    #[must_use]
+    fn round_to_nonzero_factor<S>(self, factor: Self::Factor, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            self.checked_round_to_nonzero_factor(factor, tie),
+            "round_to_nonzero_factor",
+        )
+    }
+
+    /// Round to the nearest `factor`. Returns `None` if the result does not
+    /// fit in the type, or if it is zero and the type is non-zero. This never
+    /// panics.
+    ///
+    /// ```rust
+    /// use roundable::{NonZeroFactorRoundable, Tie};
+    /// use std::num::NonZeroU8;
+    ///
+    /// let factor = NonZeroU8::new(200).unwrap();
+    /// assert!(None == 100i8.try_round_to_nonzero_factor(factor, Tie::Up));
+    /// assert!(Some(0) == 100i8.try_round_to_nonzero_factor(factor, Tie::Down));
+    /// ```
+    #[must_use]
+    fn try_round_to_nonzero_factor<S>(
+        self,
+        factor: Self::Factor,
+        tie: S,
+    ) -> Option<Self>
+    where
+        S: TieStrategy,
+    {
+        rounded_or_none(
+            self.checked_round_to_nonzero_factor(factor, tie),
+            "try_round_to_nonzero_factor",
+        )
+    }
+
+    /// Round to the nearest `factor`. Returns a [`RoundError`] on failure.
+    ///
+    /// ```rust
+    /// use roundable::{NonZeroFactorRoundable, RoundError, Tie};
+    /// use std::num::{NonZeroI8, NonZeroU8};
+    ///
+    /// let factor = NonZeroU8::new(10).unwrap();
+    /// let value = NonZeroI8::new(-4).unwrap();
+    /// assert!(Err(RoundError::Overflow) == 125i8.checked_round_to_nonzero_factor(factor, Tie::Up));
+    /// assert!(Err(RoundError::ZeroResult) == value.checked_round_to_nonzero_factor(factor, Tie::Up));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::Overflow`] if the result does not fit in the
+    /// type, or [`RoundError::ZeroResult`] if it is zero and the type is
+    /// non-zero.
+    fn checked_round_to_nonzero_factor<S>(
+        self,
+        factor: Self::Factor,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+
+    /// Round to the next `factor` in `direction`. Panics if the result does
+    /// not fit in the type.
+    ///
+    /// ```rust
+    /// use roundable::{Direction, NonZeroFactorRoundable};
+    /// use std::num::NonZeroU64;
+    ///
+    /// let factor = NonZeroU64::new(10).unwrap();
+    /// assert!(-10 == (-1i64).round_directed_to_nonzero_factor(factor, Direction::Down));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in the return type, or if it is zero
+    /// and the type is non-zero.
+    #[must_use]
+    fn round_directed_to_nonzero_factor(
+        self,
+        factor: Self::Factor,
+        direction: Direction,
+    ) -> Self {
+        unwrap_rounded(
+            self.checked_round_directed_to_nonzero_factor(factor, direction),
+            "round_directed_to_nonzero_factor",
+        )
+    }
+
+    /// Round to the next `factor` in `direction`. Returns `None` if the result
+    /// does not fit in the type, or if it is zero and the type is non-zero.
+    /// This never panics.
+    ///
+    /// ```rust
+    /// use roundable::{Direction, NonZeroFactorRoundable};
+    /// use std::num::NonZeroU8;
+    ///
+    /// let factor = NonZeroU8::new(10).unwrap();
+    /// assert!(None == 251u8.try_round_directed_to_nonzero_factor(factor, Direction::Up));
+    /// ```
+    #[must_use]
+    fn try_round_directed_to_nonzero_factor(
+        self,
+        factor: Self::Factor,
+        direction: Direction,
+    ) -> Option<Self> {
+        rounded_or_none(
+            self.checked_round_directed_to_nonzero_factor(factor, direction),
+            "try_round_directed_to_nonzero_factor",
+        )
+    }
+
+    /// Round to the next `factor` in `direction`. Returns a [`RoundError`] on
+    /// failure.
+    ///
+    /// ```rust
+    /// use roundable::{Direction, NonZeroFactorRoundable, RoundError};
+    /// use std::num::NonZeroU8;
+    ///
+    /// let factor = NonZeroU8::new(128).unwrap();
+    /// assert!(Ok(-128) == (-1i8).checked_round_directed_to_nonzero_factor(factor, Direction::Down));
+    /// assert!(
+    ///     Err(RoundError::Overflow)
+    ///         == 1i8.checked_round_directed_to_nonzero_factor(factor, Direction::Up)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::Overflow`] if the result does not fit in the
+    /// type, or [`RoundError::ZeroResult`] if it is zero and the type is
+    /// non-zero.
+    fn checked_round_directed_to_nonzero_factor(
+        self,
+        factor: Self::Factor,
+        direction: Direction,
+    ) -> Result<Self, RoundError>;
+}
+
+/// Implement rounding to non-zero factors for integer types.
+macro_rules! nonzero_factor_integer {
+    ($($ty:ident $factor:ident;)+) => {$(
+        impl NonZeroFactorRoundable for $ty {
+            type Factor = $factor;
+
+            // The factor always fits in unsigned types.
+            #[allow(
+                clippy::cast_lossless,
+                irrefutable_let_patterns,
+                unused_comparisons
+            )]
+            fn checked_round_to_nonzero_factor<S>(
+                self,
+                factor: $factor,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                // Use normal rounding, which is faster, if the factor fits.
+                if let Ok(factor) = $ty::try_from(factor.get()) {
+                    return self.checked_round_to(factor, tie);
+                }
+
+                round_u128_with_origin(
+                    Rounding::<$ty>::to_u128(self),
+                    factor.get() as u128,
+                    Rounding::<$ty>::to_u128(0),
+                    Rounding::<$ty>::to_u128($ty::MAX),
+                    self < 0,
+                    tie,
+                )
+                .map(Rounding::<$ty>::from_u128)
+            }
+
+            // The factor always fits in unsigned types.
+            #[allow(
+                clippy::cast_lossless,
+                irrefutable_let_patterns,
+                unused_comparisons
+            )]
+            fn checked_round_directed_to_nonzero_factor(
+                self,
+                factor: $factor,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                if let Ok(factor) = $ty::try_from(factor.get()) {
+                    return self.checked_round_directed_to(factor, direction);
+                }
+
+                round_u128_directed_with_origin(
+                    Rounding::<$ty>::to_u128(self),
+                    factor.get() as u128,
+                    Rounding::<$ty>::to_u128(0),
+                    Rounding::<$ty>::to_u128($ty::MAX),
+                    self < 0,
+                    direction,
+                )
+                .map(Rounding::<$ty>::from_u128)
+            }
+        }
+    )+}
+}
+
+nonzero_factor_integer! {
+    u8 NonZeroU8; u16 NonZeroU16; u32 NonZeroU32; u64 NonZeroU64;
+    u128 NonZeroU128; usize NonZeroUsize;
+    i8 NonZeroU8; i16 NonZeroU16; i32 NonZeroU32; i64 NonZeroU64;
+    i128 NonZeroU128; isize NonZeroUsize;
+}
+
+/// Implement rounding for non-zero integer types.
+///
+/// `$magnitude` converts the underlying integer to its unsigned magnitude, so
+/// that [`Roundable`] can treat negative factors like positive ones.
+macro_rules! roundable_nonzero {
+    ($($ty:ident $factor:ident $magnitude:path;)+) => {$(
+        impl Roundable for $ty {
+            fn checked_round_to<S>(
+                self,
+                factor: Self,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                self.checked_round_to_nonzero_factor(
+                    $factor::new($magnitude(factor.get()))
+                        .ok_or(RoundError::NonPositiveFactor)?,
+                    tie,
+                )
+            }
+
+            fn checked_round_directed_to(
+                self,
+                factor: Self,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                self.checked_round_directed_to_nonzero_factor(
+                    $factor::new($magnitude(factor.get()))
+                        .ok_or(RoundError::NonPositiveFactor)?,
+                    direction,
+                )
+            }
+        }
+
+        impl NonZeroFactorRoundable for $ty {
+            type Factor = $factor;
+
+            fn checked_round_to_nonzero_factor<S>(
+                self,
+                factor: $factor,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                self.get()
+                    .checked_round_to_nonzero_factor(factor, tie)
+                    .and_then(|value| {
+                        Self::new(value).ok_or(RoundError::ZeroResult)
+                    })
+            }
+
+            fn checked_round_directed_to_nonzero_factor(
+                self,
+                factor: $factor,
+                direction: Direction,
+            ) -> Result<Self, RoundError> {
+                self.get()
+                    .checked_round_directed_to_nonzero_factor(factor, direction)
+                    .and_then(|value| {
+                        Self::new(value).ok_or(RoundError::ZeroResult)
+                    })
+            }
+        }
+    )+}
+}
+
+roundable_nonzero! {
+    NonZeroU8 NonZeroU8 identity;
+    NonZeroU16 NonZeroU16 identity;
+    NonZeroU32 NonZeroU32 identity;
+    NonZeroU64 NonZeroU64 identity;
+    NonZeroU128 NonZeroU128 identity;
+    NonZeroUsize NonZeroUsize identity;
+    NonZeroI8 NonZeroU8 i8::unsigned_abs;
+    NonZeroI16 NonZeroU16 i16::unsigned_abs;
+    NonZeroI32 NonZeroU32 i32::unsigned_abs;
+    NonZeroI64 NonZeroU64 i64::unsigned_abs;
+    NonZeroI128 NonZeroU128 i128::unsigned_abs;
+    NonZeroIsize NonZeroUsize isize::unsigned_abs;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{DIRECTIONS, TIES};
+    use crate::Tie;
+    use assert2::check;
+
+    /// Convenient way to make a [`NonZeroU8`].
+    fn u8(n: u8) -> NonZeroU8 {
+        NonZeroU8::new(n).unwrap()
+    }
+
+    /// Convenient way to make a [`NonZeroI8`].
+    fn i8(n: i8) -> NonZeroI8 {
+        NonZeroI8::new(n).unwrap()
+    }
+
+    #[test]
+    fn round_nonzero_unsigned() {
+        check!(u8(10) == u8(7).round_to(u8(10), Tie::Up));
+        check!(u8(10) == u8(5).round_to(u8(10), Tie::Up));
+        check!(None == u8(5).try_round_to(u8(10), Tie::Down));
+        check!(None == u8(4).try_round_to(u8(10), Tie::Up));
+        check!(
+            Err(RoundError::ZeroResult)
+                == u8(4).checked_round_to(u8(10), Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == u8(255).checked_round_to(u8(10), Tie::Up)
+        );
+    }
+
+    #[test]
+    fn round_nonzero_signed() {
+        check!(i8(-10) == i8(-7).round_to(i8(10), Tie::Up));
+        check!(None == i8(-5).try_round_to(i8(10), Tie::Up));
+
+        // Negative factors are treated like positive ones.
+        check!(i8(10) == i8(5).round_to(i8(-10), Tie::Up));
+        check!(i8(-128) == i8(-64).round_to(i8(-128), Tie::Down));
+        check!(
+            Err(RoundError::ZeroResult)
+                == i8(-64).checked_round_to(i8(-128), Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == i8(64).checked_round_to(i8(-128), Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == i8(-125).checked_round_to(i8(10), Tie::Down)
+        );
+    }
+
+    #[test]
+    fn round_nonzero_directed() {
+        check!(u8(10) == u8(1).round_directed_to(u8(10), Direction::Up));
+        check!(None == u8(9).try_round_directed_to(u8(10), Direction::Down));
+        check!(
+            u8(10) == u8(1).round_directed_to(u8(10), Direction::AwayFromZero)
+        );
+        check!(
+            i8(-10)
+                == i8(-1).round_directed_to(i8(10), Direction::AwayFromZero)
+        );
+        check!(
+            Err(RoundError::ZeroResult)
+                == i8(-1).checked_round_directed_to(i8(10), Direction::Up)
+        );
+    }
+
+    #[test]
+    fn nonzero_factor_matches_wider_type() {
+        for factor in 1..=u8::MAX {
+            let wide_factor = i16::from(factor);
+            for value in i8::MIN..=i8::MAX {
+                let wide = i16::from(value);
+                for tie in TIES {
+                    let expected = wide
+                        .checked_round_to(wide_factor, tie)
+                        .map(|rounded| i8::try_from(rounded).ok());
+                    check!(
+                        expected
+                            == Ok(value
+                                .try_round_to_nonzero_factor(u8(factor), tie)),
+                        "{} {} {:?}",
+                        value,
+                        factor,
+                        tie
+                    );
+                }
+                for direction in DIRECTIONS {
+                    let expected = wide
+                        .checked_round_directed_to(wide_factor, direction)
+                        .map(|rounded| i8::try_from(rounded).ok());
+                    check!(
+                        expected
+                            == Ok(value.try_round_directed_to_nonzero_factor(
+                                u8(factor),
+                                direction
+                            )),
+                        "{} {} {:?}",
+                        value,
+                        factor,
+                        direction
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nonzero_factor_larger_than_max() {
+        let factor = NonZeroU128::new(u128::MAX).unwrap();
+        check!(0 == i128::MAX.round_to_nonzero_factor(factor, Tie::Up));
+        check!(
+            Err(RoundError::Overflow)
+                == i128::MIN.checked_round_to_nonzero_factor(factor, Tie::Up)
+        );
+        check!(
+            Some(0)
+                == 1i128.try_round_directed_to_nonzero_factor(
+                    factor,
+                    Direction::Down
+                )
+        );
+        check!(
+            None == 1i128
+                .try_round_directed_to_nonzero_factor(factor, Direction::Up)
+        );
+        check!(
+            Err(RoundError::ZeroResult)
+                == NonZeroI128::new(1)
+                    .unwrap()
+                    .checked_round_to_nonzero_factor(factor, Tie::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow while rounding")]
+    fn panic_nonzero_factor_overflow() {
+        let _ = 255u8.round_to_nonzero_factor(u8(10), Tie::Up);
+    }
+
+    #[test]
+    fn round_all_nonzero_u8s_away_from_zero() {
+        // Rounding away from zero never produces zero.
+        for value in 1..=u8::MAX {
+            for factor in 1..=u8::MAX {
+                check!(
+                    Err(RoundError::ZeroResult)
+                        != u8(value).checked_round_directed_to(
+                            u8(factor),
+                            Direction::AwayFromZero
+                        )
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,659 @@
+//! Round to multiples of a factor counted from an origin other than zero.
+
+use crate::{
+    rounded_or_none, unwrap_rounded, Direction, RoundError, Roundable,
+    TieStrategy,
+};
+use core::cmp::Ordering;
+
+/// Round to the nearest value on a grid that doesn’t pass through zero.
+///
+/// This is implemented for integers, floats, and
+/// [`Duration`](core::time::Duration). [`Roundable::round_to()`] rounds to
+/// multiples of `factor`, i.e. to a grid of values `factor` apart that
+/// includes 0. These functions round to a grid that includes `origin` instead,
+/// i.e. to `origin + k × factor` for some integer k. The origin can be any
+/// value, including one larger than the value being rounded.
+///
+/// Ties are resolved just like [`Roundable::round_to()`]: [`Tie::TowardZero`]
+/// and [`Tie::AwayFromZero`] refer to the value itself, not its distance from
+/// the origin. [`Tie::TowardEven`] and [`Tie::TowardOdd`] count factors from
+/// the origin, so `origin` is even, `origin + factor` is odd, etc.
+///
+/// ```rust
+/// use roundable::{OriginRoundable, Tie, MINUTE};
+///
+/// // Every 10 starting at 3: …, -7, 3, 13, 23, …
+/// assert!(3 == 7.round_to_with_origin(10, 3, Tie::Up));
+/// assert!(13 == 8.round_to_with_origin(10, 3, Tie::Up));
+/// assert!(-7 == (-4).round_to_with_origin(10, 3, Tie::Up));
+///
+/// // Every 15 minutes starting at :05.
+/// let time = 17 * MINUTE;
+/// assert!(20 * MINUTE == time.round_to_with_origin(15 * MINUTE, 5 * MINUTE, Tie::Up));
+/// ```
+///
+/// [`Tie::TowardZero`]: crate::Tie::TowardZero
+/// [`Tie::AwayFromZero`]: crate::Tie::AwayFromZero
+/// [`Tie::TowardEven`]: crate::Tie::TowardEven
+/// [`Tie::TowardOdd`]: crate::Tie::TowardOdd
+pub trait OriginRoundable: Roundable {
+    /// Round to the nearest `origin + k × factor`. Panics if the result does
+    /// not fit in the type.
+    ///
+    /// ```rust
+    /// use roundable::{OriginRoundable, Tie};
+    ///
+    /// assert!(13 == 10.round_to_with_origin(10, 3, Tie::Up));
+    /// assert!(0.75 == 0.6.round_to_with_origin(0.5, 0.25, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0, if `origin` is not
+    /// finite, if the value can’t be rounded (e.g. it’s NaN), or if rounding
+    /// would return a value that does not fit in the return type.
+    #[must_use]
+    fn round_to_with_origin<S>(self, factor: Self, origin: Self, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            self.checked_round_to_with_origin(factor, origin, tie),
+            "round_to_with_origin",
+        )
+    }
+
+    /// Round to the nearest `origin + k × factor`. Returns `None` if the
+    /// result does not fit in the type.
+    ///
+    /// ```rust
+    /// use roundable::{OriginRoundable, Tie};
+    ///
+    /// // 250 is between 243 and 253, which fits.
+    /// assert!(Some(253) == 250u8.try_round_to_with_origin(10, 3, Tie::Up));
+    /// // 0 is closer to -3, which doesn’t fit, than to 7.
+    /// assert!(None == 0u8.try_round_to_with_origin(10, 7, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0, or if `origin` is
+    /// not finite, e.g. if it’s NaN.
+    #[must_use]
+    fn try_round_to_with_origin<S>(
+        self,
+        factor: Self,
+        origin: Self,
+        tie: S,
+    ) -> Option<Self>
+    where
+        S: TieStrategy,
+    {
+        rounded_or_none(
+            self.checked_round_to_with_origin(factor, origin, tie),
+            "try_round_to_with_origin",
+        )
+    }
+
+    /// Round to the nearest `origin + k × factor`. Returns a [`RoundError`] on
+    /// failure.
+    ///
+    /// ```rust
+    /// use roundable::{OriginRoundable, RoundError, Tie};
+    ///
+    /// assert!(Ok(-110) == (-115i8).checked_round_to_with_origin(20, 110, Tie::Up));
+    ///
+    /// // -125 is closer to -130, which doesn’t fit, than to -110.
+    /// assert!(
+    ///     Err(RoundError::Overflow)
+    ///         == (-125i8).checked_round_to_with_origin(20, 110, Tie::Up)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Roundable::checked_round_to()`]: usually
+    /// [`RoundError::NonPositiveFactor`] if `factor` is not positive, or
+    /// [`RoundError::Overflow`] if the result does not fit in the type. For
+    /// floats, an `origin` that is NaN or infinite fails with
+    /// [`RoundError::InvalidOrigin`].
+    fn checked_round_to_with_origin<S>(
+        self,
+        factor: Self,
+        origin: Self,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+}
+
+/// Round `value` to the nearest `origin + k × factor`.
+///
+/// The arguments are mapped to `u128` so that their order is preserved, and
+/// the difference between any two of them is the same as before. `max` is the
+/// largest mapped value that fits in the original type; results beyond either
+/// end of `0..=max` are overflows. `negative` is whether the original value was
+/// negative. `factor` must be positive.
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use origin::*`.
+pub(crate) fn round_u128_with_origin<S>(
+    value: u128,
+    factor: u128,
+    origin: u128,
+    max: u128,
+    negative: bool,
+    tie: S,
+) -> Result<u128, RoundError>
+where
+    S: TieStrategy,
+{
+    round_u128_on_grid(value, factor, origin, max, |ordering, is_lower_odd| {
+        match ordering {
+            Ordering::Less => false,
+            Ordering::Greater => true,
+            Ordering::Equal => {
+                // The multiple toward zero is above negative values.
+                let away = tie
+                    .is_away_from_zero(negative, || is_lower_odd != negative);
+                away != negative
+            }
+        }
+    })
+}
+
+/// Round `value` to `origin + k × factor` in `direction`.
+///
+/// The arguments are the same as for [`round_u128_with_origin()`].
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use origin::*`.
+pub(crate) fn round_u128_directed_with_origin(
+    value: u128,
+    factor: u128,
+    origin: u128,
+    max: u128,
+    negative: bool,
+    direction: Direction,
+) -> Result<u128, RoundError> {
+    round_u128_on_grid(value, factor, origin, max, |_, _| {
+        // Toward zero is up for negative values.
+        direction.is_toward_zero(negative) == negative
+    })
+}
+
+/// Round `value` to one of the two points of the grid `origin + k × factor`
+/// around it. Values on the grid are returned unchanged.
+///
+/// `up` chooses the point above `value`. It gets the distance to the point
+/// below compared to the distance to the point above, and whether the point
+/// below is an odd number of factors from `origin`.
+#[allow(clippy::arithmetic_side_effects, clippy::integer_division)]
+fn round_u128_on_grid<F>(
+    value: u128,
+    factor: u128,
+    origin: u128,
+    max: u128,
+    up: F,
+) -> Result<u128, RoundError>
+where
+    F: FnOnce(Ordering, bool) -> bool,
+{
+    // Find the distance from the grid point below value to value, and whether
+    // that grid point is an odd number of factors from origin.
+    //
+    // Safe: factor is positive, and the subtractions can’t underflow.
+    let (remainder, is_lower_odd) = if value >= origin {
+        let distance = value - origin;
+        (distance % factor, (distance / factor) & 1 == 1)
+    } else {
+        let distance = origin - value;
+        let quotient = distance / factor;
+        match distance % factor {
+            0 => (0, quotient & 1 == 1),
+            // The grid point below is one factor further from origin.
+            rest => (factor - rest, quotient & 1 == 0),
+        }
+    };
+
+    if remainder == 0 {
+        return Ok(value);
+    }
+
+    // Safe: 0 < remainder < factor.
+    let up_distance = factor - remainder;
+    if up(remainder.cmp(&up_distance), is_lower_odd) {
+        value
+            .checked_add(up_distance)
+            .filter(|&upper| upper <= max)
+            .ok_or(RoundError::Overflow)
+    } else {
+        value.checked_sub(remainder).ok_or(RoundError::Overflow)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::arithmetic_side_effects, clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::test_util::TIES;
+    use crate::Tie;
+    use assert2::check;
+    use core::time::Duration;
+
+    /// Round `value` to the nearest `origin + k × factor` the slow way, using
+    /// `i32` to avoid overflow.
+    fn expected(value: i32, factor: i32, origin: i32, tie: Tie) -> i32 {
+        let offset = value - origin;
+        let multiple = offset.div_euclid(factor);
+        let lower = multiple * factor;
+        let remainder = offset - lower;
+        let up = match (2 * remainder).cmp(&factor) {
+            Ordering::Less => false,
+            Ordering::Greater => true,
+            Ordering::Equal => {
+                let is_lower_odd = multiple % 2 != 0;
+                match tie {
+                    Tie::Up => true,
+                    Tie::Down => false,
+                    Tie::TowardZero => value < 0,
+                    Tie::AwayFromZero => value >= 0,
+                    Tie::TowardEven => is_lower_odd,
+                    Tie::TowardOdd => !is_lower_odd,
+                }
+            }
+        };
+        origin + lower + if up { factor } else { 0 }
+    }
+
+    /// Check that an 8 bit result matches `expected()`.
+    fn check_small<T>(result: Result<T, RoundError>, expected: i32, min: i32)
+    where
+        T: Into<i32> + Copy + core::fmt::Debug + PartialEq,
+    {
+        let fits = (min..=min + 255).contains(&expected);
+        if fits {
+            check!(result.map(Into::into) == Ok(expected));
+        } else {
+            check!(result == Err(RoundError::Overflow));
+        }
+    }
+
+    #[test]
+    fn exhaustive_u8() {
+        for tie in TIES {
+            for factor in [1u8, 2, 3, 10, 64, 100, 127, 128, 200, 255] {
+                for origin in 0..=u8::MAX {
+                    for value in 0..=u8::MAX {
+                        check_small(
+                            value.checked_round_to_with_origin(
+                                factor, origin, tie,
+                            ),
+                            expected(
+                                value.into(),
+                                factor.into(),
+                                origin.into(),
+                                tie,
+                            ),
+                            0,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exhaustive_i8() {
+        for tie in TIES {
+            for factor in [1i8, 2, 3, 10, 64, 100, 127] {
+                for origin in i8::MIN..=i8::MAX {
+                    for value in i8::MIN..=i8::MAX {
+                        check_small(
+                            value.checked_round_to_with_origin(
+                                factor, origin, tie,
+                            ),
+                            expected(
+                                value.into(),
+                                factor.into(),
+                                origin.into(),
+                                tie,
+                            ),
+                            -128,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn zero_origin_matches_round_to() {
+        for tie in TIES {
+            for factor in [1i8, 2, 3, 10, 64, 127] {
+                for value in i8::MIN..=i8::MAX {
+                    check!(
+                        value.checked_round_to_with_origin(factor, 0, tie)
+                            == value.checked_round_to(factor, tie)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_ten_starting_at_three() {
+        check!(3 == 7.round_to_with_origin(10, 3, Tie::Up));
+        check!(13 == 8.round_to_with_origin(10, 3, Tie::Up));
+        check!(3 == 8.round_to_with_origin(10, 3, Tie::Down));
+        check!(3 == 8.round_to_with_origin(10, 3, Tie::TowardZero));
+        check!(13 == 8.round_to_with_origin(10, 3, Tie::AwayFromZero));
+        check!(3 == 8.round_to_with_origin(10, 3, Tie::TowardEven));
+        check!(13 == 8.round_to_with_origin(10, 3, Tie::TowardOdd));
+
+        // Ties below zero.
+        check!(-7 == (-12).round_to_with_origin(10, 3, Tie::TowardZero));
+        check!(-17 == (-12).round_to_with_origin(10, 3, Tie::AwayFromZero));
+        check!(-7 == (-12).round_to_with_origin(10, 3, Tie::TowardOdd));
+        check!(-17 == (-12).round_to_with_origin(10, 3, Tie::TowardEven));
+
+        // A tie that straddles zero.
+        check!(3 == (-2).round_to_with_origin(10, 3, Tie::TowardZero));
+        check!(-7 == (-2).round_to_with_origin(10, 3, Tie::AwayFromZero));
+
+        // The grid is the same for every origin ≡ 3 (mod 10).
+        check!(23 == 24.round_to_with_origin(10, 1_003, Tie::Up));
+        check!(23 == 24.round_to_with_origin(10, -997, Tie::Up));
+    }
+
+    #[test]
+    fn large_origins() {
+        // Origin is larger than the value.
+        check!(Ok(5) == 7u8.checked_round_to_with_origin(10, 255, Tie::Up));
+        check!(
+            Ok(-127) == i8::MIN.checked_round_to_with_origin(2, 127, Tie::Up)
+        );
+        check!(
+            Ok(5) == 0u128.checked_round_to_with_origin(10, u128::MAX, Tie::Up)
+        );
+
+        // The distance from the origin doesn’t fit in the type.
+        check!(
+            Ok(i128::MIN + 5)
+                == i128::MIN.checked_round_to_with_origin(
+                    10,
+                    i128::MAX,
+                    Tie::Up
+                )
+        );
+        check!(
+            Ok(i128::MAX - 1)
+                == i128::MAX.checked_round_to_with_origin(
+                    2,
+                    i128::MIN,
+                    Tie::Down
+                )
+        );
+        check!(
+            Ok(i64::MAX)
+                == 9_000_000_000_000_000_000i64.checked_round_to_with_origin(
+                    i64::MAX,
+                    i64::MIN + 1,
+                    Tie::Up
+                )
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        // Below the minimum.
+        check!(
+            Err(RoundError::Overflow)
+                == 0u8.checked_round_to_with_origin(10, 7, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == i128::MIN.checked_round_to_with_origin(10, 9, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == u128::MIN.checked_round_to_with_origin(10, 5, Tie::Down)
+        );
+        check!(Ok(5) == u128::MIN.checked_round_to_with_origin(10, 5, Tie::Up));
+
+        // Above the maximum.
+        check!(
+            Err(RoundError::Overflow)
+                == 255u8.checked_round_to_with_origin(10, 7, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == i128::MAX.checked_round_to_with_origin(10, 9, Tie::Up)
+        );
+        check!(
+            Ok(u128::MAX - 4)
+                == u128::MAX.checked_round_to_with_origin(10, 1, Tie::Down)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == u128::MAX.checked_round_to_with_origin(10, 0, Tie::Up)
+        );
+
+        check!(None == 255u8.try_round_to_with_origin(10, 7, Tie::Up));
+        check!(Some(251) == 255u8.try_round_to_with_origin(10, 1, Tie::Down));
+    }
+
+    #[test]
+    fn floats() {
+        check!(13.0 == 8.0.round_to_with_origin(10.0, 3.0, Tie::Up));
+        check!(3.0 == 8.0.round_to_with_origin(10.0, 3.0, Tie::TowardEven));
+        check!(13.0 == 8.0.round_to_with_origin(10.0, 3.0, Tie::TowardOdd));
+        check!(-7.0 == (-2.0).round_to_with_origin(10.0, 3.0, Tie::Down));
+        check!(3.0 == (-2.0).round_to_with_origin(10.0, 3.0, Tie::TowardZero));
+        check!(
+            -7.0 == (-2.0).round_to_with_origin(10.0, 3.0, Tie::AwayFromZero)
+        );
+        check!(
+            -17.0 == (-12.0).round_to_with_origin(10.0, 3.0, Tie::TowardEven)
+        );
+        check!(-7.0 == (-12.0).round_to_with_origin(10.0, 3.0, Tie::TowardOdd));
+        check!(0.75 == 0.6f32.round_to_with_origin(0.5, 0.25, Tie::Up));
+
+        for tie in TIES {
+            for factor in [1, 2, 3, 4, 10] {
+                for origin in -25..=25 {
+                    for value in -50..=50 {
+                        check!(
+                            f64::from(expected(value, factor, origin, tie))
+                                == f64::from(value).round_to_with_origin(
+                                    f64::from(factor),
+                                    f64::from(origin),
+                                    tie
+                                )
+                        );
+                    }
+                }
+            }
+        }
+
+        // Large origins are reduced first, so parity is preserved.
+        for tie in TIES {
+            for value in -30..=30 {
+                let value = f64::from(value);
+                check!(
+                    value.round_to_with_origin(4.0, 1.0, tie)
+                        == value.round_to_with_origin(4.0, 801.0, tie),
+                    "{} {:?}",
+                    value,
+                    tie
+                );
+                check!(
+                    value.round_to_with_origin(4.0, 1.0, tie)
+                        == value.round_to_with_origin(4.0, -799.0, tie),
+                    "{} {:?}",
+                    value,
+                    tie
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn float_distance_is_exact() {
+        // None of these are ties, even though the distance from the origin
+        // rounds to exactly 0.5.
+        for tie in TIES {
+            // 0.6 - 0.1 is 0.5 - 2^-55.
+            check!(0.1 == 0.6.round_to_with_origin(1.0, 0.1, tie));
+            check!(-0.1 == (-0.6).round_to_with_origin(1.0, -0.1, tie));
+            // 0.1 + 0.4 is 0.5 + 2^-55.
+            check!(0.6 == 0.1.round_to_with_origin(1.0, -0.4, tie));
+            check!(-0.6 == (-0.1).round_to_with_origin(1.0, 0.4, tie));
+            // 0.6 - 0.1 is 0.5 + 3 × 2^-27 in f32.
+            check!(1.1 == 0.6f32.round_to_with_origin(1.0, 0.1, tie));
+        }
+        check!(Ok(0.1) == 0.6.checked_round_to_with_origin(1.0, 0.1, Tie::Up));
+    }
+
+    #[test]
+    fn float_errors() {
+        check!(
+            Err(RoundError::InvalidOrigin)
+                == 1.0.checked_round_to_with_origin(1.0, f64::NAN, Tie::Up)
+        );
+        check!(
+            Err(RoundError::InvalidOrigin)
+                == 1.0.checked_round_to_with_origin(
+                    1.0,
+                    f64::INFINITY,
+                    Tie::Up
+                )
+        );
+        check!(
+            Err(RoundError::InvalidOrigin)
+                == f32::NAN.checked_round_to_with_origin(
+                    1.0,
+                    f32::NEG_INFINITY,
+                    Tie::Up
+                )
+        );
+        check!(
+            Err(RoundError::NanInput)
+                == f32::NAN.checked_round_to_with_origin(1.0, 0.5, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 1.0.checked_round_to_with_origin(-1.0, 0.5, Tie::Up)
+        );
+        // With origin 2^1020, the grid points nearest to MAX and -MAX are
+        // 4.25 × 2^1022, which overflows, and -3.75 × 2^1022.
+        let factor = 1.0 / f64::MIN_POSITIVE;
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f64::MAX.checked_round_to_with_origin(
+                    factor,
+                    factor / 4.0,
+                    Tie::Up
+                )
+        );
+        check!(
+            Ok(factor * -3.75)
+                == (-f64::MAX).checked_round_to_with_origin(
+                    factor,
+                    factor / 4.0,
+                    Tie::Up
+                )
+        );
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == (-f64::MAX).checked_round_to_with_origin(
+                    factor,
+                    -factor / 4.0,
+                    Tie::Up
+                )
+        );
+
+        // MAX + 2^1020 overflows, but the result fits.
+        check!(
+            Ok(factor * 3.75)
+                == f64::MAX.checked_round_to_with_origin(
+                    factor,
+                    -factor / 4.0,
+                    Tie::Up
+                )
+        );
+    }
+
+    #[test]
+    fn durations() {
+        const MINUTE: Duration = Duration::from_secs(60);
+
+        check!(
+            20 * MINUTE
+                == (17 * MINUTE).round_to_with_origin(
+                    15 * MINUTE,
+                    5 * MINUTE,
+                    Tie::Up
+                )
+        );
+        check!(
+            5 * MINUTE
+                == (12 * MINUTE + MINUTE / 2).round_to_with_origin(
+                    15 * MINUTE,
+                    5 * MINUTE,
+                    Tie::Down
+                )
+        );
+        check!(
+            5 * MINUTE
+                == Duration::ZERO.round_to_with_origin(
+                    15 * MINUTE,
+                    65 * MINUTE,
+                    Tie::Up
+                )
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == Duration::ZERO.checked_round_to_with_origin(
+                    15 * MINUTE,
+                    10 * MINUTE,
+                    Tie::Up
+                )
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == Duration::MAX.checked_round_to_with_origin(
+                    MINUTE,
+                    MINUTE / 2,
+                    Tie::Up
+                )
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == MINUTE.checked_round_to_with_origin(
+                    Duration::ZERO,
+                    MINUTE,
+                    Tie::Up
+                )
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "round_to_with_origin() requires positive factor"
+    )]
+    fn panic_factor_zero() {
+        let _ = 1.round_to_with_origin(0, 3, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "try_round_to_with_origin() requires finite origin"
+    )]
+    fn panic_origin_nan() {
+        let _ = 1.0.try_round_to_with_origin(1.0, f64::NAN, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow while rounding")]
+    fn panic_overflow() {
+        let _ = 0u8.round_to_with_origin(10, 7, Tie::Up);
+    }
+}
@@ -0,0 +1,768 @@
+//! Round numbers to powers of two or ten.
+
+use crate::scientific::{Buffer, Scientific};
+use crate::{rounded_or_none, unwrap_rounded, RoundError, TieStrategy};
+use core::cmp::Ordering;
+use core::fmt::Write;
+
+/// Which point between two powers separates the values that round down from
+/// the values that round up.
+///
+/// ```rust
+/// use roundable::{Midpoint, PowerRoundable, Tie};
+///
+/// // 48 is exactly halfway between 32 and 64, so it’s a tie.
+/// assert!(64 == 48.round_to_power_of_two(Midpoint::Arithmetic, Tie::Up));
+/// assert!(32 == 48.round_to_power_of_two(Midpoint::Arithmetic, Tie::Down));
+///
+/// // 48 is closer to 64 than 32 is on a log scale.
+/// assert!(64 == 48.round_to_power_of_two(Midpoint::Geometric, Tie::Down));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Midpoint {
+    /// Round to the power that is closest by subtraction, i.e. the midpoint
+    /// between 32 and 64 is 48.
+    Arithmetic,
+
+    /// Round to the power that is closest by ratio, i.e. the midpoint between
+    /// 32 and 64 is √(32 × 64) ≈ 45.25. This is the midpoint on a log scale.
+    Geometric,
+}
+
+/// Methods to round to the nearest power of two or ten.
+///
+/// This is implemented for integers and floats. Negative values are rounded to
+/// the nearest negative power, e.g. -48 might be rounded to -64, and zero is
+/// returned unchanged.
+///
+/// Ties are handled according to `tie` just like
+/// [`Roundable::round_to()`](crate::Roundable::round_to()).
+/// [`Tie::TowardEven`](crate::Tie::TowardEven) and
+/// [`Tie::TowardOdd`](crate::Tie::TowardOdd) refer to the exponent of the
+/// power, so 1 (2⁰) and 4 (2²) are even, and 2 (2¹) and 8 (2³) are odd.
+///
+/// ```rust
+/// use roundable::{Midpoint, PowerRoundable, Tie};
+///
+/// assert!(2048 == 3000.round_to_power_of_two(Midpoint::Arithmetic, Tie::Up));
+/// assert!(1000 == 3000.round_to_power_of_ten(Midpoint::Arithmetic, Tie::Up));
+/// assert!(10_000 == 3200.round_to_power_of_ten(Midpoint::Geometric, Tie::Up));
+/// assert!(0.001 == 0.0042.round_to_power_of_ten(Midpoint::Arithmetic, Tie::Up));
+/// ```
+pub trait PowerRoundable: Sized {
+    /// Round to the nearest power of two. Panics if there is an overflow.
+    ///
+    /// ```rust
+    /// use roundable::{Midpoint, PowerRoundable, Tie};
+    ///
+    /// assert!(128 == 100u8.round_to_power_of_two(Midpoint::Arithmetic, Tie::Up));
+    /// assert!(0.25 == 0.3.round_to_power_of_two(Midpoint::Arithmetic, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in the return type, or if the value
+    /// can’t be rounded (see
+    /// [`PowerRoundable::checked_round_to_power_of_two()`]).
+    #[must_use]
+    fn round_to_power_of_two<S>(self, midpoint: Midpoint, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            self.checked_round_to_power_of_two(midpoint, tie),
+            "try_round_to_power_of_two",
+        )
+    }
+
+    /// Round to the nearest power of two. Returns `None` if there is an
+    /// overflow.
+    ///
+    /// ```rust
+    /// use roundable::{Midpoint, PowerRoundable, Tie};
+    ///
+    /// assert!(Some(128) == 150u8.try_round_to_power_of_two(Midpoint::Arithmetic, Tie::Up));
+    /// assert!(None == 200u8.try_round_to_power_of_two(Midpoint::Arithmetic, Tie::Up));
+    /// ```
+    #[must_use]
+    fn try_round_to_power_of_two<S>(
+        self,
+        midpoint: Midpoint,
+        tie: S,
+    ) -> Option<Self>
+    where
+        S: TieStrategy,
+    {
+        rounded_or_none(
+            self.checked_round_to_power_of_two(midpoint, tie),
+            "try_round_to_power_of_two",
+        )
+    }
+
+    /// Round to the nearest power of two. Returns a [`RoundError`] on failure.
+    ///
+    /// ```rust
+    /// use roundable::{Midpoint, PowerRoundable, RoundError, Tie};
+    ///
+    /// assert!(
+    ///     Err(RoundError::Overflow)
+    ///         == 200u8.checked_round_to_power_of_two(Midpoint::Arithmetic, Tie::Up)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::Overflow`] if the result does not fit in the
+    /// return type. Floats can also return [`RoundError::NanInput`],
+    /// [`RoundError::InfiniteInput`], or [`RoundError::NonFiniteResult`].
+    fn checked_round_to_power_of_two<S>(
+        self,
+        midpoint: Midpoint,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+
+    /// Round to the nearest power of ten. Panics if there is an overflow.
+    ///
+    /// Floats are compared to the midpoint using their shortest decimal
+    /// representation, like
+    /// [`DecimalRoundable::round_to_decimal_places()`](crate::DecimalRoundable::round_to_decimal_places()).
+    ///
+    /// ```rust
+    /// use roundable::{Midpoint, PowerRoundable, Tie};
+    ///
+    /// assert!(100 == 55u8.round_to_power_of_ten(Midpoint::Arithmetic, Tie::Up));
+    /// assert!(10 == 55u8.round_to_power_of_ten(Midpoint::Arithmetic, Tie::Down));
+    /// assert!(1.0 == 5.5.round_to_power_of_ten(Midpoint::Arithmetic, Tie::Down));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in the return type, or if the value
+    /// can’t be rounded (see
+    /// [`PowerRoundable::checked_round_to_power_of_ten()`]).
+    #[must_use]
+    fn round_to_power_of_ten<S>(self, midpoint: Midpoint, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            self.checked_round_to_power_of_ten(midpoint, tie),
+            "try_round_to_power_of_ten",
+        )
+    }
+
+    /// Round to the nearest power of ten. Returns `None` if there is an
+    /// overflow.
+    ///
+    /// ```rust
+    /// use roundable::{Midpoint, PowerRoundable, Tie};
+    ///
+    /// assert!(Some(10) == 54u8.try_round_to_power_of_ten(Midpoint::Arithmetic, Tie::Up));
+    /// assert!(None == 40_000u16.try_round_to_power_of_ten(Midpoint::Geometric, Tie::Up));
+    /// ```
+    #[must_use]
+    fn try_round_to_power_of_ten<S>(
+        self,
+        midpoint: Midpoint,
+        tie: S,
+    ) -> Option<Self>
+    where
+        S: TieStrategy,
+    {
+        rounded_or_none(
+            self.checked_round_to_power_of_ten(midpoint, tie),
+            "try_round_to_power_of_ten",
+        )
+    }
+
+    /// Round to the nearest power of ten. Returns a [`RoundError`] on failure.
+    ///
+    /// ```rust
+    /// use roundable::{Midpoint, PowerRoundable, RoundError, Tie};
+    ///
+    /// assert!(
+    ///     Err(RoundError::NanInput)
+    ///         == f64::NAN.checked_round_to_power_of_ten(Midpoint::Arithmetic, Tie::Up)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`PowerRoundable::checked_round_to_power_of_two()`].
+    fn checked_round_to_power_of_ten<S>(
+        self,
+        midpoint: Midpoint,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+}
+
+/// Implement power rounding for integer types.
+macro_rules! power_roundable_integer {
+    ($($ty:ident)+) => {$(
+        impl PowerRoundable for $ty {
+            fn checked_round_to_power_of_two<S>(
+                self,
+                midpoint: Midpoint,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                round_integer(self, 2, midpoint, tie)
+            }
+
+            fn checked_round_to_power_of_ten<S>(
+                self,
+                midpoint: Midpoint,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                round_integer(self, 10, midpoint, tie)
+            }
+        }
+    )+}
+}
+
+power_roundable_integer!(u8 u16 u32 u64 u128 usize);
+power_roundable_integer!(i8 i16 i32 i64 i128 isize);
+
+/// Round an integer to the nearest power of `base`.
+fn round_integer<T, S>(
+    value: T,
+    base: u128,
+    midpoint: Midpoint,
+    tie: S,
+) -> Result<T, RoundError>
+where
+    T: Copy + TryFrom<u128> + TryFrom<i128> + TryInto<u128> + TryInto<i128>,
+    S: TieStrategy,
+{
+    let (magnitude, negative) = match value.try_into() {
+        Ok(magnitude) => (magnitude, false),
+        Err(_) => match value.try_into() {
+            Ok(signed) => (i128::unsigned_abs(signed), true),
+            Err(_) => return Err(RoundError::Overflow),
+        },
+    };
+
+    if magnitude == 0 {
+        return Ok(value);
+    }
+
+    let rounded = round_magnitude(magnitude, base, midpoint, tie, negative)
+        .ok_or(RoundError::Overflow)?;
+
+    if negative {
+        // -rounded might be i128::MIN, so we can’t convert rounded directly.
+        #[allow(clippy::arithmetic_side_effects)] // rounded is at least 1.
+        let one_less = rounded - 1;
+        i128::try_from(one_less)
+            .ok()
+            .and_then(i128::checked_neg)
+            .and_then(|value| value.checked_sub(1))
+            .and_then(|value| T::try_from(value).ok())
+            .ok_or(RoundError::Overflow)
+    } else {
+        T::try_from(rounded).map_err(|_| RoundError::Overflow)
+    }
+}
+
+/// Round a positive integer to the nearest power of `base`.
+///
+/// `negative` is whether the original value was negative. Returns `None` if the
+/// result doesn’t fit in a `u128`.
+#[allow(clippy::arithmetic_side_effects)]
+fn round_magnitude<S>(
+    magnitude: u128,
+    base: u128,
+    midpoint: Midpoint,
+    tie: S,
+    negative: bool,
+) -> Option<u128>
+where
+    S: TieStrategy,
+{
+    // Find the largest power of base that is no greater than magnitude.
+    let (lower, exponent) = if base == 2 {
+        let exponent = 127 - magnitude.leading_zeros();
+        (1 << exponent, exponent)
+    } else {
+        let mut lower: u128 = 1;
+        let mut exponent = 0;
+        while let Some(next) =
+            lower.checked_mul(base).filter(|&next| next <= magnitude)
+        {
+            lower = next;
+            exponent += 1;
+        }
+        (lower, exponent)
+    };
+
+    if lower == magnitude {
+        return Some(lower);
+    }
+
+    // Compare exactly in 256 bits to avoid overflow.
+    let ordering = match midpoint {
+        // 2 × magnitude vs. lower + upper
+        Midpoint::Arithmetic => (magnitude >> 127, magnitude << 1)
+            .cmp(&multiply_wide(lower, base + 1)),
+        // magnitude² vs. lower × upper
+        Midpoint::Geometric => {
+            let (high, low) = multiply_wide(lower, lower);
+            let (carry, low) = multiply_wide(low, base);
+            match high
+                .checked_mul(base)
+                .and_then(|high| high.checked_add(carry))
+            {
+                Some(high) => {
+                    multiply_wide(magnitude, magnitude).cmp(&(high, low))
+                }
+                None => Ordering::Less,
+            }
+        }
+    };
+
+    let away = match ordering {
+        Ordering::Less => false,
+        Ordering::Greater => true,
+        Ordering::Equal => {
+            tie.is_away_from_zero(negative, || exponent % 2 == 1)
+        }
+    };
+
+    if away {
+        lower.checked_mul(base)
+    } else {
+        Some(lower)
+    }
+}
+
+/// Multiply two `u128`s into a 256 bit `(high, low)` pair.
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use power::*`.
+pub(crate) const fn multiply_wide(a: u128, b: u128) -> (u128, u128) {
+    /// The low 64 bits.
+    const MASK: u128 = 0xffff_ffff_ffff_ffff;
+
+    let (a_high, a_low) = (a >> 64, a & MASK);
+    let (b_high, b_low) = (b >> 64, b & MASK);
+
+    // None of these can overflow.
+    let low_low = a_low * b_low;
+    let high_low = a_high * b_low;
+    let low_high = a_low * b_high;
+    let high_high = a_high * b_high;
+
+    let middle = (low_low >> 64) + (high_low & MASK) + (low_high & MASK);
+    (
+        high_high + (high_low >> 64) + (low_high >> 64) + (middle >> 64),
+        (middle << 64) | (low_low & MASK),
+    )
+}
+
+/// Implement power rounding for float types.
+///
+/// `$bits` is the unsigned integer type with the same size as the float.
+macro_rules! power_roundable_float {
+    ($($ty:ident $bits:ident)+) => {$(
+        impl PowerRoundable for $ty {
+            #[allow(
+                clippy::arithmetic_side_effects,
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_possible_wrap,
+            )]
+            fn checked_round_to_power_of_two<S>(
+                self,
+                midpoint: Midpoint,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                /// The number of explicit bits in the significand.
+                const FRACTION_BITS: u32 = $ty::MANTISSA_DIGITS - 1;
+
+                /// The leading bit of a normalized significand.
+                const ONE: $bits = 1 << FRACTION_BITS;
+
+                if self.is_nan() {
+                    return Err(RoundError::NanInput);
+                } else if self.is_infinite() {
+                    return Err(RoundError::InfiniteInput);
+                } else if self == 0.0 {
+                    return Ok(self);
+                }
+
+                // Clear the sign bit.
+                let bits = self.to_bits() & !(1 << ($bits::BITS - 1));
+                let biased_exponent = bits >> FRACTION_BITS;
+
+                // Find the power of two at or below the magnitude, and the
+                // significand normalized to have its leading bit at ONE.
+                let (lower, significand, exponent) = if biased_exponent == 0 {
+                    // Subnormal.
+                    let top = $bits::BITS - 1 - bits.leading_zeros();
+                    (
+                        $ty::from_bits(1 << top),
+                        bits << (FRACTION_BITS - top),
+                        top as i32 + $ty::MIN_EXP - $ty::MANTISSA_DIGITS as i32,
+                    )
+                } else {
+                    (
+                        $ty::from_bits(biased_exponent << FRACTION_BITS),
+                        bits & (ONE - 1) | ONE,
+                        biased_exponent as i32 - ($ty::MAX_EXP - 1),
+                    )
+                };
+
+                if significand == ONE {
+                    return Ok(self);
+                }
+
+                // Compare significand / ONE to the midpoint between 1 and 2.
+                let significand = u128::from(significand);
+                let ordering = match midpoint {
+                    // significand vs. 1.5
+                    Midpoint::Arithmetic => {
+                        (significand * 2).cmp(&(3 << FRACTION_BITS))
+                    }
+                    // significand² vs. 2
+                    Midpoint::Geometric => (significand * significand)
+                        .cmp(&(1 << (2 * FRACTION_BITS + 1))),
+                };
+
+                let negative = self.is_sign_negative();
+                let away = match ordering {
+                    Ordering::Less => false,
+                    Ordering::Greater => true,
+                    Ordering::Equal => {
+                        tie.is_away_from_zero(negative, || exponent % 2 != 0)
+                    }
+                };
+
+                let rounded = if away { lower * 2.0 } else { lower };
+                let rounded = if negative { -rounded } else { rounded };
+                if rounded.is_finite() {
+                    Ok(rounded)
+                } else {
+                    Err(RoundError::NonFiniteResult)
+                }
+            }
+
+            fn checked_round_to_power_of_ten<S>(
+                self,
+                midpoint: Midpoint,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if self.is_nan() {
+                    return Err(RoundError::NanInput);
+                } else if self.is_infinite() {
+                    return Err(RoundError::InfiniteInput);
+                } else if self == 0.0 {
+                    return Ok(self);
+                }
+
+                let scientific =
+                    Scientific::new(self).ok_or(RoundError::NonFiniteResult)?;
+                match round_scientific(scientific, midpoint, tie) {
+                    None => Ok(self),
+                    Some(rounded) => rounded
+                        .as_str()
+                        .parse::<$ty>()
+                        .ok()
+                        .filter(|value| value.is_finite())
+                        .ok_or(RoundError::NonFiniteResult),
+                }
+            }
+        }
+    )+}
+}
+
+power_roundable_float!(f32 u32 f64 u64);
+
+/// The digits of √10, i.e. the geometric midpoint between 1 and 10.
+///
+/// This has more digits than any float mantissa, and √10 is irrational, so
+/// comparing a mantissa to this is never equal.
+const SQRT_10_DIGITS: &[u8] = b"316227766016837933199889354443271853372";
+
+/// Round a non-zero number in scientific notation to the nearest power of ten.
+///
+/// Returns `None` if it’s already a power of ten, or a buffer containing the
+/// rounded number in scientific notation.
+fn round_scientific<S>(
+    scientific: Scientific,
+    midpoint: Midpoint,
+    tie: S,
+) -> Option<Buffer>
+where
+    S: TieStrategy,
+{
+    let digits = scientific.digits();
+    if digits == b"1" {
+        return None;
+    }
+
+    let ordering = compare_digits(
+        digits,
+        match midpoint {
+            Midpoint::Arithmetic => b"55",
+            Midpoint::Geometric => SQRT_10_DIGITS,
+        },
+    );
+
+    let away = match ordering {
+        Ordering::Less => false,
+        Ordering::Greater => true,
+        Ordering::Equal => tie.is_away_from_zero(scientific.negative, || {
+            scientific.exponent % 2 != 0
+        }),
+    };
+
+    let exponent = if away {
+        scientific.exponent.saturating_add(1)
+    } else {
+        scientific.exponent
+    };
+
+    let mut rounded = Buffer::new();
+    let sign = if scientific.negative { "-" } else { "" };
+    write!(rounded, "{}1e{}", sign, exponent).ok()?;
+    Some(rounded)
+}
+
+/// Compare two mantissas given as strings of digits.
+///
+/// Missing digits are treated as trailing zeros.
+fn compare_digits(a: &[u8], b: &[u8]) -> Ordering {
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| {
+            let a = a.get(i).copied().unwrap_or(b'0');
+            let b = b.get(i).copied().unwrap_or(b'0');
+            a.cmp(&b)
+        })
+        .find(|&ordering| ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+#[allow(clippy::arithmetic_side_effects, clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::Tie;
+    use assert2::check;
+
+    const ARITHMETIC: Midpoint = Midpoint::Arithmetic;
+    const GEOMETRIC: Midpoint = Midpoint::Geometric;
+
+    #[test]
+    fn multiply_wide_matches_u128() {
+        check!((0, 6) == multiply_wide(2, 3));
+        check!((0, u128::MAX) == multiply_wide(u128::MAX, 1));
+        check!((1, 0) == multiply_wide(1 << 64, 1 << 64));
+        check!((u128::MAX - 1, 1) == multiply_wide(u128::MAX, u128::MAX));
+    }
+
+    #[test]
+    fn integer_to_power_of_two() {
+        check!(0 == 0.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(1 == 1.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(2 == 2.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(4 == 3.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(2 == 3.round_to_power_of_two(ARITHMETIC, Tie::Down));
+        check!(32 == 47.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(64 == 49.round_to_power_of_two(ARITHMETIC, Tie::Down));
+        check!(1024 == 1000.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(-64 == (-49).round_to_power_of_two(ARITHMETIC, Tie::Up));
+    }
+
+    #[test]
+    fn integer_to_power_of_two_ties() {
+        check!(64 == 48.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(32 == 48.round_to_power_of_two(ARITHMETIC, Tie::Down));
+        check!(32 == 48.round_to_power_of_two(ARITHMETIC, Tie::TowardZero));
+        check!(64 == 48.round_to_power_of_two(ARITHMETIC, Tie::AwayFromZero));
+        check!(64 == 48.round_to_power_of_two(ARITHMETIC, Tie::TowardEven));
+        check!(32 == 48.round_to_power_of_two(ARITHMETIC, Tie::TowardOdd));
+        check!(4 == 6.round_to_power_of_two(ARITHMETIC, Tie::TowardEven));
+        check!(8 == 6.round_to_power_of_two(ARITHMETIC, Tie::TowardOdd));
+        check!(-32 == (-48).round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(-64 == (-48).round_to_power_of_two(ARITHMETIC, Tie::Down));
+    }
+
+    #[test]
+    fn integer_to_power_of_two_geometric() {
+        // √(32 × 64) ≈ 45.25
+        check!(32 == 45.round_to_power_of_two(GEOMETRIC, Tie::Up));
+        check!(64 == 46.round_to_power_of_two(GEOMETRIC, Tie::Down));
+        check!(64 == 48.round_to_power_of_two(GEOMETRIC, Tie::Down));
+        check!(-32 == (-45).round_to_power_of_two(GEOMETRIC, Tie::Down));
+    }
+
+    #[test]
+    fn integer_to_power_of_ten() {
+        check!(1 == 1.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(1 == 5.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(10 == 6.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(100 == 55.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(10 == 55.round_to_power_of_ten(ARITHMETIC, Tie::Down));
+        check!(100 == 55.round_to_power_of_ten(ARITHMETIC, Tie::TowardEven));
+        check!(10 == 55.round_to_power_of_ten(ARITHMETIC, Tie::TowardOdd));
+        check!(1000 == 3000.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(10_000 == 3200.round_to_power_of_ten(GEOMETRIC, Tie::Up));
+        check!(1000 == 3162.round_to_power_of_ten(GEOMETRIC, Tie::Up));
+        check!(10_000 == 3163.round_to_power_of_ten(GEOMETRIC, Tie::Up));
+        check!(-100 == (-60).round_to_power_of_ten(ARITHMETIC, Tie::Up));
+    }
+
+    #[test]
+    fn integer_to_power_geometric_ties() {
+        // Neither midpoint can be an integer for powers of two or ten, except
+        // between 1 and 2 or 1 and 10, where there are no integers between.
+        for value in 1..=1000_u32 {
+            let two = value.round_to_power_of_two(GEOMETRIC, Tie::Up);
+            check!(two == value.round_to_power_of_two(GEOMETRIC, Tie::Down));
+            let ten = value.round_to_power_of_ten(GEOMETRIC, Tie::Up);
+            check!(ten == value.round_to_power_of_ten(GEOMETRIC, Tie::Down));
+        }
+    }
+
+    #[test]
+    fn integer_to_power_overflow() {
+        check!(128 == 150u8.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(None == 200u8.try_round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(100 == 255u8.round_to_power_of_ten(GEOMETRIC, Tie::Up));
+        check!(10_000 == 40_000u16.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(None == 40_000u16.try_round_to_power_of_ten(GEOMETRIC, Tie::Up));
+        check!(i8::MIN == i8::MIN.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(
+            Err(RoundError::Overflow)
+                == i8::MAX.checked_round_to_power_of_two(ARITHMETIC, Tie::Up)
+        );
+        check!(
+            i128::MIN
+                == (i128::MIN + 1).round_to_power_of_two(ARITHMETIC, Tie::Up)
+        );
+        check!(
+            None == u128::MAX.try_round_to_power_of_two(ARITHMETIC, Tie::Up)
+        );
+        check!(
+            10_u128.pow(38)
+                == u128::MAX.round_to_power_of_ten(ARITHMETIC, Tie::Up)
+        );
+        check!(None == u128::MAX.try_round_to_power_of_ten(GEOMETRIC, Tie::Up));
+    }
+
+    #[test]
+    fn float_to_power_of_two() {
+        check!(0.0 == 0.0_f64.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(0.25 == 0.3_f64.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(0.5 == 0.375_f64.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(0.25 == 0.375_f64.round_to_power_of_two(ARITHMETIC, Tie::Down));
+        check!(
+            0.25 == 0.375_f64
+                .round_to_power_of_two(ARITHMETIC, Tie::TowardEven)
+        );
+        check!(
+            0.5 == 0.375_f64.round_to_power_of_two(ARITHMETIC, Tie::TowardOdd)
+        );
+        check!(64.0 == 48.0_f32.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(-32.0 == (-48.0_f32).round_to_power_of_two(ARITHMETIC, Tie::Up));
+        // SQRT_2 is slightly larger than √2.
+        check!(
+            2.0 == core::f64::consts::SQRT_2
+                .round_to_power_of_two(GEOMETRIC, Tie::Down)
+        );
+        // f32’s SQRT_2 is slightly smaller than √2.
+        let sqrt_2 = core::f32::consts::SQRT_2;
+        check!(1.0 == sqrt_2.round_to_power_of_two(GEOMETRIC, Tie::Up));
+        check!(
+            2.0 == f32::from_bits(sqrt_2.to_bits() + 1)
+                .round_to_power_of_two(GEOMETRIC, Tie::Down)
+        );
+    }
+
+    #[test]
+    fn float_to_power_of_two_extremes() {
+        let tiny = f64::from_bits(1);
+        check!(tiny == tiny.round_to_power_of_two(ARITHMETIC, Tie::Up));
+        check!(
+            tiny * 4.0
+                == f64::from_bits(3).round_to_power_of_two(ARITHMETIC, Tie::Up)
+        );
+        check!(
+            tiny * 2.0
+                == f64::from_bits(3)
+                    .round_to_power_of_two(ARITHMETIC, Tie::TowardOdd)
+        );
+        check!(
+            f64::MIN_POSITIVE
+                == (f64::MIN_POSITIVE * 1.2)
+                    .round_to_power_of_two(ARITHMETIC, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f64::MAX.checked_round_to_power_of_two(ARITHMETIC, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NanInput)
+                == f32::NAN.checked_round_to_power_of_two(ARITHMETIC, Tie::Up)
+        );
+        check!(
+            Err(RoundError::InfiniteInput)
+                == f64::INFINITY
+                    .checked_round_to_power_of_two(GEOMETRIC, Tie::Up)
+        );
+    }
+
+    #[test]
+    fn float_to_power_of_ten() {
+        check!(0.0 == 0.0_f64.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(1.0 == 1.0_f64.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(0.001 == 0.0042_f64.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(0.1 == 0.055_f64.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(0.01 == 0.055_f64.round_to_power_of_ten(ARITHMETIC, Tie::Down));
+        check!(
+            0.01 == 0.055_f64
+                .round_to_power_of_ten(ARITHMETIC, Tie::TowardEven)
+        );
+        check!(1e300 == 5e300_f64.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(
+            -1e301 == (-6e300_f64).round_to_power_of_ten(ARITHMETIC, Tie::Up)
+        );
+        check!(10.0 == 3.17_f64.round_to_power_of_ten(GEOMETRIC, Tie::Up));
+        check!(1.0 == 3.16_f64.round_to_power_of_ten(GEOMETRIC, Tie::Up));
+        check!(
+            1.0 == 3.162_277_660_168_379_f64
+                .round_to_power_of_ten(GEOMETRIC, Tie::Up)
+        );
+        check!(1e-320 == 2e-320_f64.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(1e30 == 2e30_f32.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+    }
+
+    #[test]
+    fn float_to_power_of_ten_extremes() {
+        check!(1e308 == f64::MAX.round_to_power_of_ten(ARITHMETIC, Tie::Up));
+        check!(1e308 == f64::MAX.round_to_power_of_ten(GEOMETRIC, Tie::Up));
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f32::MAX.checked_round_to_power_of_ten(GEOMETRIC, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NanInput)
+                == f64::NAN.checked_round_to_power_of_ten(GEOMETRIC, Tie::Up)
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! Round many values to the same factor.
+
+use crate::divisor::{Divisor, RoundWithDivisor};
+use crate::{unwrap_rounded, Direction, RoundError, Roundable, TieStrategy};
+
+/// Round many values to the same factor quickly.
+///
+/// Rounding with [`Roundable::try_round_to()`] divides by the factor, which is
+/// slow. A `Rounder` checks the factor once and precomputes a multiplier that
+/// can be used in place of division, like [libdivide](https://libdivide.com).
+/// Otherwise, rounding works exactly like the [`Roundable`] functions.
+///
+/// This is implemented for integers and [`Duration`](core::time::Duration).
+///
+/// ```rust
+/// use roundable::{Rounder, Tie};
+///
+/// let rounder = Rounder::new(1_000u64).unwrap();
+/// assert!(2_000 == rounder.round(1_500, Tie::Up));
+/// assert!(Some(3_000) == rounder.try_round(3_456, Tie::Up));
+/// assert!(None == rounder.try_round(u64::MAX, Tie::Up));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Rounder<T> {
+    /// The factor to round to. Always positive.
+    factor: T,
+
+    /// The precomputed divisor for `factor`.
+    divisor: Divisor,
+}
+
+impl<T: Precomputable> Rounder<T> {
+    /// Create a `Rounder` that rounds to `factor`.
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, Rounder, SECOND};
+    /// use std::time::Duration;
+    ///
+    /// assert!(Rounder::new(SECOND).is_ok());
+    /// assert!(Rounder::new(3i32).is_ok());
+    /// assert!(Err(RoundError::NonPositiveFactor) == Rounder::new(0i32).map(|r| r.factor()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::NonPositiveFactor`] if `factor` is not positive.
+    pub fn new(factor: T) -> Result<Self, RoundError> {
+        Ok(Self { factor, divisor: T::divisor(factor)? })
+    }
+
+    /// Get the factor this rounds to.
+    ///
+    /// ```rust
+    /// use roundable::Rounder;
+    ///
+    /// assert!(10 == Rounder::new(10).unwrap().factor());
+    /// ```
+    #[must_use]
+    pub fn factor(&self) -> T {
+        self.factor
+    }
+
+    /// Round `value` to the nearest factor. Panics if there is an overflow.
+    ///
+    /// This is the same as [`Roundable::round_to()`].
+    ///
+    /// ```rust
+    /// use roundable::{Rounder, Tie};
+    ///
+    /// let rounder = Rounder::new(10).unwrap();
+    /// assert!(310 == rounder.round(314, Tie::Up));
+    /// assert!(-10 == rounder.round(-15, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if rounding would return a value that does not fit in the
+    /// return type.
+    #[must_use]
+    pub fn round<S>(&self, value: T, tie: S) -> T
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(self.checked_round(value, tie), "round")
+    }
+
+    /// Round `value` to the nearest factor. Returns `None` if there is an
+    /// overflow.
+    ///
+    /// This is the same as [`Roundable::try_round_to()`], but it never panics
+    /// since the factor was already checked.
+    ///
+    /// ```rust
+    /// use roundable::{Rounder, Tie};
+    ///
+    /// let rounder = Rounder::new(10u8).unwrap();
+    /// assert!(Some(250) == rounder.try_round(254, Tie::Up));
+    /// assert!(None == rounder.try_round(255, Tie::Up));
+    /// ```
+    #[must_use]
+    pub fn try_round<S>(&self, value: T, tie: S) -> Option<T>
+    where
+        S: TieStrategy,
+    {
+        self.checked_round(value, tie).ok()
+    }
+
+    /// Round `value` to the nearest factor. Returns a [`RoundError`] on
+    /// failure.
+    ///
+    /// This is the same as [`Roundable::checked_round_to()`].
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, Rounder, Tie};
+    ///
+    /// let rounder = Rounder::new(10u8).unwrap();
+    /// assert!(Ok(250) == rounder.checked_round(254, Tie::Up));
+    /// assert!(Err(RoundError::Overflow) == rounder.checked_round(255, Tie::Up));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::Overflow`] if the result does not fit in the
+    /// return type.
+    pub fn checked_round<S>(&self, value: T, tie: S) -> Result<T, RoundError>
+    where
+        S: TieStrategy,
+    {
+        value.checked_round_with(self.factor, &self.divisor, tie)
+    }
+
+    /// Round `value` to the next factor in `direction`. Panics if there is an
+    /// overflow.
+    ///
+    /// This is the same as [`Roundable::round_directed_to()`].
+    ///
+    /// ```rust
+    /// use roundable::{Direction, Rounder};
+    ///
+    /// let rounder = Rounder::new(50).unwrap();
+    /// assert!(300 == rounder.round_directed(314, Direction::Down));
+    /// assert!(-350 == rounder.round_directed(-314, Direction::Down));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if rounding would return a value that does not fit in the
+    /// return type.
+    #[must_use]
+    pub fn round_directed(&self, value: T, direction: Direction) -> T {
+        unwrap_rounded(
+            self.checked_round_directed(value, direction),
+            "round_directed",
+        )
+    }
+
+    /// Round `value` to the next factor in `direction`. Returns `None` if
+    /// there is an overflow.
+    ///
+    /// This is the same as [`Roundable::try_round_directed_to()`], but it
+    /// never panics since the factor was already checked.
+    ///
+    /// ```rust
+    /// use roundable::{Direction, Rounder};
+    ///
+    /// let rounder = Rounder::new(10u8).unwrap();
+    /// assert!(Some(250) == rounder.try_round_directed(251, Direction::Down));
+    /// assert!(None == rounder.try_round_directed(251, Direction::Up));
+    /// ```
+    #[must_use]
+    pub fn try_round_directed(
+        &self,
+        value: T,
+        direction: Direction,
+    ) -> Option<T> {
+        self.checked_round_directed(value, direction).ok()
+    }
+
+    /// Round `value` to the next factor in `direction`. Returns a
+    /// [`RoundError`] on failure.
+    ///
+    /// This is the same as [`Roundable::checked_round_directed_to()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::Overflow`] if the result does not fit in the
+    /// return type.
+    pub fn checked_round_directed(
+        &self,
+        value: T,
+        direction: Direction,
+    ) -> Result<T, RoundError> {
+        value.checked_round_directed_with(self.factor, &self.divisor, direction)
+    }
+}
+
+/// Values that can be rounded with a [`Rounder`].
+///
+/// This is implemented for integers and [`Duration`](core::time::Duration). It
+/// can’t be implemented outside of this crate.
+pub trait Precomputable: Roundable + Copy + RoundWithDivisor {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random, DIRECTIONS, TIES};
+    use crate::Tie;
+    use assert2::check;
+    use core::time::Duration;
+
+    /// Check that a [`Rounder`] matches [`Roundable`] for `value`.
+    fn check_rounder<T>(rounder: &Rounder<T>, value: T)
+    where
+        T: Precomputable + core::fmt::Debug + PartialEq,
+    {
+        for tie in TIES {
+            check!(
+                value.checked_round_to(rounder.factor(), tie)
+                    == rounder.checked_round(value, tie),
+                "{:?} {:?} {:?}",
+                value,
+                rounder.factor(),
+                tie
+            );
+        }
+        for direction in DIRECTIONS {
+            check!(
+                value.checked_round_directed_to(rounder.factor(), direction)
+                    == rounder.checked_round_directed(value, direction),
+                "{:?} {:?} {:?}",
+                value,
+                rounder.factor(),
+                direction
+            );
+        }
+    }
+
+    #[test]
+    fn rounder_u8_exhaustive() {
+        for factor in 1..=u8::MAX {
+            let rounder = Rounder::new(factor).unwrap();
+            for value in u8::MIN..=u8::MAX {
+                check_rounder(&rounder, value);
+            }
+        }
+    }
+
+    #[test]
+    fn rounder_i8_exhaustive() {
+        for factor in 1..=i8::MAX {
+            let rounder = Rounder::new(factor).unwrap();
+            for value in i8::MIN..=i8::MAX {
+                check_rounder(&rounder, value);
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_wrap)]
+    fn rounder_64_bit() {
+        let factors = [1, 2, 3, 10, 1_000, 4_096, u64::MAX >> 1, u64::MAX];
+        for factor in factors.iter().copied().chain(random().take(50)) {
+            let rounder = Rounder::new(factor).unwrap();
+            for value in
+                [0, 1, u64::MAX].iter().copied().chain(random().take(50))
+            {
+                check_rounder(&rounder, value);
+                check_rounder(&rounder, value >> 32);
+            }
+
+            let factor = (factor >> 1).max(1) as i64;
+            let rounder = Rounder::new(factor).unwrap();
+            for value in [0, i64::MIN, i64::MAX]
+                .iter()
+                .copied()
+                .chain(random().take(50).map(|value| value as i64))
+            {
+                check_rounder(&rounder, value);
+                check_rounder(&rounder, value >> 32);
+            }
+        }
+    }
+
+    #[test]
+    fn rounder_128_bit() {
+        let random = || {
+            let mut random = random();
+            core::iter::repeat_with(move || {
+                u128::from(random.next().unwrap()) << 64
+                    | u128::from(random.next().unwrap())
+            })
+        };
+        for factor in [1, 3, 10, u128::MAX]
+            .iter()
+            .copied()
+            .chain(random().take(20))
+        {
+            let rounder = Rounder::new(factor).unwrap();
+            for value in [0, u128::MAX].iter().copied().chain(random().take(20))
+            {
+                check_rounder(&rounder, value);
+                check_rounder(&rounder, value >> 64);
+            }
+
+            #[allow(clippy::cast_possible_wrap)]
+            let rounder = Rounder::new((factor >> 1).max(1) as i128).unwrap();
+            for value in [i128::MIN, -1, i128::MAX] {
+                check_rounder(&rounder, value);
+            }
+        }
+    }
+
+    #[test]
+    fn rounder_duration() {
+        let factors = [
+            Duration::from_nanos(1),
+            Duration::from_nanos(3),
+            crate::MILLISECOND,
+            crate::SECOND,
+            Duration::from_millis(1_500),
+            Duration::MAX,
+        ];
+        for factor in factors {
+            let rounder = Rounder::new(factor).unwrap();
+            for value in [
+                Duration::ZERO,
+                Duration::from_millis(750),
+                Duration::from_nanos(1_234_567_890_123),
+                Duration::MAX,
+            ] {
+                check_rounder(&rounder, value);
+            }
+            for nanos in random().take(100) {
+                check_rounder(&rounder, Duration::from_nanos(nanos));
+            }
+        }
+    }
+
+    #[test]
+    fn non_positive_factor() {
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == Rounder::new(0u32).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == Rounder::new(-5i32).map(|r| r.factor())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == Rounder::new(Duration::ZERO).map(|r| r.factor())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow while rounding")]
+    fn round_overflow() {
+        let _ = Rounder::new(10u8).unwrap().round(255, Tie::Up);
+    }
+}
@@ -0,0 +1,90 @@
+//! Work with floats in scientific notation without allocating.
+
+use core::fmt::{self, Write};
+
+/// The longest mantissa we expect from `{:e}` (17 digits for `f64`), plus one
+/// digit for a carry.
+const MAX_DIGITS: usize = 24;
+
+/// The shortest decimal representation of a finite float.
+#[derive(Clone, Copy, Debug)]
+pub struct Scientific {
+    /// Whether the value is negative (including `-0.0`).
+    pub negative: bool,
+
+    /// The ASCII digits of the mantissa. Only the first `len` are used.
+    pub digits: [u8; MAX_DIGITS],
+
+    /// The number of digits used.
+    pub len: usize,
+
+    /// The power of ten of the first digit.
+    pub exponent: i64,
+}
+
+impl Scientific {
+    /// Get the shortest decimal representation of `value`.
+    ///
+    /// Returns `None` if `value` isn’t formatted like a finite float.
+    pub fn new<T: fmt::LowerExp>(value: T) -> Option<Self> {
+        let mut buffer = Buffer::new();
+        write!(buffer, "{:e}", value).ok()?;
+
+        let (negative, scientific) = match buffer.as_str().strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, buffer.as_str()),
+        };
+        let (mantissa, exponent) = scientific.split_once('e')?;
+
+        let mut digits = [0_u8; MAX_DIGITS];
+        let mut len = 0;
+        for digit in mantissa.bytes().filter(u8::is_ascii_digit) {
+            *digits.get_mut(len)? = digit;
+            #[allow(clippy::arithmetic_side_effects)]
+            {
+                len += 1;
+            }
+        }
+
+        Some(Self { negative, digits, len, exponent: exponent.parse().ok()? })
+    }
+
+    /// Get the digits of the mantissa.
+    pub fn digits(&self) -> &[u8] {
+        &self.digits[..self.len]
+    }
+}
+
+/// A fixed size buffer to format numbers into without allocating.
+pub struct Buffer {
+    /// The formatted bytes. Only the first `len` are used.
+    bytes: [u8; 64],
+
+    /// The number of bytes used.
+    len: usize,
+}
+
+impl Buffer {
+    /// Create an empty buffer.
+    pub const fn new() -> Self {
+        Self { bytes: [0; 64], len: 0 }
+    }
+
+    /// Get the contents of the buffer.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self
+            .len
+            .checked_add(s.len())
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(fmt::Error)?;
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
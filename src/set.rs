@@ -0,0 +1,415 @@
+//! Round to the nearest member of a set of allowed values.
+
+use crate::{unwrap_rounded, RoundError, Roundable, TieStrategy};
+use core::cmp::Ordering;
+
+/// Round to the nearest member of a sorted set of allowed values.
+///
+/// This is implemented for integers, floats, and
+/// [`Duration`](core::time::Duration). It’s useful when the allowed values
+/// aren’t evenly spaced, e.g. standard timeouts, discount levels, or paper
+/// sizes. The set is a slice, so it doesn’t require allocation.
+///
+/// `values` must be sorted in ascending order. If it isn’t, the result is
+/// unspecified, but it is still a member of `values`.
+///
+/// Ties are resolved like [`Roundable::round_to()`], except that
+/// [`Tie::TowardEven`] and [`Tie::TowardOdd`] choose by the index of the
+/// member in `values`.
+///
+/// ```rust
+/// use roundable::{SetRoundable, Tie, SECOND};
+///
+/// let timeouts = [1, 5, 10, 30, 60];
+/// assert!(30 == 25.round_to_set(&timeouts, Tie::Up));
+/// assert!(10 == 20.round_to_set(&timeouts, Tie::Down));
+/// assert!(60 == 1_000.round_to_set(&timeouts, Tie::Up));
+/// assert!(3 == 25.round_to_set_index(&timeouts, Tie::Up));
+///
+/// let discounts = [0.0, 0.05, 0.1, 0.15, 0.25];
+/// assert!(0.25 == 0.22.round_to_set(&discounts, Tie::Up));
+///
+/// let delays = [SECOND, 5 * SECOND, 30 * SECOND];
+/// assert!(5 * SECOND == (12 * SECOND).round_to_set(&delays, Tie::Up));
+/// ```
+///
+/// [`Tie::TowardEven`]: crate::Tie::TowardEven
+/// [`Tie::TowardOdd`]: crate::Tie::TowardOdd
+pub trait SetRoundable: Roundable + Copy {
+    /// Round to the nearest member of `values`, which must be sorted.
+    ///
+    /// ```rust
+    /// use roundable::{SetRoundable, Tie};
+    ///
+    /// assert!(-10 == (-8).round_to_set(&[-10, 0, 10], Tie::Up));
+    /// assert!(0 == (-5).round_to_set(&[-10, 0, 10], Tie::Up));
+    /// assert!(-10 == (-5).round_to_set(&[-10, 0, 10], Tie::AwayFromZero));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` is empty, or if the value can’t be rounded (e.g. it’s
+    /// NaN).
+    #[must_use]
+    fn round_to_set<S>(self, values: &[Self], tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(self.checked_round_to_set(values, tie), "round_to_set")
+    }
+
+    /// Round to the nearest member of `values`, which must be sorted. Returns
+    /// a [`RoundError`] on failure.
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, SetRoundable, Tie};
+    ///
+    /// assert!(Ok(2.5) == 2.0.checked_round_to_set(&[1.0, 2.5], Tie::Up));
+    /// assert!(Err(RoundError::EmptySet) == 2.0.checked_round_to_set(&[], Tie::Up));
+    /// assert!(Err(RoundError::NanInput) == f64::NAN.checked_round_to_set(&[1.0], Tie::Up));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::EmptySet`] if `values` is empty. Floats that
+    /// are NaN or infinite fail with [`RoundError::NanInput`] or
+    /// [`RoundError::InfiniteInput`].
+    fn checked_round_to_set<S>(
+        self,
+        values: &[Self],
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy,
+    {
+        self.checked_round_to_set_index(values, tie)
+            .map(|index| values[index])
+    }
+
+    /// Find the index of the nearest member of `values`, which must be
+    /// sorted. If `values` contains duplicates, this may return the index of
+    /// any of them.
+    ///
+    /// ```rust
+    /// use roundable::{SetRoundable, Tie};
+    ///
+    /// let sizes = ["A5", "A4", "A3"];
+    /// let widths = [148, 210, 297];
+    /// assert!("A4" == sizes[230.round_to_set_index(&widths, Tie::Up)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` is empty, or if the value can’t be rounded (e.g. it’s
+    /// NaN).
+    #[must_use]
+    fn round_to_set_index<S>(self, values: &[Self], tie: S) -> usize
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            self.checked_round_to_set_index(values, tie),
+            "round_to_set_index",
+        )
+    }
+
+    /// Find the index of the nearest member of `values`, which must be
+    /// sorted. Returns a [`RoundError`] on failure.
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, SetRoundable, Tie};
+    ///
+    /// assert!(Ok(1) == 7u8.checked_round_to_set_index(&[0, 10, 20], Tie::Up));
+    /// assert!(Err(RoundError::EmptySet) == 7u8.checked_round_to_set_index(&[], Tie::Up));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::EmptySet`] if `values` is empty. Floats that
+    /// are NaN or infinite fail with [`RoundError::NanInput`] or
+    /// [`RoundError::InfiniteInput`].
+    fn checked_round_to_set_index<S>(
+        self,
+        values: &[Self],
+        tie: S,
+    ) -> Result<usize, RoundError>
+    where
+        S: TieStrategy;
+}
+
+/// Find the index of the member of `values` nearest to `value`.
+///
+/// `compare` compares the distance from `lower` to `value` with the distance
+/// from `value` to `upper`, where `lower < value < upper`. It must be exact.
+/// `negative` is whether `value` is negative.
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use set::*`.
+#[allow(clippy::arithmetic_side_effects)]
+pub(crate) fn nearest_index<T, S, F>(
+    value: T,
+    values: &[T],
+    negative: bool,
+    tie: S,
+    compare: F,
+) -> Result<usize, RoundError>
+where
+    T: Copy + PartialOrd,
+    S: TieStrategy,
+    F: FnOnce(T, T, T) -> Ordering,
+{
+    if values.is_empty() {
+        return Err(RoundError::EmptySet);
+    }
+
+    // The first member that is not less than value.
+    let upper = values.partition_point(|&member| member < value);
+    if upper == values.len() {
+        // Safe: values is not empty.
+        return Ok(upper - 1);
+    } else if upper == 0 || values[upper] == value {
+        return Ok(upper);
+    }
+
+    // Safe: upper > 0.
+    let lower = upper - 1;
+    let up = match compare(value, values[lower], values[upper]) {
+        Ordering::Less => false,
+        Ordering::Greater => true,
+        Ordering::Equal => {
+            // The member toward zero is above negative values.
+            let base = if negative { upper } else { lower };
+            tie.is_away_from_zero(negative, || base & 1 == 1) != negative
+        }
+    };
+
+    Ok(if up { upper } else { lower })
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::arithmetic_side_effects,
+    clippy::float_cmp,
+    clippy::integer_division
+)]
+mod tests {
+    use super::*;
+    use crate::test_util::TIES;
+    use crate::Tie;
+    use assert2::check;
+    use core::time::Duration;
+
+    extern crate std;
+    use std::vec::Vec;
+
+    /// Find the nearest member of `values` the slow way, using `i32` to avoid
+    /// overflow.
+    fn expected(value: i32, values: &[i32], tie: Tie) -> usize {
+        let distance = |index: usize| (values[index] - value).abs();
+        let mut best = 0;
+        for index in 1..values.len() {
+            if distance(index) < distance(best) {
+                best = index;
+            } else if distance(index) == distance(best)
+                && values[index] != values[best]
+            {
+                // A tie between best (below) and index (above).
+                let is_lower_odd = best % 2 == 1;
+                let up = match tie {
+                    Tie::Up => true,
+                    Tie::Down => false,
+                    Tie::TowardZero => value < 0,
+                    Tie::AwayFromZero => value >= 0,
+                    Tie::TowardEven => is_lower_odd,
+                    Tie::TowardOdd => !is_lower_odd,
+                };
+                if up {
+                    best = index;
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn exhaustive_i8() {
+        let sets: [&[i8]; 5] = [
+            &[0],
+            &[-128, 127],
+            &[-100, -3, 0, 1, 2, 10, 11, 50, 126],
+            &[-128, -127, -1, 0, 1, 127],
+            &[-90, -70, -40, -30, -10, 0, 10, 30, 40, 70, 90],
+        ];
+        for tie in TIES {
+            for set in sets {
+                let wide: Vec<i32> =
+                    set.iter().copied().map(i32::from).collect();
+                for value in i8::MIN..=i8::MAX {
+                    let index = value.round_to_set_index(set, tie);
+                    check!(
+                        wide[index] == wide[expected(value.into(), &wide, tie)],
+                        "{} {:?} {:?}",
+                        value,
+                        set,
+                        tie
+                    );
+                    check!(set[index] == value.round_to_set(set, tie));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exhaustive_u8() {
+        let set = [0u8, 3, 10, 11, 100, 200, 255];
+        let wide = set.map(i32::from);
+        for tie in TIES {
+            for value in 0..=u8::MAX {
+                check!(
+                    value.round_to_set_index(&set, tie)
+                        == expected(value.into(), &wide, tie)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ties() {
+        let set = [-30, -10, 0, 10, 30];
+        check!(3 == 5.round_to_set_index(&set, Tie::Up));
+        check!(2 == 5.round_to_set_index(&set, Tie::Down));
+        check!(2 == 5.round_to_set_index(&set, Tie::TowardZero));
+        check!(3 == 5.round_to_set_index(&set, Tie::AwayFromZero));
+        check!(2 == 5.round_to_set_index(&set, Tie::TowardEven));
+        check!(3 == 5.round_to_set_index(&set, Tie::TowardOdd));
+        check!(4 == 20.round_to_set_index(&set, Tie::TowardEven));
+
+        check!(0 == (-20).round_to_set_index(&set, Tie::Down));
+        check!(1 == (-20).round_to_set_index(&set, Tie::Up));
+        check!(1 == (-20).round_to_set_index(&set, Tie::TowardZero));
+        check!(0 == (-20).round_to_set_index(&set, Tie::AwayFromZero));
+        check!(0 == (-20).round_to_set_index(&set, Tie::TowardEven));
+        check!(1 == (-20).round_to_set_index(&set, Tie::TowardOdd));
+    }
+
+    #[test]
+    fn extremes() {
+        let set = [i128::MIN, 0, i128::MAX];
+        check!(0 == i128::MIN.round_to_set_index(&set, Tie::Up));
+        check!(0 == (i128::MIN / 2).round_to_set_index(&set, Tie::Down));
+        check!(1 == (i128::MIN / 2).round_to_set_index(&set, Tie::Up));
+        check!(1 == (i128::MAX / 2).round_to_set_index(&set, Tie::Down));
+        check!(2 == (i128::MAX / 2 + 1).round_to_set_index(&set, Tie::Down));
+
+        let set = [i128::MIN, i128::MAX];
+        check!(0 == (-1).round_to_set_index(&set, Tie::Up));
+        check!(1 == 0.round_to_set_index(&set, Tie::Down));
+
+        let set = [0, u128::MAX];
+        check!(0 == (u128::MAX / 2).round_to_set_index(&set, Tie::Up));
+        check!(1 == (u128::MAX / 2 + 1).round_to_set_index(&set, Tie::Down));
+    }
+
+    #[test]
+    fn floats() {
+        let set = [-1.5, -0.5, 0.0, 0.25, 1.0, 1e300];
+        check!(0.25 == 0.3.round_to_set(&set, Tie::Up));
+        check!(1.0 == 0.625.round_to_set(&set, Tie::Up));
+        check!(0.25 == 0.625.round_to_set(&set, Tie::Down));
+        check!(0.0 == (-0.25).round_to_set(&set, Tie::Up));
+        check!(-0.5 == (-0.25).round_to_set(&set, Tie::Down));
+        check!(-1.5 == (-1e10).round_to_set(&set, Tie::Up));
+        check!(1e300 == 6e299.round_to_set(&set, Tie::Down));
+        check!(1.0 == 1e299.round_to_set(&set, Tie::Up));
+        check!(1.0 == 1e10.round_to_set(&set, Tie::Up));
+        check!(0.0 == (-0.0).round_to_set(&set, Tie::Up));
+
+        // Distances are compared exactly, even if subtraction rounds.
+        check!(2.0 == 1.0.round_to_set(&[-1e-17, 2.0], Tie::Down));
+        check!(0.3 == 0.2.round_to_set(&[0.1, 0.3], Tie::Down));
+        check!(0.1 == 0.2.round_to_set(&[0.1, 0.1 + 0.2], Tie::Up));
+
+        // The distances overflow.
+        let set = [-f64::MAX, f64::MAX];
+        check!(-f64::MAX == 0.0.round_to_set(&set, Tie::Down));
+        check!(f64::MAX == 0.0.round_to_set(&set, Tie::Up));
+        check!(f64::MAX == 1e-300.round_to_set(&set, Tie::Down));
+        check!(f64::MAX == 1e300.round_to_set(&set, Tie::Down));
+        check!(-f64::MAX == (-1e300).round_to_set(&set, Tie::Up));
+
+        // Infinite members are never nearest to finite values.
+        let set = [f32::NEG_INFINITY, 0.0, f32::INFINITY];
+        check!(0.0 == f32::MAX.round_to_set(&set, Tie::Up));
+        check!(0.0 == f32::MIN.round_to_set(&set, Tie::Up));
+
+        check!(
+            Err(RoundError::NanInput)
+                == f64::NAN.checked_round_to_set(&[0.0], Tie::Up)
+        );
+        check!(
+            Err(RoundError::InfiniteInput)
+                == f32::INFINITY.checked_round_to_set(&[0.0], Tie::Up)
+        );
+    }
+
+    #[test]
+    fn floats_match_integers() {
+        let set = [-100, -40, -39, -10, 0, 3, 8, 20, 21, 64];
+        let float_set = set.map(f64::from);
+        for tie in TIES {
+            for value in -120..=120 {
+                check!(
+                    f64::from(value).round_to_set_index(&float_set, tie)
+                        == value.round_to_set_index(&set, tie)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn durations() {
+        const SECOND: Duration = Duration::from_secs(1);
+        let set = [SECOND, 5 * SECOND, 30 * SECOND, Duration::MAX];
+        check!(SECOND == Duration::ZERO.round_to_set(&set, Tie::Up));
+        check!(5 * SECOND == (3 * SECOND).round_to_set(&set, Tie::Up));
+        check!(SECOND == (3 * SECOND).round_to_set(&set, Tie::Down));
+        check!(30 * SECOND == (60 * SECOND).round_to_set(&set, Tie::Up));
+        check!(
+            Duration::MAX
+                == Duration::from_secs(u64::MAX).round_to_set(&set, Tie::Down)
+        );
+        check!(
+            Ok(2) == (20 * SECOND).checked_round_to_set_index(&set, Tie::Up)
+        );
+    }
+
+    #[test]
+    fn duplicates_and_single_values() {
+        check!(7 == 100.round_to_set(&[7], Tie::Up));
+        check!(7 == (-100).round_to_set(&[7], Tie::Up));
+        check!(5 == 5.round_to_set(&[5, 5, 5, 7, 7], Tie::Up));
+        check!(7 == 6.round_to_set(&[5, 5, 5, 7, 7], Tie::Up));
+        check!(5 == 6.round_to_set(&[5, 5, 5, 7, 7], Tie::Down));
+    }
+
+    #[test]
+    fn empty_set() {
+        check!(
+            Err(RoundError::EmptySet) == 1u8.checked_round_to_set(&[], Tie::Up)
+        );
+        check!(
+            Err(RoundError::EmptySet)
+                == Duration::ZERO.checked_round_to_set_index(&[], Tie::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "round_to_set() requires a non-empty set")]
+    fn panic_empty() {
+        let _ = 1.round_to_set(&[], Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot round NaN")]
+    fn panic_nan() {
+        let _ = f32::NAN.round_to_set_index(&[1.0], Tie::Up);
+    }
+}
@@ -0,0 +1,280 @@
+//! Round numbers to a number of significant digits.
+
+use crate::decimal::round_decimal;
+use crate::{
+    rounded_or_none, unwrap_rounded, RoundError, Roundable, TieStrategy,
+};
+
+/// Methods to round to a number of significant digits.
+///
+/// This is implemented for integers and floats.
+///
+/// ```rust
+/// use roundable::{SignificantRoundable, Tie};
+///
+/// assert!(123_000 == 123_456.round_to_significant(3, Tie::Up));
+/// assert!(0.00123 == 0.001_234_5.round_to_significant(3, Tie::Up));
+/// ```
+pub trait SignificantRoundable: Sized {
+    /// Round to `digits` significant digits. Panics if there is an overflow.
+    ///
+    /// Integers are rounded to the nearest multiple of the power of ten
+    /// needed to keep `digits` digits. Ties are handled according to `tie`
+    /// just like [`Roundable::round_to()`].
+    ///
+    /// Floats are rounded like
+    /// [`DecimalRoundable::round_to_decimal_places()`](crate::DecimalRoundable::round_to_decimal_places()),
+    /// so ties are decided by the shortest decimal representation of the
+    /// value.
+    ///
+    /// ```rust
+    /// use roundable::{SignificantRoundable, Tie};
+    ///
+    /// assert!(1_500 == 1_450.round_to_significant(2, Tie::Up));
+    /// assert!(1_400 == 1_450.round_to_significant(2, Tie::TowardEven));
+    /// assert!(-0.125 == (-0.1245).round_to_significant(3, Tie::AwayFromZero));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is 0, or if the value can’t be rounded (see
+    /// [`SignificantRoundable::checked_round_to_significant()`]).
+    #[must_use]
+    fn round_to_significant<S>(self, digits: u32, tie: S) -> Self
+    where
+        S: TieStrategy,
+    {
+        unwrap_rounded(
+            self.checked_round_to_significant(digits, tie),
+            "try_round_to_significant",
+        )
+    }
+
+    /// Round to `digits` significant digits. Returns `None` if there is an
+    /// overflow.
+    ///
+    /// See [`SignificantRoundable::round_to_significant()`].
+    ///
+    /// ```rust
+    /// use roundable::{SignificantRoundable, Tie};
+    ///
+    /// assert!(Some(250) == 254u8.try_round_to_significant(2, Tie::Up));
+    /// assert!(None == 255u8.try_round_to_significant(1, Tie::Up));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is 0.
+    #[must_use]
+    fn try_round_to_significant<S>(self, digits: u32, tie: S) -> Option<Self>
+    where
+        S: TieStrategy,
+    {
+        rounded_or_none(
+            self.checked_round_to_significant(digits, tie),
+            "try_round_to_significant",
+        )
+    }
+
+    /// Round to `digits` significant digits. Returns a [`RoundError`] on
+    /// failure.
+    ///
+    /// See [`SignificantRoundable::round_to_significant()`].
+    ///
+    /// ```rust
+    /// use roundable::{RoundError, SignificantRoundable, Tie};
+    ///
+    /// assert!(Ok(300) == 314.checked_round_to_significant(1, Tie::Up));
+    /// assert!(
+    ///     Err(RoundError::ZeroDigits)
+    ///         == 314.checked_round_to_significant(0, Tie::Up)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoundError::ZeroDigits`] if `digits` is 0, and
+    /// [`RoundError::Overflow`] if the result does not fit in the return type.
+    /// Floats can also return [`RoundError::NanInput`],
+    /// [`RoundError::InfiniteInput`], or [`RoundError::NonFiniteResult`].
+    fn checked_round_to_significant<S>(
+        self,
+        digits: u32,
+        tie: S,
+    ) -> Result<Self, RoundError>
+    where
+        S: TieStrategy;
+}
+
+/// Implement significant digit rounding for integer types.
+macro_rules! significant_integer {
+    ($($ty:ident)+) => {$(
+        impl SignificantRoundable for $ty {
+            fn checked_round_to_significant<S>(
+                self,
+                digits: u32,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if digits == 0 {
+                    return Err(RoundError::ZeroDigits);
+                }
+
+                // Count the digits. Division truncates toward zero, so this
+                // works for negative numbers too.
+                let mut count: u32 = 0;
+                let mut rest = self;
+                #[allow(
+                    clippy::arithmetic_side_effects,
+                    clippy::integer_division
+                )]
+                while rest != 0 {
+                    rest /= 10;
+                    count += 1;
+                }
+
+                match count.checked_sub(digits) {
+                    None | Some(0) => Ok(self),
+                    Some(dropped) => {
+                        // dropped < count, so 10^dropped ≤ |self| and this
+                        // can’t overflow. Wrapping avoids a dead error path.
+                        let factor = (10 as $ty).wrapping_pow(dropped);
+                        self.checked_round_to(factor, tie)
+                    }
+                }
+            }
+        }
+    )+}
+}
+
+significant_integer!(u8 u16 u32 u64 u128 usize);
+significant_integer!(i8 i16 i32 i64 i128 isize);
+
+/// Implement significant digit rounding for float types.
+macro_rules! significant_float {
+    ($($ty:ident)+) => {$(
+        impl SignificantRoundable for $ty {
+            fn checked_round_to_significant<S>(
+                self,
+                digits: u32,
+                tie: S,
+            ) -> Result<Self, RoundError>
+            where
+                S: TieStrategy,
+            {
+                if digits == 0 {
+                    return Err(RoundError::ZeroDigits);
+                } else if self.is_nan() {
+                    return Err(RoundError::NanInput);
+                } else if self.is_infinite() {
+                    return Err(RoundError::InfiniteInput);
+                }
+
+                // The first significant digit is in the 10^exponent place.
+                let places = |exponent: i64| {
+                    i64::from(digits).saturating_sub(1).saturating_sub(exponent)
+                };
+                round_decimal(self, places, tie)
+                    .filter(|value| value.is_finite())
+                    .ok_or(RoundError::NonFiniteResult)
+            }
+        }
+    )+}
+}
+
+significant_float!(f32 f64);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::Tie;
+    use assert2::check;
+
+    #[test]
+    fn round_integer_to_significant() {
+        check!(123_000 == 123_456.round_to_significant(3, Tie::Up));
+        check!(123_500 == 123_456.round_to_significant(4, Tie::Up));
+        check!(123_456 == 123_456.round_to_significant(6, Tie::Up));
+        check!(123_456 == 123_456.round_to_significant(100, Tie::Up));
+        check!(100_000 == 123_456.round_to_significant(1, Tie::Up));
+        check!(-123_000 == (-123_456).round_to_significant(3, Tie::Up));
+        check!(0 == 0.round_to_significant(1, Tie::Up));
+        check!(7 == 7.round_to_significant(1, Tie::Up));
+        check!(1_000 == 999.round_to_significant(2, Tie::Up));
+    }
+
+    #[test]
+    fn round_integer_to_significant_ties() {
+        check!(1_500 == 1_450.round_to_significant(2, Tie::Up));
+        check!(1_400 == 1_450.round_to_significant(2, Tie::Down));
+        check!(1_400 == 1_450.round_to_significant(2, Tie::TowardEven));
+        check!(1_500 == 1_450.round_to_significant(2, Tie::TowardOdd));
+        check!(-1_400 == (-1_450).round_to_significant(2, Tie::Up));
+        check!(-1_500 == (-1_450).round_to_significant(2, Tie::AwayFromZero));
+    }
+
+    #[test]
+    fn round_integer_to_significant_extremes() {
+        check!(200 == 249u8.round_to_significant(1, Tie::Up));
+        check!(None == 255u8.try_round_to_significant(1, Tie::Up));
+        check!(-100 == i8::MIN.round_to_significant(1, Tie::Up));
+        check!(
+            Err(RoundError::Overflow)
+                == i8::MIN.checked_round_to_significant(2, Tie::Up)
+        );
+        check!(
+            18_000_000_000_000_000_000
+                == u64::MAX.round_to_significant(2, Tie::Up)
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == u64::MAX.checked_round_to_significant(1, Tie::Up)
+        );
+        check!(u128::MAX == u128::MAX.round_to_significant(39, Tie::Up));
+    }
+
+    #[test]
+    fn round_float_to_significant() {
+        check!(0.00123 == 0.001_234_5_f64.round_to_significant(3, Tie::Up));
+        check!(0.00124 == 0.001_235_f64.round_to_significant(3, Tie::Up));
+        check!(0.00123 == 0.001_234_5_f32.round_to_significant(3, Tie::Up));
+        check!(123_000.0 == 123_456.0_f64.round_to_significant(3, Tie::Up));
+        check!(-0.125 == (-0.1245_f64).round_to_significant(3, Tie::Down));
+        check!(-0.124 == (-0.1245_f64).round_to_significant(3, Tie::Up));
+        check!(10.0 == 9.96_f64.round_to_significant(2, Tie::Up));
+        check!(0.0 == 0.0_f64.round_to_significant(3, Tie::Up));
+        check!(1.5 == 1.5_f64.round_to_significant(17, Tie::Up));
+        check!(2e-320 == 2.4e-320_f64.round_to_significant(1, Tie::Up));
+    }
+
+    #[test]
+    fn round_float_to_significant_errors() {
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f64::MAX.checked_round_to_significant(1, Tie::Up)
+        );
+        check!(
+            Err(RoundError::NanInput)
+                == f64::NAN.checked_round_to_significant(1, Tie::Up)
+        );
+        check!(
+            Err(RoundError::InfiniteInput)
+                == f32::INFINITY.checked_round_to_significant(1, Tie::Up)
+        );
+        check!(
+            Err(RoundError::ZeroDigits)
+                == 1.0_f64.checked_round_to_significant(0, Tie::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "try_round_to_significant() requires at least one digit"
+    )]
+    fn try_round_to_zero_significant_digits() {
+        let _ = 10.try_round_to_significant(0, Tie::Up);
+    }
+}
@@ -0,0 +1,567 @@
+//! Round whole slices of values.
+
+use crate::{Roundable, TieStrategy};
+
+/// Methods to round every value in a slice to the same factor.
+///
+/// This is implemented for integers and floats. The results are exactly the
+/// same as rounding each value with [`Roundable::try_round_to()`], but these
+/// are faster for long slices. The factor is only checked once, and integers
+/// and `f32`s are rounded with a kernel that avoids branches so that the
+/// compiler can vectorize it.
+///
+/// If a value can’t be rounded, these return the index of the first value that
+/// failed. You can use [`Roundable::checked_round_to()`] on that value to find
+/// out why.
+///
+/// ```rust
+/// use roundable::{SliceRoundable, Tie};
+///
+/// let mut values = [1.2f32, 2.5, -3.75];
+/// f32::try_round_slice_to(&mut values, 0.5, Tie::Up).unwrap();
+/// assert!(values == [1.0, 2.5, -3.5]);
+///
+/// let mut values = [100u8, 249, 255, 120];
+/// assert!(Err(2) == u8::try_round_slice_to(&mut values, 10, Tie::Up));
+/// assert!(values == [100, 250, 255, 120]);
+/// ```
+pub trait SliceRoundable: Roundable + Copy {
+    /// Round every value in `values` to the nearest `factor` in place. Panics
+    /// if a value can’t be rounded.
+    ///
+    /// ```rust
+    /// use roundable::{SliceRoundable, Tie};
+    ///
+    /// let mut values = [-15i64, 14, 25];
+    /// i64::round_slice_to(&mut values, 10, Tie::TowardEven);
+    /// assert!(values == [-20, 10, 20]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, or if any value can’t be rounded,
+    /// e.g. because the result does not fit in the type.
+    fn round_slice_to<S>(values: &mut [Self], factor: Self, tie: S)
+    where
+        S: TieStrategy,
+    {
+        if let Err(index) = Self::try_round_slice_to(values, factor, tie) {
+            fail_at(values[index], factor, tie, index);
+        }
+    }
+
+    /// Round every value in `values` to the nearest `factor` in place.
+    /// Returns the index of the first value that can’t be rounded on failure.
+    ///
+    /// If this fails, the values before the index are rounded, and the value
+    /// at the index and all values after it are unchanged.
+    ///
+    /// ```rust
+    /// use roundable::{SliceRoundable, Tie};
+    ///
+    /// let mut values = [254u32, 4_294_967_295, 6];
+    /// assert!(Err(1) == u32::try_round_slice_to(&mut values, 10, Tie::Up));
+    /// assert!(values == [250, 4_294_967_295, 6]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the index of the first value that
+    /// [`Roundable::try_round_to()`] would return `None` for.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive.
+    fn try_round_slice_to<S>(
+        values: &mut [Self],
+        factor: Self,
+        tie: S,
+    ) -> Result<(), usize>
+    where
+        S: TieStrategy;
+
+    /// Round every value in `input` to the nearest `factor` and store the
+    /// results in `output`. Panics if a value can’t be rounded.
+    ///
+    /// ```rust
+    /// use roundable::{SliceRoundable, Tie};
+    ///
+    /// let input = [0.26f32, 0.74];
+    /// let mut output = [0.0; 2];
+    /// f32::round_slice_into(&input, &mut output, 0.5, Tie::Up);
+    /// assert!(output == [0.5, 0.5]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` and `output` have different lengths, if `factor` is
+    /// not positive, or if any value can’t be rounded, e.g. because the result
+    /// does not fit in the type.
+    fn round_slice_into<S>(
+        input: &[Self],
+        output: &mut [Self],
+        factor: Self,
+        tie: S,
+    ) where
+        S: TieStrategy,
+    {
+        if let Err(index) =
+            Self::try_round_slice_into(input, output, factor, tie)
+        {
+            fail_at(input[index], factor, tie, index);
+        }
+    }
+
+    /// Round every value in `input` to the nearest `factor` and store the
+    /// results in `output`. Returns the index of the first value that can’t be
+    /// rounded on failure.
+    ///
+    /// If this fails, the values in `output` before the index are rounded, and
+    /// the values at the index and after it are unspecified.
+    ///
+    /// ```rust
+    /// use roundable::{SliceRoundable, Tie};
+    ///
+    /// let input = [1.0f32, f32::NAN];
+    /// let mut output = [0.0; 2];
+    /// assert!(Err(1) == f32::try_round_slice_into(&input, &mut output, 1.0, Tie::Up));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the index of the first value that
+    /// [`Roundable::try_round_to()`] would return `None` for.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` and `output` have different lengths, or if `factor`
+    /// is not positive.
+    fn try_round_slice_into<S>(
+        input: &[Self],
+        output: &mut [Self],
+        factor: Self,
+        tie: S,
+    ) -> Result<(), usize>
+    where
+        S: TieStrategy;
+}
+
+/// Panic because `value`, at `index`, could not be rounded.
+#[cold]
+fn fail_at<T, S>(value: T, factor: T, tie: S, index: usize) -> !
+where
+    T: Roundable,
+    S: TieStrategy,
+{
+    match value.checked_round_to(factor, tie) {
+        Err(error) => panic!("{} at index {}", error, index),
+        Ok(_) => unreachable!("rounding value at index {} failed", index),
+    }
+}
+
+/// How many values to round before checking for failures.
+const CHUNK_SIZE: usize = 64;
+
+/// A [`TieStrategy`] that looks up its decisions in a table.
+///
+/// A strategy like [`Tie`](crate::Tie) is a `match`, which keeps kernels from
+/// being vectorized. There are only four possible questions, so ask them all
+/// up front.
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use slice::*`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TieTable(u8);
+
+impl TieTable {
+    /// Record the decisions of `tie`.
+    #[inline]
+    pub(crate) fn new<S: TieStrategy>(tie: S) -> Self {
+        let mut table = 0;
+        for index in 0..4 {
+            let negative = index & 2 != 0;
+            let is_base_odd = index & 1 != 0;
+            if tie.is_away_from_zero(negative, || is_base_odd) {
+                table |= 1 << index;
+            }
+        }
+        Self(table)
+    }
+}
+
+impl TieStrategy for TieTable {
+    #[inline]
+    fn is_away_from_zero<F>(self, negative: bool, is_base_odd: F) -> bool
+    where
+        F: FnOnce() -> bool,
+    {
+        let index = (u8::from(negative) << 1) | u8::from(is_base_odd());
+        (self.0 >> index) & 1 != 0
+    }
+}
+
+/// Round every value in `values` in place with `kernel`.
+///
+/// See [`round_into()`] for how `kernel` is used. Values are rounded into a
+/// buffer one chunk at a time, so that if a value can’t be rounded, the values
+/// after it can be left unchanged.
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use slice::*`.
+#[inline]
+pub(crate) fn round_in_place<T, S, K>(
+    values: &mut [T],
+    factor: T,
+    tie: S,
+    kernel: K,
+) -> Result<(), usize>
+where
+    T: Roundable + Copy,
+    S: TieStrategy,
+    K: Fn(T) -> (T, bool),
+{
+    let mut buffer = match values.first() {
+        Some(&first) => [first; CHUNK_SIZE],
+        None => return Ok(()),
+    };
+
+    for (i, chunk) in values.chunks_mut(CHUNK_SIZE).enumerate() {
+        // chunk.len() ≤ CHUNK_SIZE.
+        let buffer = &mut buffer[..chunk.len()];
+        if let Err(j) = round_chunk(chunk, buffer, factor, tie, &kernel) {
+            chunk[..j].copy_from_slice(&buffer[..j]);
+            return Err(offset(i, j));
+        }
+        chunk.copy_from_slice(buffer);
+    }
+
+    Ok(())
+}
+
+/// Round every value in `input` with `kernel` and store the results in
+/// `output`.
+///
+/// `kernel` returns the rounded value and whether it is correct. It should
+/// avoid branches so that it can be vectorized. If it returns `false`, the
+/// value is rounded again with [`Roundable::checked_round_to()`], so `kernel`
+/// can bail out on values that are hard to round.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use slice::*`.
+#[inline]
+pub(crate) fn round_into<T, S, K>(
+    input: &[T],
+    output: &mut [T],
+    factor: T,
+    tie: S,
+    kernel: K,
+) -> Result<(), usize>
+where
+    T: Roundable + Copy,
+    S: TieStrategy,
+    K: Fn(T) -> (T, bool),
+{
+    assert!(
+        input.len() == output.len(),
+        "input and output slices must have the same length",
+    );
+
+    for (i, (input, output)) in input
+        .chunks(CHUNK_SIZE)
+        .zip(output.chunks_mut(CHUNK_SIZE))
+        .enumerate()
+    {
+        round_chunk(input, output, factor, tie, &kernel)
+            .map_err(|j| offset(i, j))?;
+    }
+
+    Ok(())
+}
+
+/// Round a chunk of values with `kernel`. Returns the index of the first value
+/// that can’t be rounded on failure.
+#[inline]
+fn round_chunk<T, S, K>(
+    input: &[T],
+    output: &mut [T],
+    factor: T,
+    tie: S,
+    kernel: &K,
+) -> Result<(), usize>
+where
+    T: Roundable + Copy,
+    S: TieStrategy,
+    K: Fn(T) -> (T, bool),
+{
+    // Round everything without branching, and only then check if anything
+    // needs to be redone.
+    let mut correct = true;
+    for (&value, rounded) in input.iter().zip(output.iter_mut()) {
+        let (value, ok) = kernel(value);
+        *rounded = value;
+        correct &= ok;
+    }
+
+    if !correct {
+        for (j, (&value, rounded)) in
+            input.iter().zip(output.iter_mut()).enumerate()
+        {
+            if !kernel(value).1 {
+                *rounded =
+                    value.checked_round_to(factor, tie).map_err(|_| j)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the index of value `j` in chunk `i`.
+#[allow(clippy::arithmetic_side_effects)]
+const fn offset(i: usize, j: usize) -> usize {
+    // Safe: this is the index of a value in a slice.
+    i * CHUNK_SIZE + j
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random, TIES};
+    use crate::Tie;
+    use assert2::check;
+
+    /// Check that rounding `values` as a slice matches rounding each value.
+    ///
+    /// `same` compares two values, so that floats can be compared by bits.
+    fn check_slice<T, F>(values: &[T], factor: T, same: F)
+    where
+        T: SliceRoundable + core::fmt::Debug,
+        F: Fn(T, T) -> bool,
+    {
+        for tie in TIES {
+            let mut in_place = [values[0]; 300];
+            let in_place = &mut in_place[..values.len()];
+            in_place.copy_from_slice(values);
+            let mut output = [values[0]; 300];
+            let output = &mut output[..values.len()];
+
+            let expected_error = values
+                .iter()
+                .position(|v| v.try_round_to(factor, tie).is_none());
+            check!(
+                expected_error
+                    == T::try_round_slice_to(in_place, factor, tie).err(),
+                "{:?} {:?}",
+                factor,
+                tie
+            );
+            check!(
+                expected_error
+                    == T::try_round_slice_into(values, output, factor, tie)
+                        .err(),
+                "{:?} {:?}",
+                factor,
+                tie
+            );
+
+            let end = expected_error.unwrap_or(values.len());
+            for (i, &value) in values.iter().enumerate() {
+                if i < end {
+                    let expected = value.try_round_to(factor, tie).unwrap();
+                    check!(
+                        same(expected, in_place[i]),
+                        "{:?} {:?} {:?}: {:?} != {:?}",
+                        value,
+                        factor,
+                        tie,
+                        expected,
+                        in_place[i]
+                    );
+                    check!(
+                        same(expected, output[i]),
+                        "{:?} {:?} {:?}: {:?} != {:?}",
+                        value,
+                        factor,
+                        tie,
+                        expected,
+                        output[i]
+                    );
+                } else {
+                    // Unchanged.
+                    check!(same(value, in_place[i]));
+                }
+            }
+        }
+    }
+
+    /// Compare floats by their bits, so that -0.0 and 0.0 are different.
+    fn same_f32(a: f32, b: f32) -> bool {
+        a.to_bits() == b.to_bits()
+    }
+
+    #[test]
+    fn slice_u8_exhaustive() {
+        let mut values = [0u8; 256];
+        for (value, i) in values.iter_mut().zip(0..=u8::MAX) {
+            *value = i;
+        }
+        for factor in 1..=u8::MAX {
+            // Overflows stop rounding, so check the values before them, too.
+            check_slice(&values, factor, |a, b| a == b);
+            check_slice(&values[..200], factor, |a, b| a == b);
+            check_slice(&values[..100], factor, |a, b| a == b);
+        }
+    }
+
+    #[test]
+    fn slice_i8_exhaustive() {
+        let mut values = [0i8; 256];
+        for (value, i) in values.iter_mut().zip(i8::MIN..=i8::MAX) {
+            *value = i;
+        }
+        for factor in 1..=i8::MAX {
+            check_slice(&values, factor, |a, b| a == b);
+            check_slice(&values[10..], factor, |a, b| a == b);
+            check_slice(&values[10..200], factor, |a, b| a == b);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn slice_integers() {
+        let mut random = random();
+        let mut u32s = [0u32; 300];
+        let mut i64s = [0i64; 300];
+        for _ in 0..20 {
+            for (a, b) in u32s.iter_mut().zip(i64s.iter_mut()) {
+                let n = random.next().unwrap();
+                // Mostly small values, so overflow isn’t too common.
+                *a = (n >> (n % 40)) as u32;
+                *b = (n as i64) >> (n % 64);
+            }
+            for factor in [1, 2, 3, 10, 1_000, 4_096, u32::MAX >> 1] {
+                check_slice(&u32s, factor, |a, b| a == b);
+                check_slice(&i64s, i64::from(factor), |a, b| a == b);
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn slice_f32() {
+        let mut values = [0f32; 300];
+        let factors = [
+            1.0,
+            0.1,
+            0.5,
+            0.3,
+            3.0,
+            1e-3,
+            1e-30,
+            1e30,
+            1e-45,
+            f32::MIN_POSITIVE,
+            f32::MAX,
+            f32::INFINITY,
+        ];
+        let mut random = random();
+        for _ in 0..20 {
+            for value in &mut values {
+                let n = random.next().unwrap();
+                // Random bits make for huge and tiny values, so also make
+                // values near small multiples of the factors.
+                *value = if n & 1 == 0 {
+                    f32::from_bits(n as u32)
+                } else {
+                    ((n >> 8) % 4_000) as f32 * 0.25 - 500.0
+                };
+            }
+            for factor in factors {
+                check_slice(&values, factor, same_f32);
+                check_slice(&values, factor * 0.75, same_f32);
+            }
+        }
+
+        let special = [
+            0.0,
+            -0.0,
+            0.5,
+            -0.5,
+            1.5,
+            2.5,
+            -2.5,
+            0.15,
+            1_048_575.5,
+            1_048_576.5,
+            16_777_215.0,
+            f32::MAX,
+            f32::MIN,
+            f32::EPSILON,
+            f32::INFINITY,
+            f32::NAN,
+        ];
+        for factor in factors {
+            check_slice(&special, factor, same_f32);
+        }
+    }
+
+    #[test]
+    fn slice_f32_many_factors() {
+        // The kernel falls back to `checked_round_to()` for these.
+        let mut output = [0.0; 3];
+        f32::round_slice_into(
+            &[787_091.0, -787_091.0, 390_856.5],
+            &mut output,
+            0.1,
+            Tie::Up,
+        );
+        check!(output == [787_091.0, -787_091.0, 390_856.5]);
+    }
+
+    #[test]
+    fn slice_f64() {
+        let values = [0.0, -0.0, 0.15, 2.5, -2.5, 1e300, f64::MAX, f64::NAN];
+        for factor in [1.0, 0.1, 0.5, 3.0, 1e300, f64::MAX] {
+            check_slice(&values, factor, |a: f64, b: f64| {
+                a.to_bits() == b.to_bits()
+            });
+        }
+    }
+
+    #[test]
+    fn empty() {
+        check!(Ok(()) == u32::try_round_slice_to(&mut [], 10, Tie::Up));
+        check!(Ok(()) == f32::try_round_slice_into(&[], &mut [], 1.0, Tie::Up));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow while rounding at index 2")]
+    fn round_slice_overflow() {
+        u8::round_slice_to(&mut [0, 10, 255], 10, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot round NaN at index 1")]
+    fn round_slice_into_nan() {
+        f32::round_slice_into(&[0.0, f32::NAN], &mut [0.0; 2], 1.0, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "try_round_slice_to() requires positive factor")]
+    fn round_slice_zero_factor() {
+        i32::round_slice_to(&mut [0, 10], 0, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "try_round_slice_into() requires positive factor"
+    )]
+    fn round_slice_into_nan_factor() {
+        f32::round_slice_into(&[0.0], &mut [0.0], f32::NAN, Tie::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn round_slice_into_length() {
+        u32::round_slice_into(&[0, 10], &mut [0], 10, Tie::Up);
+    }
+}
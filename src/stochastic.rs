@@ -0,0 +1,653 @@
+//! Round randomly, in proportion to the distance to each multiple.
+
+use crate::power::multiply_wide;
+use crate::{rounded_or_none, unwrap_rounded, RoundError, Roundable};
+
+/// A source of random numbers for [`StochasticRoundable`].
+///
+/// This is a minimal interface so that any random number generator can be
+/// used without adding a dependency. With the `rand` feature, it is
+/// implemented for every [`rand_core::RngCore`][RngCore].
+///
+/// The quality of the rounding depends on the quality of the random numbers:
+/// each bit returned by [`RandomSource::next_u64()`] should be equally likely
+/// to be 0 or 1, independently of the others.
+///
+/// ```rust
+/// use roundable::RandomSource;
+///
+/// /// A tiny xorshift generator. Don’t use this for anything important.
+/// struct Xorshift(u64);
+///
+/// impl RandomSource for Xorshift {
+///     fn next_u64(&mut self) -> u64 {
+///         self.0 ^= self.0 << 13;
+///         self.0 ^= self.0 >> 7;
+///         self.0 ^= self.0 << 17;
+///         self.0
+///     }
+/// }
+/// ```
+///
+/// [RngCore]: https://docs.rs/rand_core/0.6/rand_core/trait.RngCore.html
+pub trait RandomSource {
+    /// Get the next random `u64`.
+    fn next_u64(&mut self) -> u64;
+}
+
+#[cfg(feature = "rand")]
+impl<R> RandomSource for R
+where
+    R: rand_core::RngCore + ?Sized,
+{
+    fn next_u64(&mut self) -> u64 {
+        rand_core::RngCore::next_u64(self)
+    }
+}
+
+/// Round to a multiple of a factor at random, with the probability of each
+/// multiple proportional to how close the value is to it.
+///
+/// This is implemented for integers, floats, and
+/// [`Duration`](core::time::Duration). A value 30% of the way from one
+/// multiple of `factor` to the next rounds to the next multiple 30% of the
+/// time, and to the previous multiple the rest of the time. Values that are
+/// already multiples of `factor` are unchanged.
+///
+/// Rounding to the nearest multiple introduces a bias when many values are
+/// rounded: the errors don’t cancel out. Stochastic rounding is unbiased, so
+/// the expected value of the result is the original value.
+///
+/// Random numbers come from a [`RandomSource`].
+///
+/// ```rust
+/// use roundable::{RandomSource, StochasticRoundable};
+///
+/// struct Xorshift(u64);
+///
+/// impl RandomSource for Xorshift {
+///     fn next_u64(&mut self) -> u64 {
+///         self.0 ^= self.0 << 13;
+///         self.0 ^= self.0 >> 7;
+///         self.0 ^= self.0 << 17;
+///         self.0
+///     }
+/// }
+///
+/// let mut random = Xorshift(0x2545_f491_4f6c_dd1d);
+/// let rounded = 1.25.round_stochastic_to(1.0, &mut random);
+/// assert!(rounded == 1.0 || rounded == 2.0);
+///
+/// // On average, the result is the original value.
+/// let total: u32 = (0..1000)
+///     .map(|_| 3u32.round_stochastic_to(10, &mut random))
+///     .sum();
+/// assert!((2500..3500).contains(&total));
+/// ```
+pub trait StochasticRoundable: Roundable {
+    /// Round to one of the two nearest multiples of `factor` at random.
+    /// Panics if the result does not fit in the type.
+    ///
+    /// The probability of rounding away from the multiple of `factor` toward
+    /// 0 is the distance from it divided by `factor`.
+    ///
+    /// ```rust
+    /// use roundable::{RandomSource, StochasticRoundable, SECOND};
+    /// use std::time::Duration;
+    ///
+    /// # struct Xorshift(u64);
+    /// # impl RandomSource for Xorshift {
+    /// #     fn next_u64(&mut self) -> u64 {
+    /// #         self.0 ^= self.0 << 13;
+    /// #         self.0 ^= self.0 >> 7;
+    /// #         self.0 ^= self.0 << 17;
+    /// #         self.0
+    /// #     }
+    /// # }
+    /// let mut random = Xorshift(0x2545_f491_4f6c_dd1d);
+    ///
+    /// let rounded = (-14).round_stochastic_to(10, &mut random);
+    /// assert!(rounded == -10 || rounded == -20);
+    ///
+    /// let rounded = Duration::from_millis(1_500)
+    ///     .round_stochastic_to(SECOND, &mut random);
+    /// assert!(rounded == SECOND || rounded == 2 * SECOND);
+    ///
+    /// // Multiples of the factor are never changed.
+    /// assert!(30 == 30.round_stochastic_to(10, &mut random));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0, or if rounding
+    /// would return a value that does not fit in the return type.
+    #[must_use]
+    fn round_stochastic_to<R>(self, factor: Self, random: &mut R) -> Self
+    where
+        R: RandomSource + ?Sized,
+    {
+        unwrap_rounded(
+            self.checked_round_stochastic_to(factor, random),
+            "round_stochastic_to",
+        )
+    }
+
+    /// Round to one of the two nearest multiples of `factor` at random.
+    /// Returns `None` if the result does not fit in the type.
+    ///
+    /// ```rust
+    /// use roundable::{RandomSource, StochasticRoundable};
+    ///
+    /// # struct Xorshift(u64);
+    /// # impl RandomSource for Xorshift {
+    /// #     fn next_u64(&mut self) -> u64 {
+    /// #         self.0 ^= self.0 << 13;
+    /// #         self.0 ^= self.0 >> 7;
+    /// #         self.0 ^= self.0 << 17;
+    /// #         self.0
+    /// #     }
+    /// # }
+    /// let mut random = Xorshift(0x2545_f491_4f6c_dd1d);
+    ///
+    /// assert!(Some(250) == 250u8.try_round_stochastic_to(10, &mut random));
+    ///
+    /// // 255 always rounds to 260 eventually.
+    /// let rounded: Vec<_> = (0..100)
+    ///     .map(|_| 255u8.try_round_stochastic_to(10, &mut random))
+    ///     .collect();
+    /// assert!(rounded.contains(&None));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive, e.g. if it’s 0.
+    #[must_use]
+    fn try_round_stochastic_to<R>(
+        self,
+        factor: Self,
+        random: &mut R,
+    ) -> Option<Self>
+    where
+        R: RandomSource + ?Sized,
+    {
+        rounded_or_none(
+            self.checked_round_stochastic_to(factor, random),
+            "try_round_stochastic_to",
+        )
+    }
+
+    /// Round to one of the two nearest multiples of `factor` at random.
+    /// Returns a [`RoundError`] on failure.
+    ///
+    /// ```rust
+    /// use roundable::{RandomSource, RoundError, StochasticRoundable};
+    ///
+    /// # struct Xorshift(u64);
+    /// # impl RandomSource for Xorshift {
+    /// #     fn next_u64(&mut self) -> u64 {
+    /// #         self.0 ^= self.0 << 13;
+    /// #         self.0 ^= self.0 >> 7;
+    /// #         self.0 ^= self.0 << 17;
+    /// #         self.0
+    /// #     }
+    /// # }
+    /// let mut random = Xorshift(0x2545_f491_4f6c_dd1d);
+    ///
+    /// assert!(Ok(2.5) == 2.5.checked_round_stochastic_to(0.5, &mut random));
+    /// assert!(
+    ///     Err(RoundError::NonPositiveFactor)
+    ///         == 1.5.checked_round_stochastic_to(0.0, &mut random)
+    /// );
+    /// assert!(
+    ///     Err(RoundError::NanInput)
+    ///         == f64::NAN.checked_round_stochastic_to(1.0, &mut random)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Roundable::checked_round_to()`]: usually
+    /// [`RoundError::NonPositiveFactor`] if `factor` is not positive, or
+    /// [`RoundError::Overflow`] if the result does not fit in the type.
+    fn checked_round_stochastic_to<R>(
+        self,
+        factor: Self,
+        random: &mut R,
+    ) -> Result<Self, RoundError>
+    where
+        R: RandomSource + ?Sized;
+}
+
+/// Decide at random whether to round away from zero, with probability
+/// `distance / factor`.
+///
+/// `distance` is the distance from the multiple of `factor` toward zero, and
+/// must be less than `factor`. This draws a random number in `0..factor` with
+/// 64 bits (or 128 bits if `factor` doesn’t fit in 64 bits) of precision and
+/// checks whether it’s less than `distance`, so the probability is off by less
+/// than 2^-64 (or 2^-128).
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use stochastic::*`.
+pub(crate) fn is_away<R>(distance: u128, factor: u128, random: &mut R) -> bool
+where
+    R: RandomSource + ?Sized,
+{
+    if factor <= u128::from(u64::MAX) {
+        // Safe: both factors fit in 64 bits, so the product fits in 128 bits.
+        ((u128::from(random.next_u64()) * factor) >> 64) < distance
+    } else {
+        let high = u128::from(random.next_u64());
+        let low = u128::from(random.next_u64());
+        multiply_wide((high << 64) | low, factor).0 < distance
+    }
+}
+
+/// Decide at random whether to round away from zero, with probability
+/// `ratio`, which must be in `0.0..1.0`.
+///
+/// This is exact: it compares random bits to the binary digits of `ratio`, 64
+/// at a time, and only draws more while they are equal.
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::redundant_pub_crate)] // Keep it out of `pub use stochastic::*`.
+pub(crate) fn is_away_ratio<R>(ratio: f64, random: &mut R) -> bool
+where
+    R: RandomSource + ?Sized,
+{
+    // ratio = mantissa × 2^-shift. Since ratio < 1, shift ≥ 53.
+    let bits = ratio.to_bits();
+    let exponent = (bits >> 52) & 0x7ff;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, shift) = if exponent == 0 {
+        (fraction, 1074)
+    } else {
+        (fraction | (1 << 52), 1075 - exponent)
+    };
+
+    // Digits of ratio after the binary point that have been compared.
+    let mut end = 0;
+    loop {
+        end += 64;
+
+        // The next 64 digits of ratio: ⌊ratio × 2^end⌋ mod 2^64.
+        #[allow(clippy::cast_possible_truncation)]
+        let digits = if end < shift {
+            mantissa.checked_shr((shift - end) as u32).unwrap_or(0)
+        } else if end - shift < 64 {
+            // Truncation drops digits that were already compared.
+            (u128::from(mantissa) << (end - shift)) as u64
+        } else {
+            0
+        };
+
+        let next = random.next_u64();
+        if next != digits {
+            return next < digits;
+        }
+
+        // If ratio has no more digits, the random number is at least ratio.
+        if end >= shift || shift - end <= u64::from(mantissa.trailing_zeros()) {
+            return false;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::arithmetic_side_effects,
+    clippy::float_cmp,
+    clippy::integer_division
+)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use core::time::Duration;
+
+    /// Always returns the same value.
+    struct Constant(u64);
+
+    impl RandomSource for Constant {
+        fn next_u64(&mut self) -> u64 {
+            self.0
+        }
+    }
+
+    /// Returns values from a list in order, then repeats.
+    struct Sequence<'a>(&'a [u64], usize);
+
+    impl RandomSource for Sequence<'_> {
+        fn next_u64(&mut self) -> u64 {
+            let value = self.0[self.1 % self.0.len()];
+            self.1 += 1;
+            value
+        }
+    }
+
+    /// Generates pseudo-random `u64`s.
+    struct Xorshift(u64);
+
+    impl Xorshift {
+        /// Create a generator with a fixed seed.
+        const fn new() -> Self {
+            Self(0x2545_f491_4f6c_dd1d)
+        }
+    }
+
+    impl RandomSource for Xorshift {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    /// Always returns the lowest random number, so values always round away
+    /// from zero unless they are multiples of the factor.
+    const fn lowest() -> Constant {
+        Constant(0)
+    }
+
+    /// Always returns the highest random number, so values always round
+    /// toward zero.
+    const fn highest() -> Constant {
+        Constant(u64::MAX)
+    }
+
+    #[test]
+    fn is_away_exact_threshold() {
+        // Away iff the random number is in the lowest quarter.
+        check!(is_away(1, 4, &mut Constant((1 << 62) - 1)));
+        check!(!is_away(1, 4, &mut Constant(1 << 62)));
+        check!(!is_away(0, 4, &mut lowest()));
+        check!(is_away(3, 4, &mut Constant(u64::MAX - (1 << 62))));
+        check!(!is_away(3, 4, &mut Constant(u64::MAX - (1 << 62) + 1)));
+    }
+
+    #[test]
+    fn is_away_wide_exact_threshold() {
+        let factor = 1 << 100;
+        let distance = 1 << 98;
+        check!(is_away(
+            distance,
+            factor,
+            &mut Sequence(&[(1 << 62) - 1, u64::MAX], 0)
+        ));
+        check!(!is_away(distance, factor, &mut Sequence(&[1 << 62, 0], 0)));
+        check!(is_away(
+            factor - 1,
+            factor,
+            &mut Sequence(&[u64::MAX - 1], 0)
+        ));
+        check!(!is_away(factor - 1, factor, &mut highest()));
+    }
+
+    #[test]
+    fn is_away_ratio_bounds() {
+        check!(is_away_ratio(0.5, &mut lowest()));
+        check!(!is_away_ratio(0.0, &mut lowest()));
+        check!(!is_away_ratio(0.5, &mut highest()));
+        check!(is_away_ratio(0.5, &mut Constant((1 << 63) - 1)));
+        check!(!is_away_ratio(0.5, &mut Constant(1 << 63)));
+    }
+
+    #[test]
+    fn is_away_ratio_tiny() {
+        /// 2^20, to build ratios from powers of two.
+        const TWO_TO_20: f64 = 1_048_576.0;
+
+        // 2^-60 is 1 in the 60th digit after the binary point.
+        let ratio = 1.0 / TWO_TO_20 / TWO_TO_20 / TWO_TO_20;
+        check!(is_away_ratio(ratio, &mut Constant(15)));
+        check!(!is_away_ratio(ratio, &mut Sequence(&[16, 0], 0)));
+        check!(!is_away_ratio(ratio, &mut Constant(16)));
+
+        // 2^-100 is 1 in the 36th digit of the second 64.
+        let ratio = ratio / TWO_TO_20 / TWO_TO_20;
+        check!(!is_away_ratio(ratio, &mut Constant(1)));
+        check!(is_away_ratio(ratio, &mut Sequence(&[0, (1 << 28) - 1], 0)));
+        check!(!is_away_ratio(ratio, &mut Sequence(&[0, 1 << 28], 0)));
+
+        // The smallest subnormal, 2^-1074.
+        check!(is_away_ratio(f64::from_bits(1), &mut lowest()));
+        check!(!is_away_ratio(f64::from_bits(1), &mut Constant(1)));
+
+        // 2^-20 + 2^-70 has digits in the first and second 64.
+        let ratio = (1.0 + 1.0 / TWO_TO_20 / TWO_TO_20 / 1024.0) / TWO_TO_20;
+        check!(is_away_ratio(ratio, &mut Constant(1 << 44)));
+        check!(is_away_ratio(
+            ratio,
+            &mut Sequence(&[1 << 44, (1 << 58) - 1], 0)
+        ));
+        check!(!is_away_ratio(ratio, &mut Sequence(&[1 << 44, 1 << 58], 0)));
+        check!(!is_away_ratio(ratio, &mut Sequence(&[(1 << 44) + 1, 0], 0)));
+    }
+
+    #[test]
+    fn integer_extremes() {
+        check!(10 == 3.round_stochastic_to(10, &mut lowest()));
+        check!(0 == 3.round_stochastic_to(10, &mut highest()));
+        check!(-10 == (-3).round_stochastic_to(10, &mut lowest()));
+        check!(0 == (-3).round_stochastic_to(10, &mut highest()));
+        check!(10 == 19u8.round_stochastic_to(10, &mut highest()));
+        check!(20 == 11u8.round_stochastic_to(10, &mut lowest()));
+        check!(-10 == (-19i8).round_stochastic_to(10, &mut highest()));
+        check!(-20 == (-11i8).round_stochastic_to(10, &mut lowest()));
+    }
+
+    #[test]
+    fn integer_multiples_unchanged() {
+        let mut random = Xorshift::new();
+        for value in (-100..=100).step_by(5) {
+            check!(value == value.round_stochastic_to(5, &mut random));
+            check!(value == value.round_stochastic_to(5, &mut lowest()));
+        }
+        check!(0 == 0u128.round_stochastic_to(u128::MAX, &mut lowest()));
+        check!(
+            i128::MIN == i128::MIN.round_stochastic_to(1 << 100, &mut lowest())
+        );
+    }
+
+    #[test]
+    fn integer_results_are_adjacent_multiples() {
+        let mut random = Xorshift::new();
+        for value in -100i32..=100 {
+            for factor in 1..=12 {
+                let rounded = value.round_stochastic_to(factor, &mut random);
+                let down =
+                    value.round_directed_to(factor, crate::Direction::Down);
+                check!(rounded == down || rounded == down + factor);
+            }
+        }
+    }
+
+    #[test]
+    fn integer_is_unbiased() {
+        let mut random = Xorshift::new();
+        let count = (0..10_000)
+            .filter(|_| 3u32.round_stochastic_to(10, &mut random) == 10)
+            .count();
+        check!((2_750..3_250).contains(&count));
+
+        let count = (0..10_000)
+            .filter(|_| (-7i64).round_stochastic_to(10, &mut random) == -10)
+            .count();
+        check!((6_750..7_250).contains(&count));
+    }
+
+    #[test]
+    fn integer_wide_factor() {
+        let factor = u128::MAX / 3;
+        check!(0 == (factor / 2).round_stochastic_to(factor, &mut highest()));
+        check!(
+            factor == (factor / 2).round_stochastic_to(factor, &mut lowest())
+        );
+
+        let mut random = Xorshift::new();
+        let count = (0..10_000)
+            .filter(|_| {
+                (factor / 4).round_stochastic_to(factor, &mut random) != 0
+            })
+            .count();
+        check!((2_250..2_750).contains(&count));
+    }
+
+    #[test]
+    fn integer_errors() {
+        check!(
+            Ok(250) == 255u8.checked_round_stochastic_to(10, &mut highest())
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == 255u8.checked_round_stochastic_to(10, &mut lowest())
+        );
+        check!(
+            Err(RoundError::Overflow)
+                == i8::MIN.checked_round_stochastic_to(3, &mut lowest())
+        );
+        check!(None == i8::MAX.try_round_stochastic_to(10, &mut lowest()));
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 5.checked_round_stochastic_to(0, &mut lowest())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 5.checked_round_stochastic_to(-1, &mut lowest())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "round_stochastic_to() requires positive factor")]
+    fn integer_zero_factor() {
+        let _ = 5u32.round_stochastic_to(0, &mut lowest());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "try_round_stochastic_to() requires positive factor"
+    )]
+    fn integer_try_zero_factor() {
+        let _ = 5u32.try_round_stochastic_to(0, &mut lowest());
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn integer_overflow() {
+        let _ = 255u8.round_stochastic_to(10, &mut lowest());
+    }
+
+    #[test]
+    fn float_extremes() {
+        check!(1.0 == 0.25.round_stochastic_to(1.0, &mut lowest()));
+        check!(0.0 == 0.25.round_stochastic_to(1.0, &mut highest()));
+        check!(-1.0 == (-0.25).round_stochastic_to(1.0, &mut lowest()));
+        check!(0.0 == (-0.25f32).round_stochastic_to(1.0, &mut highest()));
+        check!(0.3 == 0.25.round_stochastic_to(0.1, &mut lowest()));
+        check!(0.2 == 0.25.round_stochastic_to(0.1, &mut highest()));
+        check!(2.5 == 2.5.round_stochastic_to(0.5, &mut lowest()));
+    }
+
+    #[test]
+    fn float_is_unbiased() {
+        let mut random = Xorshift::new();
+        let count = (0..10_000)
+            .filter(|_| 0.25.round_stochastic_to(1.0, &mut random) == 1.0)
+            .count();
+        check!((2_250..2_750).contains(&count));
+
+        let count = (0..10_000)
+            .filter(|_| (-1.8f32).round_stochastic_to(1.0, &mut random) == -2.0)
+            .count();
+        check!((7_750..8_250).contains(&count));
+    }
+
+    #[test]
+    fn float_errors() {
+        check!(
+            Err(RoundError::NanInput)
+                == f64::NAN.checked_round_stochastic_to(1.0, &mut lowest())
+        );
+        check!(
+            Err(RoundError::InfiniteInput)
+                == f32::INFINITY
+                    .checked_round_stochastic_to(1.0, &mut lowest())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == 1.0.checked_round_stochastic_to(f64::NAN, &mut lowest())
+        );
+        check!(
+            Err(RoundError::NonFiniteResult)
+                == f64::MAX.checked_round_stochastic_to(
+                    f64::MAX * 0.75,
+                    &mut lowest()
+                )
+        );
+        check!(0.0 == 1.0.round_stochastic_to(f64::INFINITY, &mut lowest()));
+    }
+
+    #[test]
+    fn duration() {
+        let value = Duration::from_millis(1_500);
+        let second = Duration::from_secs(1);
+        check!(
+            Duration::from_secs(2)
+                == value.round_stochastic_to(second, &mut lowest())
+        );
+        check!(second == value.round_stochastic_to(second, &mut highest()));
+        check!(
+            Err(RoundError::Overflow)
+                == Duration::MAX
+                    .checked_round_stochastic_to(second, &mut lowest())
+        );
+        check!(
+            Err(RoundError::NonPositiveFactor)
+                == value
+                    .checked_round_stochastic_to(Duration::ZERO, &mut lowest())
+        );
+    }
+
+    #[test]
+    fn dyn_random_source() {
+        let random: &mut dyn RandomSource = &mut Xorshift::new();
+        let rounded = 14.round_stochastic_to(10, random);
+        check!(rounded == 10 || rounded == 20);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn rand_core_rng() {
+        /// A generator that only implements [`rand_core::RngCore`].
+        struct Rng(Xorshift);
+
+        impl rand_core::RngCore for Rng {
+            #[allow(clippy::cast_possible_truncation)]
+            fn next_u32(&mut self) -> u32 {
+                self.0.next_u64() as u32
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                self.0.next_u64()
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest);
+            }
+
+            fn try_fill_bytes(
+                &mut self,
+                dest: &mut [u8],
+            ) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        let mut rng = Rng(Xorshift::new());
+        let count = (0..10_000)
+            .filter(|_| 3u32.round_stochastic_to(10, &mut rng) == 10)
+            .count();
+        check!((2_750..3_250).contains(&count));
+
+        check!(10 == 3u32.round_stochastic_to(10, &mut Rng(Xorshift(0))));
+    }
+}
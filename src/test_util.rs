@@ -0,0 +1,33 @@
+//! Fixtures shared by the tests.
+
+use crate::{Direction, Tie};
+
+/// Every [`Tie`].
+pub const TIES: [Tie; 6] = [
+    Tie::Up,
+    Tie::Down,
+    Tie::TowardZero,
+    Tie::AwayFromZero,
+    Tie::TowardEven,
+    Tie::TowardOdd,
+];
+
+/// Every [`Direction`].
+pub const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::TowardZero,
+    Direction::AwayFromZero,
+];
+
+/// Generate pseudo-random `u64`s.
+pub fn random() -> impl Iterator<Item = u64> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    core::iter::repeat_with(move || {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    })
+}
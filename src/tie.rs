@@ -0,0 +1,144 @@
+//! Tie strategies that are chosen at compile time.
+//!
+//! Each of these types works just like the [`Tie`] variant with the same name,
+//! but since the strategy is part of the type, the compiler can optimize
+//! rounding for it. See [`TieStrategy`].
+//!
+//! ```rust
+//! use roundable::{tie, Roundable, Tie};
+//!
+//! assert!(20 == 15.round_to(10, tie::TowardEven));
+//! assert!(Some(20) == 15.try_round_to(10, tie::TowardEven));
+//! assert!(15.round_to(10, Tie::TowardEven) == 15.round_to(10, tie::TowardEven));
+//! ```
+
+use crate::{Tie, TieStrategy};
+
+/// Implement a zero-sized tie strategy.
+macro_rules! tie_strategy {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident ($negative:ident, $is_base_odd:ident) => $away:expr;
+    )+) => {$(
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+        pub struct $name;
+
+        impl TieStrategy for $name {
+            #[inline]
+            #[allow(unused_variables)]
+            fn is_away_from_zero<F>(
+                self,
+                $negative: bool,
+                $is_base_odd: F,
+            ) -> bool
+            where
+                F: FnOnce() -> bool,
+            {
+                $away
+            }
+        }
+
+        impl From<$name> for Tie {
+            fn from(_: $name) -> Self {
+                Self::$name
+            }
+        }
+    )+}
+}
+
+tie_strategy! {
+    /// Round half up. See [`Tie::Up`].
+    Up(negative, is_base_odd) => !negative;
+
+    /// Round half down. See [`Tie::Down`].
+    Down(negative, is_base_odd) => negative;
+
+    /// Round half toward zero. See [`Tie::TowardZero`].
+    TowardZero(negative, is_base_odd) => false;
+
+    /// Round half away from zero. See [`Tie::AwayFromZero`].
+    AwayFromZero(negative, is_base_odd) => true;
+
+    /// Round half toward even. See [`Tie::TowardEven`].
+    TowardEven(negative, is_base_odd) => is_base_odd();
+
+    /// Round half toward odd. See [`Tie::TowardOdd`].
+    TowardOdd(negative, is_base_odd) => !is_base_odd();
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::Roundable;
+    use assert2::check;
+    use core::time::Duration;
+
+    /// Check that `strategy` rounds the same way as `tie` for every input.
+    fn check_strategy<S>(strategy: S, tie: Tie)
+    where
+        S: TieStrategy + Into<Tie> + core::fmt::Debug,
+    {
+        for negative in [false, true] {
+            for is_base_odd in [false, true] {
+                check!(
+                    tie.is_away_from_zero(negative, || is_base_odd)
+                        == strategy.is_away_from_zero(negative, || is_base_odd),
+                    "{:?} {} {}",
+                    strategy,
+                    negative,
+                    is_base_odd
+                );
+            }
+        }
+
+        for value in -30..=30 {
+            check!(
+                value.try_round_to(10, tie) == value.try_round_to(10, strategy),
+                "{:?} {}",
+                strategy,
+                value
+            );
+        }
+        for value in i8::MIN..=i8::MAX {
+            check!(
+                value.checked_round_to(4, tie)
+                    == value.checked_round_to(4, strategy),
+                "{:?} {}",
+                strategy,
+                value
+            );
+        }
+        for value in [-2.5, -1.5, -0.5, 0.5, 1.5, 2.5] {
+            check!(
+                value.round_to(1.0, tie) == value.round_to(1.0, strategy),
+                "{:?} {}",
+                strategy,
+                value
+            );
+        }
+        for millis in [500, 1_500, 2_500] {
+            let value = Duration::from_millis(millis);
+            check!(
+                value.round_to(crate::SECOND, tie)
+                    == value.round_to(crate::SECOND, strategy),
+                "{:?} {:?}",
+                strategy,
+                value
+            );
+        }
+
+        check!(tie == strategy.into());
+    }
+
+    #[test]
+    fn strategies_match_tie() {
+        check_strategy(Up, Tie::Up);
+        check_strategy(Down, Tie::Down);
+        check_strategy(TowardZero, Tie::TowardZero);
+        check_strategy(AwayFromZero, Tie::AwayFromZero);
+        check_strategy(TowardEven, Tie::TowardEven);
+        check_strategy(TowardOdd, Tie::TowardOdd);
+    }
+}